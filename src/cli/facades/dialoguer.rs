@@ -1,5 +1,5 @@
 use anyhow::Result;
-use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect};
 use grass::dev::RepositoryLocation;
 use thiserror::Error;
 
@@ -43,6 +43,15 @@ where
         .ok_or(DialoguerError::NothingSelected)?)
 }
 
+/// Ask the user a yes or no question, which defaults to no.
+pub fn confirm<T: Into<String>>(prompt: T) -> Result<bool> {
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(false)
+        .interact_opt()?
+        .unwrap_or(false))
+}
+
 pub fn select_category_and_repository(
     categories: &[RepositoryLocation],
 ) -> Result<&RepositoryLocation> {
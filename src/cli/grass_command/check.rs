@@ -1,4 +1,7 @@
+mod branches;
 mod changes;
+mod size;
+mod stashes;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -6,7 +9,10 @@ use grass::dev::{strategy::api::SupportsAll, Api};
 
 #[derive(Debug, Subcommand)]
 enum CheckSubCommand {
+    Branches(branches::BranchesCommand),
     Changes(changes::ChangesCommand),
+    Size(size::SizeCommand),
+    Stashes(stashes::StashesCommand),
 }
 
 #[derive(Parser, Debug)]
@@ -18,7 +24,10 @@ pub struct CheckCommand {
 impl CheckCommand {
     pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
         match &self.command {
+            CheckSubCommand::Branches(branches_command) => branches_command.handle(api)?,
             CheckSubCommand::Changes(changes_command) => changes_command.handle(api)?,
+            CheckSubCommand::Size(size_command) => size_command.handle(api)?,
+            CheckSubCommand::Stashes(stashes_command) => stashes_command.handle(api)?,
         };
 
         Ok(())
@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use grass::dev::{
+    delete_branch,
+    iterator::parallel::ParallelIterExtensions,
+    list_all_repositories, list_branches, list_repositories_in_category,
+    strategy::{api::SupportsAll, git::BranchInfo},
+    Api, RepositoryLocation,
+};
+use itertools::Itertools;
+
+use crate::{
+    error::CliError,
+    facades::dialoguer::confirm,
+    output::{format_age, generate_fancy_vertical_list},
+};
+
+#[derive(Parser, Debug)]
+/// Find branches which are merged, lost their upstream, or had no commits for a while
+///
+/// The default branch is never listed.
+pub struct BranchesCommand {
+    category: Option<String>,
+    #[clap(short, long)]
+    all: bool,
+    /// Branches without commits for this many days are stale
+    #[clap(long, default_value_t = 90)]
+    stale_days: u64,
+    /// Delete the merged branches, after confirmation
+    #[clap(long)]
+    prune: bool,
+}
+
+impl BranchesCommand {
+    fn get_findings(&self, branch: &BranchInfo) -> Vec<String> {
+        let max_age = Duration::from_secs(self.stale_days.saturating_mul(24 * 60 * 60));
+        let mut findings = Vec::new();
+
+        if branch.is_merged {
+            findings.push(String::from("merged"));
+        }
+        if branch.upstream_gone {
+            findings.push(String::from("upstream gone"));
+        }
+        if branch.is_stale(max_age) {
+            findings.push(format!("stale, last commit {}", format_age(branch.age())));
+        }
+
+        findings
+    }
+
+    fn format_branch(&self, branch: &BranchInfo) -> String {
+        format!(
+            "{}: {}",
+            branch,
+            self.get_findings(branch).join(", ").yellow()
+        )
+    }
+
+    fn prune<T: SupportsAll>(
+        api: &Api<T>,
+        branches: &[(RepositoryLocation, BranchInfo)],
+    ) -> Result<()> {
+        if branches.is_empty() {
+            eprintln!("No merged branches to delete");
+            return Ok(());
+        }
+
+        if !confirm(format!("Delete {} merged branches?", branches.len()))? {
+            return Ok(());
+        }
+
+        let results: Vec<_> = branches
+            .iter()
+            .map(|(repository, branch)| {
+                let result = delete_branch(api, repository.clone(), &branch.branch, false);
+                match result {
+                    Ok(()) => format!(
+                        "{}: {}",
+                        repository,
+                        format!("Deleted {}", branch.branch).green()
+                    ),
+                    Err(error) => format!(
+                        "{}: {}",
+                        repository,
+                        format!(
+                            "Failed to delete {}, {}",
+                            branch.branch,
+                            error.to_string().lines().join(" ")
+                        )
+                        .red()
+                    ),
+                }
+            })
+            .collect();
+
+        eprintln!(
+            "{}",
+            generate_fancy_vertical_list("Pruned branches", results)
+        );
+        Ok(())
+    }
+
+    pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
+        let mut repositories: Vec<RepositoryLocation> = match self {
+            Self {
+                category: Some(category),
+                all: false,
+                ..
+            } => list_repositories_in_category(api, category)?,
+            Self {
+                category: None,
+                all: true,
+                ..
+            } => list_all_repositories(api)?,
+            Self {
+                category: Some(_),
+                all: true,
+                ..
+            } => return Err(CliError::new("Received incompatible category with all flag").into()),
+            Self {
+                category: None,
+                all: false,
+                ..
+            } => return Err(CliError::new("Provide either a category, or the --all flag").into()),
+        };
+
+        repositories.sort();
+
+        let results: Vec<_> = repositories
+            .into_iter()
+            .parallel_map(api, |repository| {
+                let branches = list_branches(api, repository.clone());
+                (repository, branches)
+            })
+            .collect();
+
+        let mut num_reported = 0;
+        let mut merged = Vec::new();
+        for (repository, branches) in results {
+            let branches: Vec<_> = match branches {
+                Ok(branches) => branches
+                    .into_iter()
+                    .filter(|branch| !branch.is_default && !self.get_findings(branch).is_empty())
+                    .collect(),
+                Err(error) => {
+                    eprintln!(
+                        "{}\n",
+                        format!(
+                            "Could not list the branches of {}, {}",
+                            repository,
+                            error.to_string().lines().join(" ")
+                        )
+                        .red()
+                    );
+                    continue;
+                }
+            };
+
+            if branches.is_empty() {
+                continue;
+            }
+
+            num_reported += 1;
+            eprintln!(
+                "{}\n",
+                generate_fancy_vertical_list(
+                    repository.to_string(),
+                    branches.iter().map(|branch| self.format_branch(branch)),
+                )
+            );
+
+            merged.extend(
+                branches
+                    .into_iter()
+                    .filter(|branch| branch.is_merged && !branch.is_current)
+                    .map(|branch| (repository.clone(), branch)),
+            );
+        }
+
+        if num_reported == 0 {
+            eprintln!("No branches to clean up");
+        }
+
+        if self.prune {
+            Self::prune(api, &merged)?;
+        }
+
+        Ok(())
+    }
+}
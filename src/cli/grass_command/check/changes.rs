@@ -1,16 +1,22 @@
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
+use colored::Colorize;
 use grass::dev::{
+    get_repository_change_details,
     iterator::{
         iter::{IterExtensions, TakeUntilIteratorItem},
         location::LocationIterExtensions,
         location_and_change_status::LocationAndChangeStatusIterExtensions,
     },
     list_all_repositories, list_repositories_in_category,
-    strategy::{api::SupportsAll, git::RepositoryChangeStatus},
+    strategy::{
+        api::SupportsAll,
+        git::{FileChange, GitOperation, RepositoryChangeStatus},
+    },
     Api, RepositoryLocation,
 };
 use itertools::Itertools;
+use tracing::warn;
 
 use crate::error::CliError;
 
@@ -28,31 +34,82 @@ pub struct ChangesCommand {
     all: bool,
     #[clap(long)]
     format: Option<Format>,
+    /// List the changed files for each repository
+    #[clap(long)]
+    detailed: bool,
 }
 
+type RepositoryChanges = (RepositoryLocation, RepositoryChangeStatus, Vec<FileChange>);
+
 impl ChangesCommand {
-    fn display_plain(&self, repositories: &[(RepositoryLocation, RepositoryChangeStatus)]) {
-        for (location, change_status) in repositories.iter() {
+    fn display_plain(&self, repositories: &[RepositoryChanges]) {
+        for (location, change_status, files) in repositories.iter() {
             let change_status = match change_status {
                 RepositoryChangeStatus::UpToDate => String::from("up_to_date"),
                 RepositoryChangeStatus::NoRepository => String::from("no_repository"),
+                RepositoryChangeStatus::OperationInProgress { operation, .. } => {
+                    let operation = match operation {
+                        GitOperation::Rebase => "rebase",
+                        GitOperation::Merge => "merge",
+                        GitOperation::CherryPick => "cherry_pick",
+                        GitOperation::Revert => "revert",
+                        GitOperation::Bisect => "bisect",
+                    };
+                    format!("{}_in_progress", operation)
+                }
+                RepositoryChangeStatus::Conflicts { .. } => String::from("conflicts"),
                 RepositoryChangeStatus::UncommittedChanges { num_changes } => {
                     num_changes.to_string()
                 }
+                RepositoryChangeStatus::SubmoduleChanges { .. } => {
+                    String::from("submodule_changes")
+                }
+                RepositoryChangeStatus::MissingLfsObjects { .. } => {
+                    String::from("missing_lfs_objects")
+                }
+                RepositoryChangeStatus::StaleStashes { .. } => String::from("stale_stashes"),
+                RepositoryChangeStatus::UnpushedWork { .. } => String::from("unpushed_work"),
                 RepositoryChangeStatus::Unknown => String::from("unknown_status"),
             };
 
             println!(
                 "{} {} {}",
                 location.category, location.repository, change_status
-            )
+            );
+
+            for file in files {
+                println!("{} {} {}", location.category, location.repository, file);
+            }
         }
     }
 
-    fn display_pretty(&self, repositories: &[(RepositoryLocation, RepositoryChangeStatus)]) {
+    /// Repositories which are stuck in an operation, or have conflicts, are highlighted.
+    fn format_change_status_pretty(change_status: &RepositoryChangeStatus) -> String {
+        match change_status {
+            RepositoryChangeStatus::OperationInProgress { .. }
+            | RepositoryChangeStatus::Conflicts { .. } => {
+                change_status.to_string().red().bold().to_string()
+            }
+            _ => change_status.to_string(),
+        }
+    }
+
+    fn format_files_pretty(indent: &str, files: &[FileChange]) -> String {
+        files
+            .iter()
+            .cloned()
+            .mark_end(1)
+            .map(|file| match file {
+                TakeUntilIteratorItem::Start(file) => format!("\n{}  ├─ {}", indent, file),
+                TakeUntilIteratorItem::End(file) => format!("\n{}  └─ {}", indent, file),
+            })
+            .join("")
+    }
+
+    fn display_pretty(&self, repositories: &[RepositoryChanges]) {
         let result = (&repositories
             .iter()
-            .chunk_by(|(location, _)| &location.category))
+            .chunk_by(|(location, _, _)| &location.category))
             .into_iter()
             .map(|(category, repositories)| {
                 let mut repositories = repositories.cloned();
@@ -61,11 +118,23 @@ impl ChangesCommand {
                         TakeUntilIteratorItem::Start((
                             RepositoryLocation { repository, .. },
                             change_status,
-                        )) => format!("\n├─ {}: {}", repository, change_status),
+                            files,
+                        )) => format!(
+                            "\n├─ {}: {}{}",
+                            repository,
+                            Self::format_change_status_pretty(&change_status),
+                            Self::format_files_pretty("│", &files)
+                        ),
                         TakeUntilIteratorItem::End((
                             RepositoryLocation { repository, .. },
                             change_status,
-                        )) => format!("\n└─ {}: {}", repository, change_status),
+                            files,
+                        )) => format!(
+                            "\n└─ {}: {}{}",
+                            repository,
+                            Self::format_change_status_pretty(&change_status),
+                            Self::format_files_pretty(" ", &files)
+                        ),
                     });
 
                 format!("┌── {}\n│{}\n", category, repositories.join(""))
@@ -115,6 +184,32 @@ impl ChangesCommand {
 
         repositories.sort();
 
+        let repositories: Box<[RepositoryChanges]> = repositories
+            .into_vec()
+            .into_iter()
+            .map(|(location, change_status)| {
+                let files = match (self.detailed, &change_status) {
+                    (
+                        true,
+                        RepositoryChangeStatus::UncommittedChanges { .. }
+                        | RepositoryChangeStatus::SubmoduleChanges { .. }
+                        | RepositoryChangeStatus::Conflicts { .. }
+                        | RepositoryChangeStatus::OperationInProgress { .. },
+                    ) => get_repository_change_details(api, location.clone()).unwrap_or_else(
+                        |error| {
+                            warn!(
+                                "Could not get the changed files of repository {}\nReason:\n{}",
+                                location, error
+                            );
+                            Vec::new()
+                        },
+                    ),
+                    _ => Vec::new(),
+                };
+                (location, change_status, files)
+            })
+            .collect();
+
         match self.format {
             Some(Format::Simple) => self.display_plain(repositories.as_ref()),
             _ => self.display_pretty(repositories.as_ref()),
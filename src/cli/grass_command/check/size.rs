@@ -0,0 +1,141 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use grass::dev::{
+    get_repository_size,
+    iterator::parallel::ParallelIterExtensions,
+    list_all_repositories, list_repositories_in_category,
+    strategy::{api::SupportsAll, git::RepositorySize},
+    Api, RepositoryLocation,
+};
+use itertools::Itertools;
+
+use crate::{
+    error::CliError,
+    output::{format_bytes, generate_fancy_vertical_list},
+};
+
+#[derive(Parser, Debug)]
+/// Show the disk usage of every repository in a category
+///
+/// Besides the total size, shows the size of the '.git' directory,
+/// and the size of the ignored files which 'grass repo clean' would remove.
+/// Repositories are sorted by size, largest first.
+pub struct SizeCommand {
+    category: Option<String>,
+    #[clap(short, long)]
+    all: bool,
+}
+
+impl SizeCommand {
+    fn format_size(repository: &str, size: &RepositorySize, name_width: usize) -> String {
+        let reclaimable = format!("{} reclaimable", format_bytes(size.reclaimable_bytes));
+        let reclaimable = match size.reclaimable_bytes {
+            0 => reclaimable.dimmed(),
+            _ => reclaimable.yellow(),
+        };
+        format!(
+            "{:name_width$}  {:>10}  ({} git, {})",
+            repository,
+            format_bytes(size.total_bytes),
+            format_bytes(size.git_bytes),
+            reclaimable
+        )
+    }
+
+    pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
+        let repositories: Vec<RepositoryLocation> = match self {
+            Self {
+                category: Some(category),
+                all: false,
+            } => list_repositories_in_category(api, category)?,
+            Self {
+                category: None,
+                all: true,
+            } => list_all_repositories(api)?,
+            Self {
+                category: Some(_),
+                all: true,
+            } => return Err(CliError::new("Received incompatible category with all flag").into()),
+            Self {
+                category: None,
+                all: false,
+            } => return Err(CliError::new("Provide either a category, or the --all flag").into()),
+        };
+
+        let mut results: Vec<_> = repositories
+            .into_iter()
+            .parallel_map(api, |repository| {
+                let size = get_repository_size(api, repository.clone());
+                (repository, size)
+            })
+            .collect();
+
+        // Largest first within each category, repositories with errors last.
+        results.sort_by(|(left, left_size), (right, right_size)| {
+            let left_size = left_size.as_ref().map(|size| size.total_bytes).ok();
+            let right_size = right_size.as_ref().map(|size| size.total_bytes).ok();
+            left.category
+                .cmp(&right.category)
+                .then(right_size.cmp(&left_size))
+                .then(left.repository.cmp(&right.repository))
+        });
+
+        if results.is_empty() {
+            eprintln!("No repositories found");
+            return Ok(());
+        }
+
+        let name_width = results
+            .iter()
+            .map(|(location, _)| location.repository.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut total = RepositorySize::default();
+        for (category, results) in &results.iter().chunk_by(|(location, _)| &location.category) {
+            let mut category_total = 0;
+            let items: Vec<_> = results
+                .map(|(location, size)| match size {
+                    Ok(size) => {
+                        category_total += size.total_bytes;
+                        total.total_bytes += size.total_bytes;
+                        total.reclaimable_bytes += size.reclaimable_bytes;
+                        total.git_bytes += size.git_bytes;
+                        Self::format_size(&location.repository, size, name_width)
+                    }
+                    Err(error) => format!(
+                        "{:name_width$}  {}",
+                        location.repository,
+                        format!(
+                            "Could not compute the size, {}",
+                            error.to_string().lines().join(" ")
+                        )
+                        .red()
+                    ),
+                })
+                .collect();
+
+            eprintln!(
+                "{}\n",
+                generate_fancy_vertical_list(
+                    format!(
+                        "Size of category '{}' ({})",
+                        category,
+                        format_bytes(category_total)
+                    ),
+                    items,
+                )
+            );
+        }
+
+        eprintln!(
+            "Total {}, of which {} git and {} reclaimable",
+            format_bytes(total.total_bytes),
+            format_bytes(total.git_bytes),
+            format_bytes(total.reclaimable_bytes)
+        );
+
+        Ok(())
+    }
+}
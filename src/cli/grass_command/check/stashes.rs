@@ -0,0 +1,104 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use grass::dev::{
+    get_stale_stash_age,
+    iterator::parallel::ParallelIterExtensions,
+    list_all_repositories, list_repositories_in_category, list_stashes,
+    strategy::{api::SupportsAll, git::Stash},
+    Api, RepositoryLocation,
+};
+use itertools::Itertools;
+
+use crate::{
+    error::CliError,
+    output::{format_age, generate_fancy_vertical_list},
+};
+
+#[derive(Parser, Debug)]
+/// List the stashes of every repository in a category
+///
+/// Stashes older than the configured 'stale_stash_days' are highlighted.
+pub struct StashesCommand {
+    category: Option<String>,
+    #[clap(short, long)]
+    all: bool,
+}
+
+impl StashesCommand {
+    fn format_stash(stash: &Stash, is_stale: bool) -> String {
+        let age = format_age(stash.age());
+        let age = match is_stale {
+            true => age.yellow().to_string(),
+            false => age,
+        };
+        format!("stash@{{{}}} ({}): {}", stash.index, age, stash.message)
+    }
+
+    pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
+        let mut repositories: Vec<RepositoryLocation> = match self {
+            Self {
+                category: Some(category),
+                all: false,
+            } => list_repositories_in_category(api, category)?,
+            Self {
+                category: None,
+                all: true,
+            } => list_all_repositories(api)?,
+            Self {
+                category: Some(_),
+                all: true,
+            } => return Err(CliError::new("Received incompatible category with all flag").into()),
+            Self {
+                category: None,
+                all: false,
+            } => return Err(CliError::new("Provide either a category, or the --all flag").into()),
+        };
+
+        repositories.sort();
+
+        let max_age = get_stale_stash_age(api);
+        let results: Vec<_> = repositories
+            .into_iter()
+            .parallel_map(api, |repository| {
+                let stashes = list_stashes(api, repository.clone());
+                (repository, stashes)
+            })
+            .collect();
+
+        let mut num_stashes = 0;
+        for (repository, stashes) in results.iter() {
+            match stashes {
+                Ok(stashes) if stashes.is_empty() => (),
+                Ok(stashes) => {
+                    num_stashes += stashes.len();
+                    eprintln!(
+                        "{}\n",
+                        generate_fancy_vertical_list(
+                            repository.to_string(),
+                            stashes.iter().map(|stash| Self::format_stash(
+                                stash,
+                                max_age.is_some_and(|max_age| stash.is_stale(max_age))
+                            )),
+                        )
+                    );
+                }
+                Err(error) => eprintln!(
+                    "{}\n",
+                    format!(
+                        "Could not list the stashes of {}, {}",
+                        repository,
+                        error.to_string().lines().join(" ")
+                    )
+                    .red()
+                ),
+            }
+        }
+
+        if num_stashes == 0 {
+            eprintln!("No stashes found");
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use grass::dev::{strategy::api::SupportsAll, Api};
 
+mod adopt;
 mod explain;
 mod list;
 
@@ -9,6 +10,7 @@ mod list;
 pub enum ConfigSubcommand {
     List(list::ListCommand),
     Explain(explain::ExplainCommand),
+    Adopt(adopt::AdoptCommand),
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -25,6 +27,7 @@ impl ConfigCommand {
         match &self.command {
             ConfigSubcommand::List(command) => command.handle(api),
             ConfigSubcommand::Explain(command) => command.handle(api),
+            ConfigSubcommand::Adopt(command) => command.handle(api),
         }
     }
 }
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use clap::Parser;
+use grass::dev::{
+    config::{add_categories_to_user_config, load_user_config},
+    strategy::api::SupportsAll,
+    Api,
+};
+
+#[derive(Parser, Debug, Clone)]
+/// Declare the undeclared categories in the configuration file
+///
+/// Every directory in the base directory which is not used by a category is added,
+/// even when 'discover_categories' is off.
+/// The categories are appended to 'config.toml', keeping the existing content.
+pub struct AdoptCommand;
+
+impl AdoptCommand {
+    pub fn handle<T>(&self, _api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        let categories = load_user_config()?.grass.list_undeclared_categories();
+
+        if categories.is_empty() {
+            eprintln!("No undeclared categories found");
+            return Ok(());
+        }
+
+        let file = add_categories_to_user_config(&categories)?;
+        for category in &categories {
+            eprintln!("Adopted category '{}'", category);
+        }
+        eprintln!("Written to '{}'", file.display());
+
+        Ok(())
+    }
+}
@@ -6,10 +6,52 @@ use grass::dev::{strategy::api::SupportsAll, Api};
 enum ConfigKey {
     #[value(name = "base_dir")]
     Basedir,
+    #[value(name = "workers")]
+    Workers,
+    #[value(name = "git_backend")]
+    GitBackend,
+    #[value(name = "stale_stash_days")]
+    StaleStashDays,
+    #[value(name = "default_branch")]
+    DefaultBranch,
+    #[value(name = "recurse_submodules")]
+    RecurseSubmodules,
+    #[value(name = "discover_categories")]
+    DiscoverCategories,
     #[value(name = "category.*")]
     CategoryKey,
     #[value(name = "category.*.alias")]
     CategoryKeyAlias,
+    #[value(name = "category.*.clean_profiles")]
+    CategoryKeyCleanProfiles,
+    #[value(name = "category.*.template")]
+    CategoryKeyTemplate,
+    #[value(name = "category.*.remote_template")]
+    CategoryKeyRemoteTemplate,
+    #[value(name = "category.*.discovery_depth")]
+    CategoryKeyDiscoveryDepth,
+    #[value(name = "category.*.path")]
+    CategoryKeyPath,
+    #[value(name = "category.*.discovery_filter.require_git")]
+    CategoryKeyDiscoveryFilterRequireGit,
+    #[value(name = "category.*.discovery_filter.include")]
+    CategoryKeyDiscoveryFilterInclude,
+    #[value(name = "category.*.discovery_filter.exclude")]
+    CategoryKeyDiscoveryFilterExclude,
+    #[value(name = "category.*.discovery_filter.ignore_hidden")]
+    CategoryKeyDiscoveryFilterIgnoreHidden,
+    #[value(name = "category.*.auth.ssh_key")]
+    CategoryKeyAuthSshKey,
+    #[value(name = "category.*.auth.ssh_command")]
+    CategoryKeyAuthSshCommand,
+    #[value(name = "category.*.auth.credential_helper")]
+    CategoryKeyAuthCredentialHelper,
+    #[value(name = "category.*.auth.token_env")]
+    CategoryKeyAuthTokenEnv,
+    #[value(name = "category.*.auth.token_username")]
+    CategoryKeyAuthTokenUsername,
+    #[value(name = "clean_profile.*.patterns")]
+    CleanProfileKeyPatterns,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -24,8 +66,75 @@ impl ExplainCommand {
     {
         match self.config_key {
             ConfigKey::Basedir => eprintln!("The base directory where repositories are stored"),
+            ConfigKey::Workers => {
+                eprintln!("How many repositories are processed at the same time, defaults to the number of CPUs")
+            }
+            ConfigKey::GitBackend => {
+                eprintln!("How git is accessed, either 'cli' (default) or 'native', which requires the native-git feature")
+            }
+            ConfigKey::StaleStashDays => {
+                eprintln!("Stashes older than this many days count as a change, off if not set")
+            }
+            ConfigKey::DefaultBranch => {
+                eprintln!(
+                    "Name of the initial branch of new repositories, the git default if not set"
+                )
+            }
+            ConfigKey::RecurseSubmodules => {
+                eprintln!("Count changed files inside submodules as changes, off if not set")
+            }
+            ConfigKey::DiscoverCategories => {
+                eprintln!("Use undeclared directories in the base directory as categories, off if not set")
+            }
             ConfigKey::CategoryKey => eprintln!("The name of a category"),
             ConfigKey::CategoryKeyAlias => eprintln!("Aliases for the category"),
+            ConfigKey::CategoryKeyCleanProfiles => {
+                eprintln!("Clean profiles to use for the category, all ignored files if empty")
+            }
+            ConfigKey::CategoryKeyTemplate => {
+                eprintln!("Directory copied into new repositories, its 'hooks' directory goes to '.git/hooks'")
+            }
+            ConfigKey::CategoryKeyRemoteTemplate => {
+                eprintln!(
+                    "Remote URL of repositories, like 'git@github.com:my-org/{{repository}}.git'"
+                )
+            }
+            ConfigKey::CategoryKeyDiscoveryDepth => {
+                eprintln!("How many directory levels hold repositories, like 2 for 'team/repo', 1 if not set")
+            }
+            ConfigKey::CategoryKeyPath => {
+                eprintln!("Directory of the repositories, like '$SECURE_VOLUME/src', '<base_dir>/<category>' if not set")
+            }
+            ConfigKey::CategoryKeyDiscoveryFilterRequireGit => {
+                eprintln!("Only list directories containing '.git' as repositories, off if not set")
+            }
+            ConfigKey::CategoryKeyDiscoveryFilterInclude => {
+                eprintln!("Only list repositories matching one of these patterns, like 'team/*'")
+            }
+            ConfigKey::CategoryKeyDiscoveryFilterExclude => {
+                eprintln!("Do not list repositories matching one of these patterns, like 'archive'")
+            }
+            ConfigKey::CategoryKeyDiscoveryFilterIgnoreHidden => {
+                eprintln!("Do not list directories starting with '.', off if not set")
+            }
+            ConfigKey::CategoryKeyAuthSshKey => {
+                eprintln!("The SSH key used for remotes of the category")
+            }
+            ConfigKey::CategoryKeyAuthSshCommand => {
+                eprintln!("The SSH command used for remotes of the category, overrides ssh_key")
+            }
+            ConfigKey::CategoryKeyAuthCredentialHelper => {
+                eprintln!("The git credential helper used for remotes of the category")
+            }
+            ConfigKey::CategoryKeyAuthTokenEnv => {
+                eprintln!("An environment variable with a token, used as password over HTTPS")
+            }
+            ConfigKey::CategoryKeyAuthTokenUsername => {
+                eprintln!("The username used together with the token, 'git' if not set")
+            }
+            ConfigKey::CleanProfileKeyPatterns => {
+                eprintln!("Ignored files to remove when cleaning, like 'node_modules/' or '*.pyc'")
+            }
         };
         Ok(())
     }
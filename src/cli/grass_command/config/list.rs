@@ -14,9 +14,42 @@ impl ListCommand {
         eprintln!();
 
         eprintln!("base_dir => Home directory where repositories are stored");
+        eprintln!("workers => Number of repositories processed at the same time");
+        eprintln!("git_backend => Use the git command line tool, or the native library");
+        eprintln!("stale_stash_days => Age in days at which stashes count as a change");
+        eprintln!("default_branch => Initial branch of new repositories");
+        eprintln!("recurse_submodules => Look inside submodules for changes");
+        eprintln!("discover_categories => Use undeclared directories as categories");
 
         eprintln!("category.[category_name] => A category of repositories");
         eprintln!("category.[category_name].[[alias]] => An alias to the category");
+        eprintln!(
+            "category.[category_name].[[clean_profiles]] => Clean profiles used for the category"
+        );
+        eprintln!("category.[category_name].template => Directory copied into new repositories");
+        eprintln!("category.[category_name].remote_template => Remote URL of the repositories");
+        eprintln!("category.[category_name].path => Directory of the repositories");
+        eprintln!("category.[category_name].discovery_depth => Directory levels of repositories");
+        eprintln!(
+            "category.[category_name].discovery_filter => Which directories are repositories"
+        );
+        eprintln!("category.[category_name].discovery_filter.require_git => Require '.git'");
+        eprintln!("category.[category_name].discovery_filter.[[include]] => Patterns to list");
+        eprintln!("category.[category_name].discovery_filter.[[exclude]] => Patterns to skip");
+        eprintln!(
+            "category.[category_name].discovery_filter.ignore_hidden => Skip hidden directories"
+        );
+        eprintln!("category.[category_name].auth => Authentication used for remotes");
+        eprintln!("category.[category_name].auth.ssh_key => SSH key to use");
+        eprintln!("category.[category_name].auth.ssh_command => SSH command to use");
+        eprintln!("category.[category_name].auth.credential_helper => Credential helper to use");
+        eprintln!("category.[category_name].auth.token_env => Environment variable with a token");
+        eprintln!("category.[category_name].auth.token_username => Username for the token");
+
+        eprintln!("clean_profile.[profile_name] => A named set of patterns used when cleaning");
+        eprintln!(
+            "clean_profile.[profile_name].[[patterns]] => Patterns of ignored files to remove"
+        );
         Ok(())
     }
 }
@@ -1,13 +1,27 @@
 use clap::{Parser, ValueEnum};
-use grass::dev::{strategy::api::SupportsAll, Api, RepositoryLocation};
+use colored::Colorize;
+use grass::dev::{
+    get_repository_metadata,
+    iterator::parallel::ParallelIterExtensions,
+    strategy::{
+        api::SupportsAll,
+        git::{GitStrategyError, HeadState, RepositoryMetadata},
+    },
+    Api, RepositoryLocation,
+};
 use itertools::Itertools;
 
 use crate::{
     cli_result::{CliOutput, CliResult},
     error::CliError,
-    output::generate_fancy_vertical_list,
+    output::{format_age, generate_fancy_vertical_list},
 };
 
+type RepositoryWithMetadata = (
+    RepositoryLocation,
+    Result<RepositoryMetadata, GitStrategyError>,
+);
+
 #[derive(ValueEnum, Debug, Clone, Default)]
 enum Format {
     #[default]
@@ -30,6 +44,11 @@ pub struct LsCommand {
     all: bool,
     #[clap(long)]
     format: Option<Format>,
+    /// Show the checked out branch and the last commit of each repository
+    ///
+    /// Requires a category, or the --all flag.
+    #[clap(short, long)]
+    long: bool,
 }
 
 impl LsCommand {
@@ -68,7 +87,7 @@ impl LsCommand {
         for location in categories
             .sorted()
             .map(Some)
-            .chain([None].into_iter())
+            .chain([None])
             .tuple_windows::<(_, _)>()
         {
             let (
@@ -113,6 +132,108 @@ impl LsCommand {
         }
     }
 
+    fn format_repository_long(
+        (location, metadata): &RepositoryWithMetadata,
+        name_width: usize,
+        head_width: usize,
+    ) -> String {
+        let metadata = match metadata {
+            Ok(metadata) => metadata,
+            Err(_) => return format!("{:name_width$}  {}", location.repository, "unknown".red()),
+        };
+
+        let head = format!("{:head_width$}", metadata.head.to_string());
+        let head = match metadata.head {
+            HeadState::Branch(_) => head.green(),
+            HeadState::Detached(_) | HeadState::Unborn(_) => head.yellow(),
+        };
+
+        match &metadata.last_commit {
+            Some(commit) => format!(
+                "{:name_width$}  {}  {} ({})",
+                location.repository,
+                head,
+                commit.subject,
+                format_age(commit.age()).dimmed()
+            ),
+            None => format!("{:name_width$}  {}", location.repository, head),
+        }
+    }
+
+    fn generate_output_long<T: SupportsAll>(
+        api: &Api<T>,
+        mut repositories: Vec<RepositoryLocation>,
+        format: &Format,
+    ) -> CliOutput {
+        repositories.sort();
+
+        let repositories: Vec<RepositoryWithMetadata> = repositories
+            .into_iter()
+            .parallel_map(api, |repository| {
+                let metadata = get_repository_metadata(api, repository.clone());
+                (repository, metadata)
+            })
+            .collect();
+
+        if let Format::Simple = format {
+            return CliOutput::Stdout(
+                repositories
+                    .iter()
+                    .map(|(location, metadata)| {
+                        let location = format!("{}/{}", location.category, location.repository);
+                        match metadata {
+                            Ok(RepositoryMetadata {
+                                head,
+                                last_commit: Some(commit),
+                            }) => {
+                                let head = match head {
+                                    HeadState::Branch(branch) | HeadState::Unborn(branch) => branch,
+                                    HeadState::Detached(_) => "HEAD",
+                                };
+                                format!("{} {} {} {}", location, head, commit.id, commit.subject)
+                            }
+                            Ok(RepositoryMetadata {
+                                head: HeadState::Branch(branch) | HeadState::Unborn(branch),
+                                last_commit: None,
+                            }) => format!("{} {} -", location, branch),
+                            _ => format!("{} unknown", location),
+                        }
+                    })
+                    .join("\n")
+                    .into(),
+            );
+        }
+
+        let name_width = repositories
+            .iter()
+            .map(|(location, _)| location.repository.len())
+            .max()
+            .unwrap_or_default();
+        let head_width = repositories
+            .iter()
+            .filter_map(|(_, metadata)| metadata.as_ref().ok())
+            .map(|metadata| metadata.head.to_string().len())
+            .max()
+            .unwrap_or_default();
+
+        CliOutput::Stderr(
+            (&repositories
+                .iter()
+                .chunk_by(|(location, _)| &location.category))
+                .into_iter()
+                .map(|(category, repositories)| {
+                    generate_fancy_vertical_list(
+                        format!("Repositories for category '{}'", category),
+                        repositories.map(|repository| {
+                            Self::format_repository_long(repository, name_width, head_width)
+                        }),
+                    )
+                })
+                .join("\n\n")
+                .into(),
+        )
+    }
+
     fn generate_output_category_name_only<'a, T>(categories: T, format: &Format) -> CliOutput
     where
         T: IntoIterator<Item = &'a String>,
@@ -130,6 +251,7 @@ impl LsCommand {
             LsCommand {
                 category: None,
                 all: false,
+                long: false,
                 format,
             } => Self::generate_output_category_name_only(
                 grass::dev::list_categories::<_, Vec<_>>(api)?.iter(),
@@ -138,6 +260,17 @@ impl LsCommand {
             LsCommand {
                 category: Some(category),
                 all: false,
+                long: true,
+                format,
+            } => Self::generate_output_long(
+                api,
+                grass::dev::list_repositories_in_category(api, category)?,
+                &format.clone().unwrap_or_default(),
+            ),
+            LsCommand {
+                category: Some(category),
+                all: false,
+                long: false,
                 format,
             } => Self::generate_output_repositories_for_category(
                 category.clone(),
@@ -147,6 +280,17 @@ impl LsCommand {
             LsCommand {
                 category: None,
                 all: true,
+                long: true,
+                format,
+            } => Self::generate_output_long(
+                api,
+                grass::dev::list_all_repositories(api)?,
+                &format.clone().unwrap_or_default(),
+            ),
+            LsCommand {
+                category: None,
+                all: true,
+                long: false,
                 format,
             } => Self::generate_output_all_repositories(
                 &format.clone().unwrap_or_default(),
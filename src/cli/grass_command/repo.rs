@@ -1,7 +1,12 @@
 mod clean;
 mod clone;
+mod commit;
 mod create;
+mod fork;
+mod mv;
 mod rename;
+mod sync;
+mod worktree;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -11,8 +16,13 @@ use grass::dev::{strategy::api::SupportsAll, Api};
 pub enum RepoSubcommand {
     Clean(clean::CleanCommand),
     Clone(clone::CloneCommand),
+    Commit(commit::CommitCommand),
     Create(create::CreateCommand),
+    Fork(fork::ForkCommand),
+    Mv(mv::MvCommand),
     Rename(rename::RenameCommand),
+    Sync(sync::SyncCommand),
+    Worktree(worktree::WorktreeCommand),
 }
 
 #[derive(Parser, Debug)]
@@ -29,8 +39,13 @@ impl RepoCommand {
         match &self.command {
             RepoSubcommand::Clean(command) => command.handle(api),
             RepoSubcommand::Clone(command) => command.handle(api),
+            RepoSubcommand::Commit(command) => command.handle(api),
             RepoSubcommand::Create(command) => command.handle(api),
+            RepoSubcommand::Fork(command) => command.handle(api),
+            RepoSubcommand::Mv(command) => command.handle(api),
             RepoSubcommand::Rename(command) => command.handle(api),
+            RepoSubcommand::Sync(command) => command.handle(api),
+            RepoSubcommand::Worktree(command) => command.handle(api),
         }
     }
 }
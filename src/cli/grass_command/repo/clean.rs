@@ -1,8 +1,15 @@
 use anyhow::Result;
 use clap::Parser;
 use grass::dev::{strategy::api::SupportsAll, Api};
+use tracing::info;
+
+use crate::output::format_bytes;
 
 #[derive(Parser, Debug)]
+/// Remove ignored files from a repository
+///
+/// Only files ignored by git are removed.
+/// When clean profiles are configured for the category, only matching files are removed.
 pub struct CleanCommand {
     category: String,
     repository: String,
@@ -13,9 +20,18 @@ impl CleanCommand {
     where
         T: SupportsAll,
     {
-        Ok(grass::dev::clean_repository(
-            api,
-            (&self.category, &self.repository),
-        )?)
+        let report = grass::dev::clean_repository(api, (&self.category, &self.repository))?;
+
+        for file in &report.removed_files {
+            info!("Removed '{}'", file.display());
+        }
+
+        eprintln!(
+            "Removed {} file(s), freeing {}",
+            report.removed_files.len(),
+            format_bytes(report.freed_bytes)
+        );
+
+        Ok(())
     }
 }
@@ -1,13 +1,41 @@
 use anyhow::Result;
 use clap::Parser;
-use grass::dev::{strategy::api::SupportsAll, Api};
+use grass::dev::{
+    strategy::{api::SupportsAll, git::CloneOptions},
+    Api,
+};
 
-use crate::facades::dialoguer::select_selectable;
+use crate::{error::CliError, facades::dialoguer::select_selectable};
 
 #[derive(Parser, Debug)]
+/// Clone a repository from a remote
+///
+/// Use either 'clone <REMOTE> [CATEGORY]', or 'clone <CATEGORY> <REPOSITORY>'.
+/// The second form expands the repository name, using the remote template of the category.
+/// A remote without ':' or '/' is considered a category.
 pub struct CloneCommand {
+    /// The remote URL, or the category when cloning by name
     remote: String,
+    /// The category, or the name of the repository when cloning by name
     category: Option<String>,
+    /// Name of the repository, generated from the remote if not set
+    #[clap(short, long)]
+    name: Option<String>,
+    /// Branch to check out, instead of the default branch of the remote
+    #[clap(short, long)]
+    branch: Option<String>,
+    /// Only fetch this many commits of history
+    #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+    depth: Option<u32>,
+    /// Object filter for a partial clone, like 'blob:none'
+    #[clap(long)]
+    filter: Option<String>,
+    /// Clone submodules as well
+    #[clap(long)]
+    recurse_submodules: bool,
+    /// Check out Git LFS files as pointers, without downloading their content
+    #[clap(long)]
+    skip_lfs: bool,
 }
 
 impl CloneCommand {
@@ -15,15 +43,41 @@ impl CloneCommand {
     where
         T: SupportsAll,
     {
-        let category = self.category.clone().map_or_else(
-            || {
-                select_selectable(grass::dev::list_categories::<_, Vec<_>>(api)?.as_slice())
-                    .cloned()
-            },
-            Ok,
-        )?;
+        let is_name = !self.remote.contains([':', '/', '\\']);
+        let (category, remote) = match (is_name, &self.category) {
+            (true, Some(repository)) => (self.remote.clone(), repository.clone()),
+            (true, None) => {
+                return Err(CliError::new(format!(
+                    "Provide the name of a repository to clone into the category '{}', or a full remote URL",
+                    self.remote
+                ))
+                .into())
+            }
+            (false, Some(category)) => (category.clone(), self.remote.clone()),
+            (false, None) => (
+                select_selectable(grass::dev::list_categories::<_, Vec<_>>(api)?.as_slice())?
+                    .clone(),
+                self.remote.clone(),
+            ),
+        };
 
-        grass::dev::clone_repository_default(api, category, &self.remote)?;
+        let options = CloneOptions {
+            branch: self.branch.clone(),
+            depth: self.depth,
+            filter: self.filter.clone(),
+            recurse_submodules: self.recurse_submodules,
+            skip_lfs_smudge: self.skip_lfs,
+        };
+
+        match &self.name {
+            Some(name) => grass::dev::clone_repository(
+                api,
+                (category.as_str(), name.as_str()),
+                &remote,
+                &options,
+            )?,
+            None => grass::dev::clone_repository_default(api, category, &remote, &options)?,
+        };
         Ok(())
     }
 }
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use grass::dev::{
+    commit_repository, list_repositories_with_uncommitted_changes_in_category, push_repository,
+    strategy::{api::SupportsAll, git::RepositoryChangeStatusWithError},
+    Api, ChangeStatusResult, RepositoryLocation,
+};
+use itertools::Itertools;
+
+use crate::{facades::dialoguer::confirm, output::generate_fancy_vertical_list};
+
+#[derive(Parser, Debug)]
+/// Stage every change in a repository, commit them, and optionally push
+///
+/// Without a repository, every repository in the category with uncommitted changes is offered,
+/// asking for each one whether it should be committed.
+pub struct CommitCommand {
+    category: String,
+    repository: Option<String>,
+    /// The commit message
+    #[clap(short, long)]
+    message: String,
+    /// Push the current branch after committing
+    #[clap(long)]
+    push: bool,
+}
+
+impl CommitCommand {
+    fn commit<T: SupportsAll>(&self, api: &Api<T>, repository: RepositoryLocation) -> Result<()> {
+        commit_repository(api, repository.clone(), &self.message)?;
+        if self.push {
+            push_repository(api, repository)?;
+        }
+        Ok(())
+    }
+
+    fn format_result(&self, repository: &RepositoryLocation, result: &Result<()>) -> String {
+        match result {
+            Ok(()) if self.push => format!("{}: {}", repository, "Committed and pushed".green()),
+            Ok(()) => format!("{}: {}", repository, "Committed".green()),
+            Err(error) => format!(
+                "{}: {}",
+                repository,
+                format!("Failed, {}", error.to_string().lines().join(" ")).red()
+            ),
+        }
+    }
+
+    pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
+        if let Some(repository) = &self.repository {
+            let repository =
+                RepositoryLocation::from((self.category.as_str(), repository.as_str()));
+            self.commit(api, repository.clone())?;
+            eprintln!("{}", self.format_result(&repository, &Ok(())));
+            return Ok(());
+        }
+
+        let mut repositories: Vec<_> = list_repositories_with_uncommitted_changes_in_category::<
+            _,
+            _,
+            Vec<_>,
+        >(api, self.category.as_str())?
+        .into_iter()
+        .filter_map(|result| match result {
+            ChangeStatusResult {
+                location: Some(location),
+                change_status: RepositoryChangeStatusWithError::UncommittedChanges { num_changes },
+            } => Some((location, num_changes)),
+            _ => None,
+        })
+        .collect();
+
+        if repositories.is_empty() {
+            eprintln!("No repositories with uncommitted changes");
+            return Ok(());
+        }
+
+        repositories.sort();
+
+        let mut results = Vec::new();
+        for (repository, num_changes) in repositories {
+            if confirm(format!(
+                "Commit {} change(s) in {}?",
+                num_changes, repository
+            ))? {
+                let result = self.commit(api, repository.clone());
+                results.push(self.format_result(&repository, &result));
+            }
+        }
+
+        if !results.is_empty() {
+            eprintln!(
+                "{}",
+                generate_fancy_vertical_list(format!("{} repositories", results.len()), results)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,27 @@
 use anyhow::Result;
 use clap::Parser;
-use grass::dev::{strategy::api::SupportsAll, Api};
+use grass::dev::{
+    strategy::{api::SupportsAll, git::InitOptions},
+    Api,
+};
 
 #[derive(Parser, Debug)]
+/// Create a directory for a new repository
+///
+/// With --init, a git repository is initialized,
+/// and the template of the category is copied into it.
 pub struct CreateCommand {
     category: String,
     repository: String,
+    /// Initialize a git repository
+    #[clap(long)]
+    init: bool,
+    /// Name of the initial branch, instead of the configured default branch
+    #[clap(short, long, requires = "init")]
+    branch: Option<String>,
+    /// Add an 'origin' remote, using the remote template of the category
+    #[clap(long, requires = "init")]
+    remote: bool,
 }
 
 impl CreateCommand {
@@ -16,6 +32,14 @@ impl CreateCommand {
         let location: (&str, &str) = (self.category.as_ref(), self.repository.as_ref());
         grass::dev::create_repository(api, location)?;
 
+        if self.init {
+            let options = InitOptions {
+                branch: self.branch.clone(),
+                add_remote: self.remote,
+            };
+            grass::dev::init_repository(api, location, &options)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::Parser;
+use grass::dev::{strategy::api::SupportsAll, Api};
+
+#[derive(Parser, Debug)]
+/// Fork a repository from a remote, to the remote of a category
+///
+/// The remote is cloned, and renamed to 'upstream'.
+/// A new 'origin' is added, using the remote template of the category.
+pub struct ForkCommand {
+    /// The remote URL of the repository to fork
+    remote: String,
+    /// The category to fork into, can be an alias
+    category: String,
+    /// Name of the repository, generated from the remote if not set
+    name: Option<String>,
+    /// Push the current branch to the new 'origin', which has to exist already
+    #[clap(long)]
+    push: bool,
+}
+
+impl ForkCommand {
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        match &self.name {
+            Some(name) => grass::dev::fork_repository(
+                api,
+                (self.category.as_str(), name.as_str()),
+                &self.remote,
+                self.push,
+            )?,
+            None => {
+                grass::dev::fork_repository_default(api, &self.category, &self.remote, self.push)?
+            }
+        };
+        Ok(())
+    }
+}
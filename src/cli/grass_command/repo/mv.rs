@@ -0,0 +1,136 @@
+use std::process::Command as ProcessCommand;
+
+use anyhow::Result;
+use clap::Parser;
+use grass::dev::{
+    strategy::{api::SupportsAll, git::RepositoryChangeStatus},
+    Api, RepositoryLocation,
+};
+use tracing::info;
+
+use crate::error::CliError;
+
+#[derive(Parser, Debug)]
+/// Move a repository to another category
+///
+/// Open tmux sessions of the repository are renamed to the new location.
+/// Repositories with linked worktrees cannot be moved, remove the worktrees first.
+pub struct MvCommand {
+    from_category: String,
+    repository: String,
+    to_category: String,
+    /// Name of the repository in the new category, defaults to the current name
+    new_name: Option<String>,
+    /// Also move the repository when it has uncommitted changes
+    #[clap(short, long)]
+    force: bool,
+}
+
+/// Get the new name of a tmux session, if it belongs to the old location.
+///
+/// Worktree sessions are named 'repository+branch@category',
+/// where the branch never contains '+' or '@', see 'grass session create'.
+fn get_new_session_name(
+    session: &str,
+    old_location: &RepositoryLocation,
+    new_location: &RepositoryLocation,
+) -> Option<String> {
+    let suffix = session
+        .strip_prefix(old_location.repository.as_str())?
+        .strip_suffix(format!("@{}", old_location.category).as_str())?;
+    let is_session = match suffix.strip_prefix('+') {
+        Some(branch) => !branch.is_empty() && !branch.contains(['+', '@']),
+        None => suffix.is_empty(),
+    };
+    if !is_session {
+        return None;
+    }
+
+    Some(format!(
+        "{}{}@{}",
+        new_location.repository, suffix, new_location.category
+    ))
+}
+
+/// Rename the tmux sessions of the old location, this is skipped if tmux is not running.
+fn rename_sessions_tmux(old_location: &RepositoryLocation, new_location: &RepositoryLocation) {
+    let Ok(output) = ProcessCommand::new("tmux")
+        .args(["list-sessions", "-F", "#S"])
+        .output()
+    else {
+        return;
+    };
+
+    for session in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some(new_session) = get_new_session_name(session, old_location, new_location) else {
+            continue;
+        };
+
+        let renamed = ProcessCommand::new("tmux")
+            .args([
+                "rename-session",
+                "-t",
+                &format!("={}", session),
+                &new_session,
+            ])
+            .status()
+            .is_ok_and(|status| status.success());
+
+        if renamed {
+            info!("Renamed tmux session '{}' to '{}'", session, new_session);
+        } else {
+            eprintln!("Could not rename tmux session '{}'", session);
+        }
+    }
+}
+
+impl MvCommand {
+    fn check_committed<T: SupportsAll>(api: &Api<T>, location: &RepositoryLocation) -> Result<()> {
+        let status = grass::dev::get_repository_change_status(api, location.clone())?;
+        let has_uncommitted_changes = matches!(
+            status,
+            RepositoryChangeStatus::UncommittedChanges { .. }
+                | RepositoryChangeStatus::OperationInProgress { .. }
+                | RepositoryChangeStatus::Conflicts { .. }
+                | RepositoryChangeStatus::SubmoduleChanges { .. }
+        );
+        if has_uncommitted_changes {
+            return Err(CliError::new(format!(
+                "Repository '{}' has uncommitted changes: {}, use --force to move it anyway",
+                location, status
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        let old_location = grass::dev::resolve_repository_alias(
+            api,
+            RepositoryLocation::from((self.from_category.as_str(), self.repository.as_str())),
+        )?;
+        let new_name = match &self.new_name {
+            Some(new_name) => new_name.as_str(),
+            None => old_location.repository.as_str(),
+        };
+        let new_location = grass::dev::resolve_repository_alias(
+            api,
+            RepositoryLocation::from((self.to_category.as_str(), new_name)),
+        )?;
+
+        if !self.force {
+            Self::check_committed(api, &old_location)?;
+        }
+
+        grass::dev::move_repository(api, old_location.clone(), new_location.clone())?;
+        rename_sessions_tmux(&old_location, &new_location);
+
+        eprintln!("Moved '{}' to '{}'", old_location, new_location);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use colored::Colorize;
+use grass::dev::{
+    get_repository_change_status,
+    iterator::parallel::ParallelIterExtensions,
+    list_all_repositories, list_repositories_in_category, pull_repository,
+    strategy::{
+        api::SupportsAll,
+        git::{PullMode, PullResult, RepositoryChangeStatus},
+    },
+    Api, RepositoryLocation,
+};
+use itertools::Itertools;
+
+use crate::{error::CliError, output::generate_fancy_vertical_list};
+
+#[derive(ValueEnum, Debug, Clone, Default)]
+enum Mode {
+    #[default]
+    FastForward,
+    Merge,
+    Rebase,
+}
+
+impl From<&Mode> for PullMode {
+    fn from(value: &Mode) -> Self {
+        match value {
+            Mode::FastForward => PullMode::FastForwardOnly,
+            Mode::Merge => PullMode::Merge,
+            Mode::Rebase => PullMode::Rebase,
+        }
+    }
+}
+
+enum SyncOutcome {
+    Pulled(PullResult),
+    Skipped(RepositoryChangeStatus),
+    Failed(String),
+}
+
+#[derive(Parser, Debug)]
+/// Fetch and pull every repository in a category
+///
+/// Repositories with uncommitted changes, conflicts, or an unfinished operation are skipped.
+pub struct SyncCommand {
+    category: Option<String>,
+    #[clap(short, long)]
+    all: bool,
+    /// How to update a branch which has diverged from its upstream
+    #[clap(long)]
+    mode: Option<Mode>,
+}
+
+impl SyncCommand {
+    fn sync_repository<T: SupportsAll>(
+        &self,
+        api: &Api<T>,
+        repository: RepositoryLocation,
+    ) -> SyncOutcome {
+        let change_status = match get_repository_change_status(api, repository.clone()) {
+            Ok(change_status) => change_status,
+            Err(error) => return SyncOutcome::Failed(error.to_string()),
+        };
+
+        if matches!(
+            change_status,
+            RepositoryChangeStatus::NoRepository
+                | RepositoryChangeStatus::UncommittedChanges { .. }
+                | RepositoryChangeStatus::Conflicts { .. }
+                | RepositoryChangeStatus::OperationInProgress { .. }
+        ) {
+            return SyncOutcome::Skipped(change_status);
+        }
+
+        let mode = self.mode.as_ref().map(PullMode::from).unwrap_or_default();
+        match pull_repository(api, repository, mode) {
+            Ok(result) => SyncOutcome::Pulled(result),
+            Err(error) => SyncOutcome::Failed(error.to_string()),
+        }
+    }
+
+    fn format_outcome(repository: &RepositoryLocation, outcome: &SyncOutcome) -> String {
+        match outcome {
+            SyncOutcome::Pulled(result @ PullResult::Updated { .. }) => {
+                format!("{}: {}", repository, result.to_string().green())
+            }
+            SyncOutcome::Pulled(result) => format!("{}: {}", repository, result),
+            SyncOutcome::Skipped(change_status) => format!(
+                "{}: {}",
+                repository,
+                format!("Skipped, {}", change_status).yellow()
+            ),
+            SyncOutcome::Failed(reason) => format!(
+                "{}: {}",
+                repository,
+                format!("Failed, {}", reason.lines().join(" ")).red()
+            ),
+        }
+    }
+
+    pub fn handle<T: SupportsAll>(&self, api: &Api<T>) -> Result<()> {
+        let mut repositories: Vec<RepositoryLocation> = match self {
+            Self {
+                category: Some(category),
+                all: false,
+                ..
+            } => list_repositories_in_category(api, category)?,
+            Self {
+                category: None,
+                all: true,
+                ..
+            } => list_all_repositories(api)?,
+            Self {
+                category: Some(_),
+                all: true,
+                ..
+            } => return Err(CliError::new("Received incompatible category with all flag").into()),
+            Self {
+                category: None,
+                all: false,
+                ..
+            } => return Err(CliError::new("Provide either a category, or the --all flag").into()),
+        };
+
+        repositories.sort();
+
+        let outcomes: Vec<_> = repositories
+            .into_iter()
+            .parallel_map(api, |repository| {
+                let outcome = self.sync_repository(api, repository.clone());
+                (repository, outcome)
+            })
+            .collect();
+
+        let num_updated = outcomes
+            .iter()
+            .filter(|(_, outcome)| {
+                matches!(outcome, SyncOutcome::Pulled(PullResult::Updated { .. }))
+            })
+            .count();
+        let num_skipped = outcomes
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, SyncOutcome::Skipped(_)))
+            .count();
+        let num_failed = outcomes
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, SyncOutcome::Failed(_)))
+            .count();
+
+        eprintln!(
+            "{}",
+            generate_fancy_vertical_list(
+                format!(
+                    "{} updated, {} unchanged, {} skipped, {} failed",
+                    num_updated,
+                    outcomes.len() - num_updated - num_skipped - num_failed,
+                    num_skipped,
+                    num_failed
+                ),
+                outcomes
+                    .iter()
+                    .map(|(repository, outcome)| Self::format_outcome(repository, outcome)),
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+mod add;
+mod ls;
+mod rm;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use grass::dev::{strategy::api::SupportsAll, Api};
+
+#[derive(Debug, Subcommand)]
+pub enum WorktreeSubcommand {
+    Add(add::AddCommand),
+    Ls(ls::LsCommand),
+    Rm(rm::RmCommand),
+}
+
+#[derive(Parser, Debug)]
+/// Manage the worktrees of a repository
+pub struct WorktreeCommand {
+    #[command(subcommand)]
+    command: WorktreeSubcommand,
+}
+
+impl WorktreeCommand {
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        match &self.command {
+            WorktreeSubcommand::Add(command) => command.handle(api),
+            WorktreeSubcommand::Ls(command) => command.handle(api),
+            WorktreeSubcommand::Rm(command) => command.handle(api),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use clap::Parser;
+use grass::dev::{strategy::api::SupportsAll, Api};
+
+#[derive(Parser, Debug)]
+/// Add a worktree with a branch checked out
+///
+/// The branch is created when it does not exist yet,
+/// tracking the remote branch with the same name if there is one.
+pub struct AddCommand {
+    category: String,
+    repository: String,
+    branch: String,
+}
+
+impl AddCommand {
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        let worktree = (
+            self.category.as_str(),
+            self.repository.as_str(),
+            self.branch.as_str(),
+        );
+
+        grass::dev::add_worktree(api, worktree)?;
+
+        let path = grass::dev::get_worktree_path(api, worktree)?;
+        eprintln!("Added worktree for '{}' at {}", self.branch, path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use grass::dev::{strategy::api::SupportsAll, Api, RepositoryLocation};
+
+use crate::output::generate_fancy_vertical_list;
+
+#[derive(Parser, Debug)]
+/// List the worktrees of a repository
+pub struct LsCommand {
+    category: String,
+    repository: String,
+}
+
+impl LsCommand {
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        let repository = grass::dev::resolve_repository_alias(
+            api,
+            RepositoryLocation::new(self.category.as_str(), self.repository.as_str()),
+        )?;
+        let worktrees = grass::dev::list_worktrees(api, repository.clone())?;
+
+        eprintln!(
+            "{}",
+            generate_fancy_vertical_list(
+                format!("Worktrees of {}", repository),
+                worktrees.iter().map(|worktree| {
+                    let branch = worktree.branch.as_deref().unwrap_or("(detached HEAD)");
+                    match worktree.is_main {
+                        true => format!("{} {}", branch.green(), "(main)".dimmed()),
+                        false => format!(
+                            "{} {}",
+                            branch.green(),
+                            worktree.path.display().to_string().dimmed()
+                        ),
+                    }
+                })
+            )
+        );
+
+        Ok(())
+    }
+}
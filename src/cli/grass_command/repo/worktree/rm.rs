@@ -0,0 +1,37 @@
+use anyhow::Result;
+use clap::Parser;
+use grass::dev::{strategy::api::SupportsAll, Api};
+
+#[derive(Parser, Debug)]
+/// Remove the worktree which has a branch checked out
+///
+/// The branch itself is kept.
+pub struct RmCommand {
+    category: String,
+    repository: String,
+    branch: String,
+    /// Also remove the worktree when it has uncommitted changes
+    #[clap(short, long)]
+    force: bool,
+}
+
+impl RmCommand {
+    pub fn handle<T>(&self, api: &Api<T>) -> Result<()>
+    where
+        T: SupportsAll,
+    {
+        grass::dev::remove_worktree(
+            api,
+            (
+                self.category.as_str(),
+                self.repository.as_str(),
+                self.branch.as_str(),
+            ),
+            self.force,
+        )?;
+
+        eprintln!("Removed worktree for '{}'", self.branch);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
-use grass::dev::{strategy::api::SupportsAll, Api, RepositoryLocation};
+use grass::dev::{strategy::api::SupportsAll, Api, RepositoryLocation, WorktreeLocation};
 
 use std::process::{Child, Command as ProcessCommand};
 
@@ -22,6 +22,9 @@ pub struct CreateCommand {
     repository: Option<String>,
     #[clap(short)]
     target: Option<Multiplexer>,
+    /// Open the worktree which has this branch checked out, instead of the repository
+    #[clap(short, long)]
+    worktree: Option<String>,
 }
 
 fn session_command_tmux(name: &str) -> Result<Child, CliError> {
@@ -36,10 +39,43 @@ fn session_command_zellij(_name: &str) -> Result<Child, CliError> {
 }
 
 impl CreateCommand {
+    fn get_session_string<T: SupportsAll>(
+        api: &Api<T>,
+        repository_location: RepositoryLocation,
+        worktree: Option<&str>,
+    ) -> Result<String> {
+        let Some(branch) = worktree else {
+            return Ok(repository_location.to_session_string());
+        };
+
+        // Session names are 'repository+branch@category', which must be parsed back later.
+        if branch.contains(['+', '@']) {
+            return Err(CliError::new(format!(
+                "Cannot open a session for the branch '{}', because it contains '+' or '@'",
+                branch
+            ))
+            .into());
+        }
+
+        let has_worktree = grass::dev::list_worktrees(api, repository_location.clone())?
+            .iter()
+            .any(|worktree| !worktree.is_main && worktree.branch.as_deref() == Some(branch));
+        if !has_worktree {
+            return Err(CliError::new(format!(
+                "No worktree has the branch '{}' checked out, add one with 'grass repo worktree add'",
+                branch
+            ))
+            .into());
+        }
+
+        Ok(WorktreeLocation::new(repository_location, branch).to_session_string())
+    }
+
     fn create_session<T, U, V>(
         api: &Api<T>,
         category: U,
         repository: V,
+        worktree: Option<&str>,
         target: Multiplexer,
     ) -> Result<()>
     where
@@ -51,23 +87,17 @@ impl CreateCommand {
             api,
             RepositoryLocation::from((category.as_ref(), repository.as_ref())),
         )?;
+        let session_string = Self::get_session_string(api, repository_location, worktree)?;
 
         let mut child = match target {
-            Multiplexer::Tmux => {
-                session_command_tmux(repository_location.to_session_string().as_str())
-            }
-            Multiplexer::Zellij => {
-                session_command_zellij(repository_location.to_session_string().as_str())
-            }
+            Multiplexer::Tmux => session_command_tmux(session_string.as_str()),
+            Multiplexer::Zellij => session_command_zellij(session_string.as_str()),
         }?;
 
         match child.wait() {
             Ok(status) => {
                 if let Some(0) = status.code() {
-                    eprintln!(
-                        "Opened tmux session {}",
-                        &repository_location.to_session_string()
-                    );
+                    eprintln!("Opened tmux session {}", &session_string);
                 } else {
                     eprintln!("Issue starting session");
                 };
@@ -80,6 +110,7 @@ impl CreateCommand {
     fn select_repository<T: SupportsAll>(
         api: &Api<T>,
         category: &String,
+        worktree: Option<&str>,
         target: Multiplexer,
     ) -> Result<()> {
         let repositories: Vec<_> = grass::dev::list_repositories_in_category(api, category)?;
@@ -90,16 +121,26 @@ impl CreateCommand {
 
         let repository = select_selectable(&repositories).unwrap();
 
-        Self::create_session(api, category, repository, target)?;
+        Self::create_session(api, category, repository, worktree, target)?;
         Ok(())
     }
 
-    fn select_category<T: SupportsAll>(api: &Api<T>, target: Multiplexer) -> Result<()> {
+    fn select_category<T: SupportsAll>(
+        api: &Api<T>,
+        worktree: Option<&str>,
+        target: Multiplexer,
+    ) -> Result<()> {
         let categories: Vec<_> = grass::dev::list_all_repositories(api)?;
 
         let repository = select_category_and_repository(categories.as_slice())
             .context("When running the command 'grass session create'")?;
-        Self::create_session(api, &repository.category, &repository.repository, target)?;
+        Self::create_session(
+            api,
+            &repository.category,
+            &repository.repository,
+            worktree,
+            target,
+        )?;
         Ok(())
     }
 
@@ -109,19 +150,29 @@ impl CreateCommand {
                 category: Some(category),
                 repository: Some(repository),
                 target,
+                worktree,
             } => Self::create_session(
                 api,
                 category,
                 repository,
+                worktree.as_deref(),
                 target.clone().unwrap_or_default(),
             )?,
             CreateCommand {
                 category: Some(category),
                 repository: None,
                 target,
-            } => Self::select_repository(api, category, target.clone().unwrap_or_default())?,
-            CreateCommand { target, .. } => {
-                Self::select_category(api, target.clone().unwrap_or_default())?
+                worktree,
+            } => Self::select_repository(
+                api,
+                category,
+                worktree.as_deref(),
+                target.clone().unwrap_or_default(),
+            )?,
+            CreateCommand {
+                target, worktree, ..
+            } => {
+                Self::select_category(api, worktree.as_deref(), target.clone().unwrap_or_default())?
             }
         };
         Ok(())
@@ -1,9 +1,12 @@
-use std::{env, process::Command as ProcessCommand, str};
+use std::{env, path::PathBuf, process::Command as ProcessCommand, str};
 
 use anyhow::Result;
 use clap::{CommandFactory, Parser, ValueEnum};
 use clap_complete::{shells::Bash, Generator};
-use grass::dev::{strategy::api::SupportsAll, Api};
+use grass::dev::{
+    strategy::{api::SupportsAll, discovery::DiscoveryExists},
+    Api, RepositoryLocation,
+};
 
 use crate::error::CliError;
 
@@ -35,6 +38,56 @@ impl ShellInsertCommand {
         println!("{}", String::from_utf8(buf).unwrap_or_default());
     }
 
+    /// Get the path of a tmux session, which is named 'repository@category'.
+    ///
+    /// Worktree sessions are named 'repository+branch@category', see 'grass session create'.
+    /// Repository names may contain '+' as well, so these are only used when the worktree exists.
+    fn get_session_path<T: SupportsAll>(
+        api: &Api<T>,
+        category: &str,
+        repository: &str,
+    ) -> Result<PathBuf> {
+        let worktree = repository
+            .rsplit_once('+')
+            .and_then(|(repository, branch)| {
+                Self::find_worktree_path(api, RepositoryLocation::new(category, repository), branch)
+            });
+
+        match worktree {
+            Some(path) => Ok(path),
+            None => Ok(grass::dev::get_repository_path_next(
+                api,
+                (category, repository),
+            )?),
+        }
+    }
+
+    /// Find the worktree which has the branch of a session checked out.
+    ///
+    /// Tmux replaces '.' and ':' in session names with '_', so branches are compared the same way.
+    fn find_worktree_path<T: SupportsAll>(
+        api: &Api<T>,
+        repository: RepositoryLocation,
+        branch: &str,
+    ) -> Option<PathBuf> {
+        let exists = grass::dev::verify_repository_exists(api, repository.clone());
+        if branch.is_empty() || exists != Ok(DiscoveryExists::Exists) {
+            return None;
+        }
+
+        grass::dev::list_worktrees(api, repository)
+            .ok()?
+            .into_iter()
+            .find(|worktree| {
+                !worktree.is_main
+                    && worktree
+                        .branch
+                        .as_deref()
+                        .is_some_and(|name| name.replace(['.', ':'], "_") == branch)
+            })
+            .map(|worktree| worktree.path)
+    }
+
     fn handle_bash<T: SupportsAll>(api: &Api<T>) -> Result<()> {
         Self::print_shell_complete(Bash);
 
@@ -49,7 +102,7 @@ impl ShellInsertCommand {
                 if let [repository, category] =
                     output.trim().split('@').collect::<Vec<_>>().as_slice()
                 {
-                    let path = grass::dev::get_repository_path_next(api, (*category, *repository))?;
+                    let path = Self::get_session_path(api, category, repository)?;
                     let path = path
                         .to_str()
                         .ok_or(CliError::new("Could not convert repository path to str"))?;
@@ -69,3 +122,102 @@ impl ShellInsertCommand {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs, path::Path, sync::Arc, sync::RwLock};
+
+    use grass::dev::{
+        config::{GrassCategory, GrassConfig},
+        strategy::{
+            alias::LocalAliasStrategy, api::LocalApiStrategy, discovery::LocalDiscoveryStrategy,
+            git::ConfiguredGitStrategy, path::LocalPathStrategy,
+        },
+    };
+
+    use super::*;
+
+    fn git(directory: &Path, args: &[&str]) {
+        let status = ProcessCommand::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "grass")
+            .env("GIT_AUTHOR_EMAIL", "grass@example.com")
+            .env("GIT_COMMITTER_NAME", "grass")
+            .env("GIT_COMMITTER_EMAIL", "grass@example.com")
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn with_api(root: &Path, test: impl FnOnce(&Api<LocalApiStrategy<'_>>)) {
+        let category = Arc::new(RwLock::new(GrassCategory {
+            name: String::from("cat"),
+            ..Default::default()
+        }));
+        let config = GrassConfig {
+            category: HashMap::from([(String::from("cat"), category)]),
+            aliases: HashMap::new(),
+            base_dir: root.to_path_buf(),
+            ..GrassConfig::try_default().unwrap()
+        };
+        let alias_strategy = LocalAliasStrategy::new(&config);
+        let path_strategy = LocalPathStrategy::new(&config);
+        let discovery_strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+        let git_strategy = ConfiguredGitStrategy::new(&config, &path_strategy);
+
+        test(&Api::from(LocalApiStrategy::new(
+            &config,
+            &alias_strategy,
+            &discovery_strategy,
+            &git_strategy,
+            &path_strategy,
+        )));
+    }
+
+    #[test]
+    fn test_session_path_with_plus() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        fs::create_dir_all(root.join("cat/foo+bar/.git")).unwrap();
+
+        with_api(root, |api| {
+            assert_eq!(
+                ShellInsertCommand::get_session_path(api, "cat", "foo+bar").unwrap(),
+                root.join("cat/foo+bar")
+            );
+        });
+    }
+
+    #[test]
+    fn test_session_path_worktree() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let repository = root.join("cat/app");
+        fs::create_dir_all(&repository).unwrap();
+        git(&repository, &["init", "-q"]);
+        git(
+            &repository,
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+        );
+        let worktree = root.join("release");
+        let worktree_path = worktree.to_str().unwrap();
+        git(
+            &repository,
+            &["worktree", "add", "-q", "-b", "release-1.2", worktree_path],
+        );
+
+        with_api(root, |api| {
+            assert_eq!(
+                ShellInsertCommand::get_session_path(api, "cat", "app+release-1_2").unwrap(),
+                worktree
+            );
+            assert_eq!(
+                ShellInsertCommand::get_session_path(api, "cat", "app").unwrap(),
+                repository
+            );
+        });
+    }
+}
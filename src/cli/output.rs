@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use itertools::Itertools;
 
@@ -23,3 +23,39 @@ where
             .join("\n")
     )
 }
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{} {}", bytes, UNITS[0]),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+pub fn format_age(age: Duration) -> String {
+    const UNITS: [(&str, u64); 4] = [
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+        ("second", 1),
+    ];
+
+    let seconds = age.as_secs();
+    let (unit, size) = UNITS
+        .into_iter()
+        .find(|(_, size)| seconds >= *size)
+        .unwrap_or(UNITS[UNITS.len() - 1]);
+
+    match seconds / size {
+        1 => format!("1 {} ago", unit),
+        amount => format!("{} {}s ago", amount, unit),
+    }
+}
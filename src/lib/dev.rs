@@ -5,9 +5,9 @@ mod public;
 pub mod strategy;
 
 pub use public::{
-    api::{Category, RepositoryLocation},
+    api::{Category, RepositoryLocation, WorktreeLocation},
     changes::{
-        get_repository_change_status,
+        get_repository_change_details, get_repository_change_status,
         list_repositories_with_change_status as list_repositories_with_change_status_next,
         list_repositories_with_change_status_in_category,
         list_repositories_with_uncommitted_changes,
@@ -17,8 +17,14 @@ pub use public::{
         create_repository, list_all_repositories, list_categories, list_repositories_in_category,
         list_repositories_in_category_with_errors, move_repository, verify_repository_exists,
     },
-    git::{clean_repository, clone_repository, clone_repository_default},
-    path::get_repository_path as get_repository_path_next,
+    git::{
+        add_worktree, clean_repository, clone_repository, clone_repository_default,
+        commit_repository, delete_branch, fetch_repository, fork_repository,
+        fork_repository_default, get_lfs_status, get_repository_metadata, get_repository_size,
+        get_stale_stash_age, init_repository, list_branches, list_stashes, list_submodules,
+        list_worktrees, pull_repository, push_repository, remove_worktree,
+    },
+    path::{get_repository_path as get_repository_path_next, get_worktree_path},
     repositories::resolve_repository_alias,
     strategy::{use_local_strategy_with_default_config, use_mock_strategy, Api},
 };
@@ -1,28 +1,194 @@
 mod load;
 
 use std::{
-    cell::{Ref, RefCell},
     collections::hash_map::{Entry, HashMap},
     fs::{self, File},
     io::Read,
-    path::PathBuf,
-    rc::Rc,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard},
+    thread,
+    time::Duration,
 };
 use thiserror::Error;
 
-use self::load::LoadRootConfig;
+use self::load::{LoadGrassAuthentication, LoadGrassDiscoveryFilter, LoadRootConfig};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct GrassCategory {
     pub name: String,
     pub alias: Vec<String>,
+    /// Names of the clean profiles which apply to this category.
+    ///
+    /// When empty, cleaning will remove all ignored files.
+    pub clean_profiles: Vec<String>,
+    /// How to authenticate with remotes, when cloning or fetching.
+    pub auth: GrassAuthentication,
+    /// Directory which is copied into new repositories, when they are initialized.
+    ///
+    /// A `hooks` directory in the template is copied to `.git/hooks` instead.
+    pub template: Option<PathBuf>,
+    /// URL of the remote of a repository, like `git@github.com:my-org/{repository}.git`.
+    ///
+    /// `{repository}` and `{category}` are replaced by the name of the repository and category.
+    pub remote_template: Option<String>,
+    /// How many directory levels are searched for repositories, like `2` for `<team>/<repo>`.
+    ///
+    /// Searching stops at directories containing `.git`, or at the last level.
+    /// When `None`, only the directories directly in the category are repositories.
+    pub discovery_depth: Option<usize>,
+    /// Which directories count as repositories, when discovering them.
+    pub discovery_filter: GrassDiscoveryFilter,
+    /// Directory containing the repositories, instead of `<base_dir>/<name>`.
+    pub path: Option<PathBuf>,
+}
+
+impl GrassCategory {
+    /// Get the directory containing the repositories of this category.
+    pub fn get_directory(&self, base_dir: &Path) -> PathBuf {
+        self.path
+            .clone()
+            .unwrap_or_else(|| base_dir.join(&self.name))
+    }
+
+    /// Get how many directory levels are searched for repositories, at least 1.
+    pub fn get_discovery_depth(&self) -> usize {
+        self.discovery_depth.unwrap_or(1)
+    }
+
+    /// Get the remote URL of a repository in this category, from the remote template.
+    pub fn get_remote_url<T: AsRef<str>>(&self, repository: T) -> Option<String> {
+        self.remote_template.as_ref().map(|template| {
+            template
+                .replace("{repository}", repository.as_ref())
+                .replace("{category}", &self.name)
+        })
+    }
+}
+
+/// Authentication used for remotes of repositories in a category.
+///
+/// All fields are optional, when nothing is set git is used as is.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct GrassAuthentication {
+    /// Private key used for SSH remotes.
+    pub ssh_key: Option<PathBuf>,
+    /// Value for `core.sshCommand`, takes precedence over `ssh_key`.
+    pub ssh_command: Option<String>,
+    /// Value for `credential.helper`, replacing any globally configured helpers.
+    pub credential_helper: Option<String>,
+    /// Name of the environment variable containing a token for HTTPS remotes.
+    pub token_env: Option<String>,
+    /// Username used together with the token, defaults to `git`.
+    pub token_username: Option<String>,
+}
+
+/// Rules for which directories in a category are repositories.
+///
+/// Patterns are matched against the repository name, like `archive` or `team/*`.
+/// The wildcard `*` matches any number of characters, including `/`.
+/// By default every directory is a repository.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct GrassDiscoveryFilter {
+    /// Only directories containing `.git` are repositories.
+    pub require_git: bool,
+    /// When not empty, only repositories matching one of these patterns are listed.
+    pub include: Vec<String>,
+    /// Repositories matching one of these patterns are not listed.
+    pub exclude: Vec<String>,
+    /// Ignore directories starting with `.`.
+    pub ignore_hidden: bool,
+}
+
+impl GrassDiscoveryFilter {
+    /// Get the exclude pattern matching a repository name, if any.
+    pub fn get_exclude_pattern<T: AsRef<str>>(&self, repository: T) -> Option<&str> {
+        let repository: Box<[char]> = repository.as_ref().chars().collect();
+        self.exclude
+            .iter()
+            .find(|pattern| glob_matches(&pattern.chars().collect::<Box<[_]>>(), &repository))
+            .map(String::as_str)
+    }
+
+    /// Whether a repository name is included, which is always the case without include patterns.
+    pub fn is_included<T: AsRef<str>>(&self, repository: T) -> bool {
+        let repository: Box<[char]> = repository.as_ref().chars().collect();
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob_matches(&pattern.chars().collect::<Box<[_]>>(), &repository))
+    }
+}
+
+/// Match a value against a pattern, where `*` matches any number of characters.
+pub(crate) fn glob_matches(pattern: &[char], value: &[char]) -> bool {
+    match (pattern.split_first(), value.split_first()) {
+        (None, None) => true,
+        (Some(('*', rest)), _) => {
+            glob_matches(rest, value) || (!value.is_empty() && glob_matches(pattern, &value[1..]))
+        }
+        (Some((expected, pattern_rest)), Some((actual, value_rest))) if expected == actual => {
+            glob_matches(pattern_rest, value_rest)
+        }
+        _ => false,
+    }
+}
+
+/// A named set of patterns, which limits what gets removed when cleaning a repository.
+///
+/// Patterns are matched against each component of the path of an ignored file.
+/// A pattern ending in `/` only matches directories.
+/// The wildcard `*` matches any number of characters.
+///
+/// For example, `node_modules/` or `*.pyc`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct GrassCleanProfile {
+    pub name: String,
+    pub patterns: Vec<String>,
+}
+
+/// Which implementation is used to work with git repositories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum GitBackend {
+    /// Run the `git` command line tool.
+    #[default]
+    Cli,
+    /// Use libgit2 in process, this requires the `native-git` feature.
+    Native,
 }
 
 #[derive(Debug, Clone)]
 pub struct GrassConfig {
-    pub category: HashMap<String, Rc<RefCell<GrassCategory>>>,
-    pub aliases: HashMap<String, Rc<RefCell<GrassCategory>>>,
+    pub category: HashMap<String, Arc<RwLock<GrassCategory>>>,
+    pub aliases: HashMap<String, Arc<RwLock<GrassCategory>>>,
     pub base_dir: PathBuf,
+    pub clean_profile: HashMap<String, GrassCleanProfile>,
+    /// Number of repositories which are processed at the same time, during bulk operations.
+    ///
+    /// When `None`, this depends on the number of available CPUs.
+    pub workers: Option<usize>,
+    pub git_backend: GitBackend,
+    /// Stashes older than this many days count as a change, in the change status.
+    ///
+    /// When `None`, stashes are never part of the change status.
+    pub stale_stash_days: Option<u64>,
+    /// Name of the initial branch of new repositories.
+    ///
+    /// When `None`, the default of git is used.
+    pub default_branch: Option<String>,
+    /// Look inside submodules when computing the change status.
+    pub recurse_submodules: bool,
+    /// Treat directories in the base directory, which are not used by a category, as categories.
+    pub discover_categories: bool,
+}
+
+/// Read a category, which may be shared between threads.
+///
+/// Categories are only written while loading the configuration,
+/// so a poisoned lock still contains a usable category.
+pub fn read_category(category: &RwLock<GrassCategory>) -> RwLockReadGuard<'_, GrassCategory> {
+    category.read().unwrap_or_else(PoisonError::into_inner)
 }
 
 #[derive(Debug, Clone)]
@@ -36,34 +202,146 @@ impl GrassConfig {
             category: HashMap::default(),
             aliases: HashMap::default(),
             base_dir: dirs::home_dir()?.join("repos"),
+            clean_profile: HashMap::default(),
+            workers: None,
+            git_backend: GitBackend::default(),
+            stale_stash_days: None,
+            default_branch: None,
+            recurse_submodules: false,
+            discover_categories: false,
+        })
+    }
+
+    /// Get the configured number of workers, or one for each available CPU.
+    pub fn get_worker_count(&self) -> usize {
+        self.workers.unwrap_or_else(|| {
+            thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1)
         })
     }
-    pub fn get_from_category_or_alias<T>(&self, name: T) -> Option<Ref<GrassCategory>>
+
+    /// Get the age at which stashes become stale, `None` when stashes are not part of the change status.
+    pub fn get_stale_stash_age(&self) -> Option<Duration> {
+        self.stale_stash_days
+            .map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)))
+    }
+
+    pub fn get_from_category_or_alias<T>(
+        &self,
+        name: T,
+    ) -> Option<RwLockReadGuard<'_, GrassCategory>>
     where
         T: AsRef<str>,
     {
         self.category
             .get(name.as_ref())
             .or_else(|| self.aliases.get(name.as_ref()))
-            .map(|value| value.borrow())
+            .map(|value| read_category(value))
     }
 
-    pub fn get_by_category<T>(&self, category_name: T) -> Option<Ref<GrassCategory>>
+    pub fn get_by_category<T>(&self, category_name: T) -> Option<RwLockReadGuard<'_, GrassCategory>>
     where
         T: AsRef<str>,
     {
         self.category
             .get(category_name.as_ref())
-            .map(|value| value.borrow())
+            .map(|value| read_category(value))
+    }
+
+    /// Get a category, including undeclared categories when `discover_categories` is enabled.
+    ///
+    /// Undeclared categories use the defaults, see [GrassConfig::is_undeclared_category].
+    pub fn get_by_category_or_discovered<T>(&self, category_name: T) -> Option<GrassCategory>
+    where
+        T: AsRef<str>,
+    {
+        if let Some(category) = self.get_by_category(&category_name) {
+            return Some(category.clone());
+        }
+
+        let is_discovered = self.discover_categories && self.is_undeclared_category(&category_name);
+        is_discovered.then(|| GrassCategory {
+            name: String::from(category_name.as_ref()),
+            ..Default::default()
+        })
     }
 
-    pub fn get_by_alias<T>(&self, alias_name: T) -> Option<Ref<GrassCategory>>
+    /// Whether a directory in the base directory is not used by any category.
+    ///
+    /// Hidden directories are never categories, these include the directory with worktrees.
+    pub fn is_undeclared_category<T>(&self, name: T) -> bool
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+        if name.is_empty() || name.starts_with('.') || name.contains('/') {
+            return false;
+        }
+        if self.category.contains_key(name) || self.aliases.contains_key(name) {
+            return false;
+        }
+
+        let directory = self.base_dir.join(name);
+        directory.is_dir()
+            && !self
+                .category
+                .values()
+                .any(|category| read_category(category).get_directory(&self.base_dir) == directory)
+    }
+
+    /// List the directories in the base directory which are not used by any category, sorted.
+    ///
+    /// This does not depend on `discover_categories`.
+    pub fn list_undeclared_categories(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.base_dir) else {
+            return Vec::new();
+        };
+
+        let mut result: Vec<_> = entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| self.is_undeclared_category(name))
+            .collect();
+        result.sort();
+        result
+    }
+
+    pub fn get_by_alias<T>(&self, alias_name: T) -> Option<RwLockReadGuard<'_, GrassCategory>>
     where
         T: AsRef<str>,
     {
         self.aliases
             .get(alias_name.as_ref())
-            .map(|value| value.borrow())
+            .map(|value| read_category(value))
+    }
+
+    /// Get the clean patterns which apply to a category.
+    ///
+    /// Returns `Ok(None)` when no clean profiles are configured for the category,
+    /// meaning that everything which is ignored may be cleaned.
+    /// If a profile is referenced but not defined, its name is returned as the error.
+    pub fn get_clean_patterns<T>(&self, category_name: T) -> Result<Option<Vec<String>>, String>
+    where
+        T: AsRef<str>,
+    {
+        let category = match self.get_by_category(category_name) {
+            Some(category) => category,
+            None => return Ok(None),
+        };
+
+        if category.clean_profiles.is_empty() {
+            return Ok(None);
+        }
+
+        let mut result = Vec::new();
+        for profile_name in &category.clean_profiles {
+            match self.clean_profile.get(profile_name) {
+                Some(profile) => result.extend(profile.patterns.iter().cloned()),
+                None => return Err(profile_name.clone()),
+            }
+        }
+
+        Ok(Some(result))
     }
 }
 
@@ -71,6 +349,98 @@ impl GrassConfig {
 pub enum MergeError {
     #[error("Cannot find home directory")]
     MissingHomeDirectory,
+    #[error("The number of workers must be at least 1")]
+    InvalidWorkerCount,
+    #[error("The discovery depth of category '{category}' must be at least 1")]
+    InvalidDiscoveryDepth { category: String },
+    #[error("The environment variable '{variable}' used in '{value}' is not set")]
+    MissingEnvironmentVariable { variable: String, value: String },
+    #[error("Unclosed '${{' in '{value}'")]
+    InvalidEnvironmentVariable { value: String },
+    #[error("Unknown git backend '{backend}', expected 'cli' or 'native'")]
+    InvalidGitBackend { backend: String },
+    #[error("The git backend '{backend}' requires grass to be built with the '{feature}' feature")]
+    UnsupportedGitBackend { backend: String, feature: String },
+}
+
+/// Expand a leading `~/` to the home directory.
+fn expand_home(value: &str) -> Result<PathBuf, MergeError> {
+    match value.strip_prefix("~/") {
+        Some(suffix) => match dirs::home_dir() {
+            Some(home_dir) => Ok(home_dir.join(suffix)),
+            None => Err(MergeError::MissingHomeDirectory),
+        },
+        None => Ok(PathBuf::from(value)),
+    }
+}
+
+/// Expand environment variables, written as `$NAME` or `${NAME}`, and a leading `~`.
+fn expand_path(value: &str) -> Result<PathBuf, MergeError> {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(index) = rest.find('$') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+
+        let (name, remainder) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.split_once('}') {
+                Some(split) => split,
+                None => {
+                    return Err(MergeError::InvalidEnvironmentVariable {
+                        value: value.into(),
+                    })
+                }
+            },
+            None => rest.split_at(
+                rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len()),
+            ),
+        };
+
+        // A `$` which does not start a name is kept as is.
+        if name.is_empty() && !rest.starts_with('{') {
+            result.push('$');
+            continue;
+        }
+
+        result.push_str(&std::env::var(name).map_err(|_| {
+            MergeError::MissingEnvironmentVariable {
+                variable: name.into(),
+                value: value.into(),
+            }
+        })?);
+        rest = remainder;
+    }
+    result.push_str(rest);
+
+    match result.as_str() {
+        "~" => dirs::home_dir().ok_or(MergeError::MissingHomeDirectory),
+        result => expand_home(result),
+    }
+}
+
+impl GrassAuthentication {
+    fn try_from_load(value: &LoadGrassAuthentication) -> Result<Self, MergeError> {
+        Ok(GrassAuthentication {
+            ssh_key: value.ssh_key.as_deref().map(expand_home).transpose()?,
+            ssh_command: value.ssh_command.clone(),
+            credential_helper: value.credential_helper.clone(),
+            token_env: value.token_env.clone(),
+            token_username: value.token_username.clone(),
+        })
+    }
+}
+
+impl From<&LoadGrassDiscoveryFilter> for GrassDiscoveryFilter {
+    fn from(value: &LoadGrassDiscoveryFilter) -> Self {
+        GrassDiscoveryFilter {
+            require_git: value.require_git,
+            include: value.include.clone(),
+            exclude: value.exclude.clone(),
+            ignore_hidden: value.ignore_hidden,
+        }
+    }
 }
 
 impl RootConfig {
@@ -89,29 +459,109 @@ impl RootConfig {
         };
 
         if let Some(base_dir) = &grass.base_dir {
-            match base_dir.strip_prefix("~/") {
-                Some(suffix) => {
-                    if let Some(home_dir) = dirs::home_dir() {
-                        self.grass.base_dir = home_dir.join(suffix);
-                    } else {
-                        return Err(MergeError::MissingHomeDirectory);
-                    };
-                }
-                None => self.grass.base_dir = PathBuf::from(base_dir),
-            }
+            self.grass.base_dir = expand_home(base_dir)?;
         };
 
+        if let Some(workers) = grass.workers {
+            if workers == 0 {
+                return Err(MergeError::InvalidWorkerCount);
+            }
+            self.grass.workers = Some(workers);
+        }
+
+        if let Some(stale_stash_days) = grass.stale_stash_days {
+            self.grass.stale_stash_days = Some(stale_stash_days);
+        }
+
+        if let Some(default_branch) = &grass.default_branch {
+            self.grass.default_branch = Some(default_branch.clone());
+        }
+
+        if let Some(recurse_submodules) = grass.recurse_submodules {
+            self.grass.recurse_submodules = recurse_submodules;
+        }
+
+        if let Some(discover_categories) = grass.discover_categories {
+            self.grass.discover_categories = discover_categories;
+        }
+
+        if let Some(git_backend) = &grass.git_backend {
+            self.grass.git_backend = match git_backend.as_str() {
+                "cli" => GitBackend::Cli,
+                "native" if cfg!(feature = "native-git") => GitBackend::Native,
+                "native" => {
+                    return Err(MergeError::UnsupportedGitBackend {
+                        backend: git_backend.clone(),
+                        feature: String::from("native-git"),
+                    })
+                }
+                _ => {
+                    return Err(MergeError::InvalidGitBackend {
+                        backend: git_backend.clone(),
+                    })
+                }
+            };
+        }
+
         for (key, category) in &grass.category {
-            let category_rc = match self.grass.category.entry(key.clone()) {
+            let auth = category
+                .auth
+                .as_ref()
+                .map(GrassAuthentication::try_from_load)
+                .transpose()?;
+            let template = category.template.as_deref().map(expand_home).transpose()?;
+            let path = category.path.as_deref().map(expand_path).transpose()?;
+            let discovery_filter = category
+                .discovery_filter
+                .as_ref()
+                .map(GrassDiscoveryFilter::from);
+            if category.discovery_depth == Some(0) {
+                return Err(MergeError::InvalidDiscoveryDepth {
+                    category: key.clone(),
+                });
+            }
+
+            let shared_category = match self.grass.category.entry(key.clone()) {
                 Entry::Vacant(e) => {
-                    let result = Rc::from(RefCell::from(GrassCategory {
+                    let result = Arc::from(RwLock::from(GrassCategory {
                         name: key.clone(),
                         alias: category.alias.clone(),
+                        clean_profiles: category.clean_profiles.clone(),
+                        auth: auth.unwrap_or_default(),
+                        template,
+                        remote_template: category.remote_template.clone(),
+                        discovery_depth: category.discovery_depth,
+                        discovery_filter: discovery_filter.unwrap_or_default(),
+                        path,
                     }));
                     e.insert(result).clone()
                 }
                 Entry::Occupied(e) => {
-                    e.get().borrow_mut().name = key.clone();
+                    {
+                        let mut existing = e.get().write().unwrap_or_else(PoisonError::into_inner);
+                        existing.name = key.clone();
+                        if !category.clean_profiles.is_empty() {
+                            existing.clean_profiles = category.clean_profiles.clone();
+                        }
+                        if let Some(auth) = auth {
+                            existing.auth = auth;
+                        }
+                        if template.is_some() {
+                            existing.template = template;
+                        }
+                        if category.remote_template.is_some() {
+                            existing.remote_template = category.remote_template.clone();
+                        }
+                        if category.discovery_depth.is_some() {
+                            existing.discovery_depth = category.discovery_depth;
+                        }
+                        if let Some(discovery_filter) = discovery_filter {
+                            existing.discovery_filter = discovery_filter;
+                        }
+                        if path.is_some() {
+                            existing.path = path;
+                        }
+                    }
                     e.get().clone()
                 }
             };
@@ -119,10 +569,20 @@ impl RootConfig {
             for alias in &category.alias {
                 self.grass
                     .aliases
-                    .insert(alias.clone(), category_rc.clone());
+                    .insert(alias.clone(), shared_category.clone());
             }
         }
 
+        for (key, profile) in &grass.clean_profile {
+            self.grass.clean_profile.insert(
+                key.clone(),
+                GrassCleanProfile {
+                    name: key.clone(),
+                    patterns: profile.patterns.clone(),
+                },
+            );
+        }
+
         Ok(self)
     }
 }
@@ -135,6 +595,8 @@ pub enum LoadUserError {
     MissingConfigurationDirectory,
     #[error("Cannot read configuration file:\n{io_error}")]
     CannotReadConfigurationFile { io_error: std::io::Error },
+    #[error("Cannot write configuration file:\n{io_error}")]
+    CannotWriteConfigurationFile { io_error: std::io::Error },
     #[error("Cannot create default configuration")]
     CannotCreateDefault,
     #[error("The configuration file\n''\nwas improperly formatted:\n{reason}")]
@@ -167,7 +629,7 @@ pub fn load_user_config() -> Result<RootConfig, LoadUserError> {
                     _ => None,
                 })
         {
-            let mut file = if let Ok(file) = File::open(&config_dir.join(&file_name)) {
+            let mut file = if let Ok(file) = File::open(config_dir.join(&file_name)) {
                 file
             } else {
                 continue;
@@ -181,26 +643,95 @@ pub fn load_user_config() -> Result<RootConfig, LoadUserError> {
 
             let load_config: LoadRootConfig =
                 toml::from_str(&contents).map_err(|error| LoadUserError::ImproperlyFormatted {
-                    file: PathBuf::from(file_name),
+                    file: PathBuf::from(&file_name),
                     reason: error.to_string(),
                 })?;
-            config
-                .merge(&load_config)
-                .map_err(|_| LoadUserError::MissingHomeDirectory)?;
+            config.merge(&load_config).map_err(|error| match error {
+                MergeError::MissingHomeDirectory => LoadUserError::MissingHomeDirectory,
+                error => LoadUserError::ImproperlyFormatted {
+                    file: PathBuf::from(&file_name),
+                    reason: error.to_string(),
+                },
+            })?;
         }
     }
 
     Ok(config)
 }
 
+/// Format the table declaring a category, quoting the name when it is not a bare key.
+fn format_category_table(name: &str) -> String {
+    let is_bare_key = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let key = match is_bare_key {
+        true => String::from(name),
+        false => toml::Value::String(String::from(name)).to_string(),
+    };
+
+    format!("[grass.category.{}]\nalias = []\n", key)
+}
+
+/// Declare categories in the user configuration file, returning the path of the file.
+///
+/// The categories are appended, so existing content and comments are kept.
+pub fn add_categories_to_user_config<T>(categories: &[T]) -> Result<PathBuf, LoadUserError>
+where
+    T: AsRef<str>,
+{
+    let file = dirs::config_dir()
+        .ok_or(LoadUserError::MissingConfigurationDirectory)?
+        .join("grass/config.toml");
+
+    let mut contents = fs::read_to_string(&file)
+        .map_err(|error| LoadUserError::CannotReadConfigurationFile { io_error: error })?;
+
+    for category in categories {
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push('\n');
+        contents.push_str(&format_category_table(category.as_ref()));
+    }
+
+    toml::from_str::<LoadRootConfig>(&contents).map_err(|error| {
+        LoadUserError::ImproperlyFormatted {
+            file: file.clone(),
+            reason: error.to_string(),
+        }
+    })?;
+
+    fs::write(&file, contents)
+        .map_err(|error| LoadUserError::CannotWriteConfigurationFile { io_error: error })?;
+
+    Ok(file)
+}
+
 pub fn load_example_config() -> RootConfig {
-    let general = Rc::from(RefCell::from(GrassCategory {
+    let general = Arc::from(RwLock::from(GrassCategory {
         name: String::from("general"),
         alias: vec![String::from("gen")],
+        clean_profiles: Vec::new(),
+        auth: GrassAuthentication::default(),
+        template: None,
+        remote_template: None,
+        discovery_depth: None,
+        discovery_filter: GrassDiscoveryFilter::default(),
+        path: None,
     }));
-    let work = Rc::from(RefCell::from(GrassCategory {
+    let work = Arc::from(RwLock::from(GrassCategory {
         name: String::from("work"),
         alias: Vec::new(),
+        clean_profiles: vec![String::from("node")],
+        auth: GrassAuthentication {
+            ssh_key: Some(dirs::home_dir().unwrap().join(".ssh/work")),
+            ..Default::default()
+        },
+        template: None,
+        remote_template: Some(String::from("git@github.com:my-org/{repository}.git")),
+        discovery_depth: None,
+        discovery_filter: GrassDiscoveryFilter::default(),
+        path: None,
     }));
     RootConfig {
         grass: GrassConfig {
@@ -210,6 +741,19 @@ pub fn load_example_config() -> RootConfig {
                 (String::from("work"), work),
             ]),
             base_dir: dirs::home_dir().unwrap().join("repos"),
+            clean_profile: HashMap::from([(
+                String::from("node"),
+                GrassCleanProfile {
+                    name: String::from("node"),
+                    patterns: vec![String::from("node_modules/")],
+                },
+            )]),
+            workers: None,
+            git_backend: GitBackend::default(),
+            stale_stash_days: None,
+            default_branch: None,
+            recurse_submodules: false,
+            discover_categories: false,
         },
     }
 }
@@ -217,7 +761,10 @@ pub fn load_example_config() -> RootConfig {
 #[cfg(test)]
 mod tests {
     use super::{
-        load::{LoadGrassCategory, LoadGrassConfig},
+        load::{
+            LoadGrassAuthentication, LoadGrassCategory, LoadGrassCleanProfile, LoadGrassConfig,
+            LoadGrassDiscoveryFilter,
+        },
         *,
     };
 
@@ -225,21 +772,57 @@ mod tests {
         LoadRootConfig {
             grass: Some(LoadGrassConfig {
                 category: HashMap::from([
-                    (String::from("work"), LoadGrassCategory { alias: vec![] }),
+                    (
+                        String::from("work"),
+                        LoadGrassCategory {
+                            alias: vec![],
+                            clean_profiles: vec![String::from("rust")],
+                            auth: Some(LoadGrassAuthentication {
+                                ssh_key: Some(String::from("~/.ssh/work")),
+                                token_env: Some(String::from("WORK_TOKEN")),
+                                ..Default::default()
+                            }),
+                            template: Some(String::from("~/templates/work")),
+                            remote_template: Some(String::from(
+                                "git@github.com:my-org/{repository}.git",
+                            )),
+                            discovery_depth: Some(2),
+                            discovery_filter: Some(LoadGrassDiscoveryFilter {
+                                require_git: true,
+                                exclude: vec![String::from("archive/*")],
+                                ..Default::default()
+                            }),
+                            path: Some(String::from("${GRASS_TEST_WORK_ROOT}/src")),
+                        },
+                    ),
                     (
                         String::from("general"),
                         LoadGrassCategory {
                             alias: vec![String::from("gen")],
+                            ..Default::default()
                         },
                     ),
                 ]),
                 base_dir: Some(String::from("~/my-repositories")),
+                clean_profile: HashMap::from([(
+                    String::from("rust"),
+                    LoadGrassCleanProfile {
+                        patterns: vec![String::from("target/")],
+                    },
+                )]),
+                workers: Some(4),
+                git_backend: Some(String::from("cli")),
+                stale_stash_days: Some(30),
+                default_branch: Some(String::from("main")),
+                recurse_submodules: Some(true),
+                discover_categories: Some(true),
             }),
         }
     }
 
     #[test]
     fn test_config_merge() {
+        std::env::set_var("GRASS_TEST_WORK_ROOT", "/work");
         let mut config = RootConfig::try_default().unwrap();
         config.merge(&get_load_config()).expect("Could not merge");
 
@@ -250,17 +833,148 @@ mod tests {
             dirs::home_dir().unwrap().join("my-repositories")
         );
         assert_eq!(
-            config.grass.category.get("work").unwrap().borrow().name,
+            config
+                .grass
+                .category
+                .get("work")
+                .unwrap()
+                .read()
+                .unwrap()
+                .name,
             "work"
         );
         assert_eq!(
-            config.grass.category.get("general").unwrap().borrow().name,
+            config
+                .grass
+                .category
+                .get("general")
+                .unwrap()
+                .read()
+                .unwrap()
+                .name,
             "general"
         );
         assert_eq!(
-            config.grass.aliases.get("gen").unwrap().borrow().name,
+            config
+                .grass
+                .aliases
+                .get("gen")
+                .unwrap()
+                .read()
+                .unwrap()
+                .name,
             "general"
         );
+        assert_eq!(
+            config.grass.get_clean_patterns("work"),
+            Ok(Some(vec![String::from("target/")]))
+        );
+        assert_eq!(config.grass.get_clean_patterns("general"), Ok(None));
+        assert_eq!(config.grass.workers, Some(4));
+        assert_eq!(config.grass.git_backend, GitBackend::Cli);
+        assert_eq!(config.grass.stale_stash_days, Some(30));
+        assert_eq!(config.grass.default_branch, Some(String::from("main")));
+        assert!(config.grass.recurse_submodules);
+        assert!(config.grass.discover_categories);
+        assert_eq!(
+            config
+                .grass
+                .category
+                .get("work")
+                .unwrap()
+                .read()
+                .unwrap()
+                .auth,
+            GrassAuthentication {
+                ssh_key: Some(dirs::home_dir().unwrap().join(".ssh/work")),
+                token_env: Some(String::from("WORK_TOKEN")),
+                ..Default::default()
+            }
+        );
+
+        let work = config.grass.get_by_category("work").unwrap();
+        assert_eq!(
+            work.template,
+            Some(dirs::home_dir().unwrap().join("templates/work"))
+        );
+        assert_eq!(work.get_discovery_depth(), 2);
+        assert_eq!(work.path, Some(PathBuf::from("/work/src")));
+        assert_eq!(
+            work.get_directory(&config.grass.base_dir),
+            PathBuf::from("/work/src")
+        );
+        assert!(work.discovery_filter.require_git);
+        assert_eq!(
+            work.discovery_filter.get_exclude_pattern("archive/api"),
+            Some("archive/*")
+        );
+        assert_eq!(work.discovery_filter.get_exclude_pattern("team/api"), None);
+        assert!(work.discovery_filter.is_included("team/api"));
+        assert_eq!(
+            work.get_remote_url("api"),
+            Some(String::from("git@github.com:my-org/api.git"))
+        );
+        assert_eq!(
+            config
+                .grass
+                .get_by_category("general")
+                .unwrap()
+                .get_remote_url("api"),
+            None
+        );
+        assert_eq!(
+            config
+                .grass
+                .get_by_category("general")
+                .unwrap()
+                .get_discovery_depth(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_expand_path() {
+        std::env::set_var("GRASS_TEST_VOLUME", "/mnt/secure");
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(
+            expand_path("/work/src").unwrap(),
+            PathBuf::from("/work/src")
+        );
+        assert_eq!(expand_path("~").unwrap(), home);
+        assert_eq!(expand_path("~/repos").unwrap(), home.join("repos"));
+        assert_eq!(
+            expand_path("$GRASS_TEST_VOLUME/src").unwrap(),
+            PathBuf::from("/mnt/secure/src")
+        );
+        assert_eq!(
+            expand_path("${GRASS_TEST_VOLUME}_work/$").unwrap(),
+            PathBuf::from("/mnt/secure_work/$")
+        );
+        assert!(matches!(
+            expand_path("$GRASS_TEST_MISSING/src"),
+            Err(MergeError::MissingEnvironmentVariable { .. })
+        ));
+        assert!(matches!(
+            expand_path("${GRASS_TEST_VOLUME/src"),
+            Err(MergeError::InvalidEnvironmentVariable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_category_table() {
+        assert_eq!(
+            format_category_table("work"),
+            "[grass.category.work]\nalias = []\n"
+        );
+        assert_eq!(
+            format_category_table("side projects"),
+            "[grass.category.\"side projects\"]\nalias = []\n"
+        );
+
+        let config: LoadRootConfig = toml::from_str(&format_category_table("side projects"))
+            .expect("Could not parse the category table");
+        assert!(config.grass.unwrap().category.contains_key("side projects"));
     }
 
     #[test]
@@ -274,7 +988,17 @@ mod tests {
             *result_work,
             GrassCategory {
                 name: String::from("work"),
-                alias: vec![]
+                alias: vec![],
+                clean_profiles: vec![String::from("node")],
+                auth: GrassAuthentication {
+                    ssh_key: Some(dirs::home_dir().unwrap().join(".ssh/work")),
+                    ..Default::default()
+                },
+                template: None,
+                remote_template: Some(String::from("git@github.com:my-org/{repository}.git")),
+                discovery_depth: None,
+                discovery_filter: GrassDiscoveryFilter::default(),
+                path: None,
             }
         );
 
@@ -282,7 +1006,8 @@ mod tests {
             *result_general,
             GrassCategory {
                 name: String::from("general"),
-                alias: vec![String::from("gen")]
+                alias: vec![String::from("gen")],
+                ..Default::default()
             }
         );
     }
@@ -297,7 +1022,8 @@ mod tests {
             *result_gen,
             GrassCategory {
                 name: String::from("general"),
-                alias: vec![String::from("gen")]
+                alias: vec![String::from("gen")],
+                ..Default::default()
             }
         );
     }
@@ -5,12 +5,54 @@ use serde::Deserialize;
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct LoadGrassCategory {
     pub alias: Vec<String>,
+    #[serde(default)]
+    pub clean_profiles: Vec<String>,
+    pub auth: Option<LoadGrassAuthentication>,
+    pub template: Option<String>,
+    pub remote_template: Option<String>,
+    pub discovery_depth: Option<usize>,
+    pub discovery_filter: Option<LoadGrassDiscoveryFilter>,
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct LoadGrassDiscoveryFilter {
+    #[serde(default)]
+    pub require_git: bool,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub ignore_hidden: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct LoadGrassAuthentication {
+    pub ssh_key: Option<String>,
+    pub ssh_command: Option<String>,
+    pub credential_helper: Option<String>,
+    pub token_env: Option<String>,
+    pub token_username: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct LoadGrassCleanProfile {
+    pub patterns: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 pub struct LoadGrassConfig {
     pub category: HashMap<String, LoadGrassCategory>,
     pub base_dir: Option<String>,
+    #[serde(default)]
+    pub clean_profile: HashMap<String, LoadGrassCleanProfile>,
+    pub workers: Option<usize>,
+    pub git_backend: Option<String>,
+    pub stale_stash_days: Option<u64>,
+    pub default_branch: Option<String>,
+    pub recurse_submodules: Option<bool>,
+    pub discover_categories: Option<bool>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
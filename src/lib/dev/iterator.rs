@@ -1,3 +1,4 @@
 pub mod iter;
 pub mod location;
 pub mod location_and_change_status;
+pub mod parallel;
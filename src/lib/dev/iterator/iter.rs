@@ -61,11 +61,11 @@ impl<'a, T: Iterator<Item = U>, U: Clone> Iterator for MarkEndIterator<'a, T> {
 }
 
 pub trait IterExtensions: Iterator + Sized {
-    fn mark_end(&mut self, n: usize) -> MarkEndIterator<Self>;
+    fn mark_end(&mut self, n: usize) -> MarkEndIterator<'_, Self>;
 }
 
 impl<T: Iterator<Item = U> + Sized, U: Default> IterExtensions for T {
-    fn mark_end(&mut self, n: usize) -> MarkEndIterator<T> {
+    fn mark_end(&mut self, n: usize) -> MarkEndIterator<'_, T> {
         MarkEndIterator {
             source: self,
             result: Vec::from_iter(repeat_with(Default::default).take(n)),
@@ -1,59 +1,76 @@
+use std::vec::IntoIter;
+
 use tracing::warn;
 
 use crate::dev::{
     get_repository_change_status,
+    iterator::parallel::ParallelIterExtensions,
     strategy::{
         alias::SupportsAlias,
+        api::SupportsConfig,
         git::{RepositoryChangeStatus, SupportsGit},
     },
     Api, RepositoryLocation,
 };
 
+/// Adds the change status to each repository.
+///
+/// The change status of every repository is retrieved in parallel, when the first item is requested.
 pub struct WithChangeStatusIterator<'a, T, U>
 where
     T: Iterator<Item = RepositoryLocation>,
-    U: SupportsGit + SupportsAlias,
+    U: SupportsConfig + SupportsGit + SupportsAlias + Sync,
 {
     source: &'a mut T,
     api: &'a Api<U>,
+    result: Option<IntoIter<Option<(RepositoryLocation, RepositoryChangeStatus)>>>,
 }
 
-impl<'a, T: Iterator<Item = RepositoryLocation>, U: SupportsGit + SupportsAlias> Iterator
-    for WithChangeStatusIterator<'a, T, U>
+impl<
+        'a,
+        T: Iterator<Item = RepositoryLocation>,
+        U: SupportsConfig + SupportsGit + SupportsAlias + Sync,
+    > Iterator for WithChangeStatusIterator<'a, T, U>
 {
     type Item = (RepositoryLocation, RepositoryChangeStatus);
 
     fn next(&mut self) -> Option<Self::Item> {
-        for next in &mut self.source {
-            let change_status = match get_repository_change_status(self.api, next.clone()) {
-                Ok(change_status) => change_status,
-                Err(error) => {
-                    warn!(
-                        "Could not get the repository change status of repository {}\nReason:\n{}",
-                        next, error
-                    );
-                    continue;
+        let api = self.api;
+        let result = self.result.get_or_insert_with(|| {
+            (&mut *self.source).parallel_map(api, |next| {
+                match get_repository_change_status(api, next.clone()) {
+                    Ok(change_status) => Some((next, change_status)),
+                    Err(error) => {
+                        warn!(
+                            "Could not get the repository change status of repository {}\nReason:\n{}",
+                            next, error
+                        );
+                        None
+                    }
                 }
-            };
+            })
+        });
 
-            return Some((next, change_status));
-        }
-        None
+        result.flatten().next()
     }
 }
 
 pub trait LocationIterExtensions: Iterator<Item = RepositoryLocation> + Sized {
-    fn with_change_status<'a, T: SupportsGit + SupportsAlias>(
+    fn with_change_status<'a, T: SupportsConfig + SupportsGit + SupportsAlias + Sync>(
         &'a mut self,
         api: &'a Api<T>,
     ) -> WithChangeStatusIterator<'a, Self, T>;
 }
 
 impl<T: Iterator<Item = RepositoryLocation> + Sized> LocationIterExtensions for T {
-    fn with_change_status<'a, U: SupportsGit + SupportsAlias>(
+    fn with_change_status<'a, U: SupportsConfig + SupportsGit + SupportsAlias + Sync>(
         &'a mut self,
         api: &'a Api<U>,
     ) -> WithChangeStatusIterator<'a, Self, U> {
-        WithChangeStatusIterator { source: self, api }
+        WithChangeStatusIterator {
+            source: self,
+            api,
+            result: None,
+        }
     }
 }
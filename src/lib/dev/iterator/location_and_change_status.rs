@@ -32,13 +32,13 @@ impl<'a, T: Iterator<Item = (RepositoryLocation, RepositoryChangeStatus)>> Itera
 pub trait LocationAndChangeStatusIterExtensions:
     Iterator<Item = (RepositoryLocation, RepositoryChangeStatus)> + Sized
 {
-    fn uncommitted_changes_only(&mut self) -> UncommittedChangesOnlyIterator<Self>;
+    fn uncommitted_changes_only(&mut self) -> UncommittedChangesOnlyIterator<'_, Self>;
 }
 
 impl<T: Iterator<Item = (RepositoryLocation, RepositoryChangeStatus)> + Sized>
     LocationAndChangeStatusIterExtensions for T
 {
-    fn uncommitted_changes_only(&mut self) -> UncommittedChangesOnlyIterator<Self> {
+    fn uncommitted_changes_only(&mut self) -> UncommittedChangesOnlyIterator<'_, Self> {
         UncommittedChangesOnlyIterator { source: self }
     }
 }
@@ -0,0 +1,90 @@
+use std::{
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+use crate::dev::{strategy::api::SupportsConfig, Api};
+
+/// Map each item using a bounded number of threads.
+///
+/// The results are returned in the same order as the items.
+/// With a single worker, or a single item, everything runs on the current thread.
+///
+/// # Example
+///
+/// ```rust
+/// use grass::dev::iterator::parallel::parallel_map;
+///
+/// let result = parallel_map((1..=10).collect(), 4, |value: u32| value * 2);
+///
+/// assert_eq!(result, vec![2, 4, 6, 8, 10, 12, 14, 16, 18, 20]);
+/// ```
+pub fn parallel_map<T, U, F>(items: Vec<T>, workers: usize, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> U + Sync,
+{
+    let workers = workers.min(items.len());
+    if workers <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let num_items = items.len();
+    let items = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let items = &items;
+            let f = &f;
+            scope.spawn(move || loop {
+                // The lock is released before the item is processed.
+                let next = items
+                    .lock()
+                    .unwrap_or_else(|error| error.into_inner())
+                    .next();
+                let Some((index, item)) = next else {
+                    break;
+                };
+                if sender.send((index, f(item))).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut result: Vec<Option<U>> = (0..num_items).map(|_| None).collect();
+    for (index, value) in receiver {
+        result[index] = Some(value);
+    }
+
+    result.into_iter().flatten().collect()
+}
+
+pub trait ParallelIterExtensions: Iterator + Sized {
+    /// Map each item on multiple threads, using the configured worker count.
+    ///
+    /// All items are consumed before the first result is returned.
+    /// The results are returned in the same order as the items.
+    fn parallel_map<T, U, F>(self, api: &Api<T>, f: F) -> std::vec::IntoIter<U>
+    where
+        T: SupportsConfig,
+        Self::Item: Send,
+        U: Send,
+        F: Fn(Self::Item) -> U + Sync;
+}
+
+impl<T: Iterator + Sized> ParallelIterExtensions for T {
+    fn parallel_map<U, V, F>(self, api: &Api<U>, f: F) -> std::vec::IntoIter<V>
+    where
+        U: SupportsConfig,
+        Self::Item: Send,
+        V: Send,
+        F: Fn(Self::Item) -> V + Sync,
+    {
+        parallel_map(self.collect(), api.get_config().get_worker_count(), f).into_iter()
+    }
+}
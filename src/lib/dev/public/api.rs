@@ -32,6 +32,54 @@ impl Display for RepositoryLocation {
     }
 }
 
+/// A worktree of a repository, addressed by the branch which is checked out in it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct WorktreeLocation {
+    pub repository: RepositoryLocation,
+    pub branch: String,
+}
+
+impl WorktreeLocation {
+    pub fn new<T, U>(repository: T, branch: U) -> Self
+    where
+        T: Into<RepositoryLocation>,
+        U: Into<String>,
+    {
+        WorktreeLocation {
+            repository: repository.into(),
+            branch: branch.into(),
+        }
+    }
+
+    pub fn to_session_string(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+impl Display for WorktreeLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}+{}@{}",
+            self.repository.repository, self.branch, self.repository.category
+        )
+    }
+}
+
+impl<T, U, V> From<(T, U, V)> for WorktreeLocation
+where
+    T: Into<Category>,
+    U: Into<String>,
+    V: Into<String>,
+{
+    fn from((category, repository, branch): (T, U, V)) -> Self {
+        Self {
+            repository: RepositoryLocation::new(category, repository),
+            branch: branch.into(),
+        }
+    }
+}
+
 impl From<String> for Category {
     fn from(value: String) -> Self {
         Category(value)
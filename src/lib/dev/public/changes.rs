@@ -1,11 +1,13 @@
 use crate::dev::{
     error::GrassError,
+    iterator::parallel::ParallelIterExtensions,
     strategy::{
         alias::{AliasStrategy, SupportsAlias},
+        api::SupportsConfig,
         discovery::{DiscoveryStrategy, DiscoveryStrategyError, SupportsDiscovery},
         git::{
-            GitStrategy, GitStrategyError, RepositoryChangeStatus, RepositoryChangeStatusWithError,
-            SupportsGit,
+            FileChange, GitStrategy, GitStrategyError, RepositoryChangeStatus,
+            RepositoryChangeStatusWithError, SupportsGit,
         },
     },
     Api, Category, RepositoryLocation,
@@ -56,13 +58,43 @@ where
         .get_changes(api.get_alias_strategy().resolve_alias(repository.into())?)
 }
 
-fn location_result_to_change_status_result<T: GitStrategy>(
-    value: (Result<RepositoryLocation, DiscoveryStrategyError>, &T),
-) -> ChangeStatusResult {
-    let (location, git) = value;
+/// Get every changed file of a specific repository
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::FileChangeKind;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+///
+/// let no_changes =
+///     grass::dev::get_repository_change_details(&api, ("with_changes", "first")).unwrap();
+/// let with_changes =
+///     grass::dev::get_repository_change_details(&api, ("with_changes", "third")).unwrap();
+///
+/// assert!(no_changes.is_empty());
+/// assert_eq!(with_changes.len(), 9);
+/// assert_eq!(with_changes[0].staged, FileChangeKind::Modified);
+/// ```
+pub fn get_repository_change_details<T, U>(
+    api: &Api<T>,
+    repository: U,
+) -> Result<Vec<FileChange>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .get_change_details(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
 
+fn location_result_to_change_status_result<T: SupportsGit>(
+    api: &Api<T>,
+    location: Result<RepositoryLocation, DiscoveryStrategyError>,
+) -> ChangeStatusResult {
     let location = match location {
-        Ok(location) => location.clone(),
+        Ok(location) => location,
         Err(error) => {
             return ChangeStatusResult {
                 location: None,
@@ -72,7 +104,7 @@ fn location_result_to_change_status_result<T: GitStrategy>(
     };
     ChangeStatusResult {
         location: Some(location.clone()),
-        change_status: git.get_changes(location).into(),
+        change_status: api.get_git_strategy().get_changes(location).into(),
     }
 }
 
@@ -99,7 +131,7 @@ fn filter_away_up_to_date_repositories(value: &ChangeStatusResult) -> bool {
 /// #     self,
 /// #     ChangeStatusResult,
 /// #     strategy::{
-/// #         api::MockApiStrategy,
+/// #         api::{MockApiStrategy, SupportsConfig},
 /// #         discovery::SupportsDiscovery,
 /// #         git::{RepositoryChangeStatusWithError, SupportsGit},
 /// #     },
@@ -108,7 +140,7 @@ fn filter_away_up_to_date_repositories(value: &ChangeStatusResult) -> bool {
 /// #
 /// # let api = Api::from(MockApiStrategy::default());
 /// #
-/// fn test_api<T: SupportsGit + SupportsDiscovery>(api: &Api<T>) {
+/// fn test_api<T: SupportsConfig + SupportsGit + SupportsDiscovery + Sync>(api: &Api<T>) {
 ///     let repositories: HashSet<_> =
 ///         dev::list_repositories_with_change_status_next(api).unwrap();
 ///
@@ -128,26 +160,32 @@ fn filter_away_up_to_date_repositories(value: &ChangeStatusResult) -> bool {
 ///             num_changes: 9
 ///         },
 ///     }));
+///
+///     assert!(repositories.iter().any(|result| matches!(
+///         result,
+///         ChangeStatusResult {
+///             change_status: RepositoryChangeStatusWithError::UnpushedWork { .. },
+///             ..
+///         }
+///     )));
 /// }
 ///
 /// test_api(&api)
 /// ```
 pub fn list_repositories_with_change_status<T, U>(api: &Api<T>) -> Result<U, GrassError>
 where
-    T: SupportsGit + SupportsDiscovery,
+    T: SupportsConfig + SupportsGit + SupportsDiscovery + Sync,
     U: FromIterator<ChangeStatusResult>,
 {
-    let git = api.get_git_strategy();
     let discovery = api.get_discovery_strategy();
 
     let categories: Vec<_> = discovery.list_categories()?;
     Ok(categories
         .iter()
         .filter_map(|category| discovery.list_repositories_in_category(category).ok())
-        .flat_map(|repositories| {
-            repositories
-                .map(|repository| (repository, git))
-                .map(location_result_to_change_status_result)
+        .flatten()
+        .parallel_map(api, |repository| {
+            location_result_to_change_status_result(api, repository)
         })
         .collect())
 }
@@ -165,7 +203,7 @@ where
 /// #     self,
 /// #     ChangeStatusResult,
 /// #     strategy::{
-/// #         api::MockApiStrategy,
+/// #         api::{MockApiStrategy, SupportsConfig},
 /// #         discovery::SupportsDiscovery,
 /// #         git::{RepositoryChangeStatusWithError, SupportsGit},
 /// #     },
@@ -174,7 +212,7 @@ where
 /// #
 /// # let api = Api::from(MockApiStrategy::default());
 /// #
-/// fn test_api<T: SupportsGit + SupportsDiscovery>(api: &Api<T>) {
+/// fn test_api<T: SupportsConfig + SupportsGit + SupportsDiscovery + Sync>(api: &Api<T>) {
 ///     let repositories: HashSet<_> =
 ///         dev::list_repositories_with_uncommitted_changes(api).unwrap();
 ///
@@ -194,28 +232,34 @@ where
 ///             num_changes: 9
 ///         },
 ///     }));
+///
+///     assert!(repositories.iter().any(|result| matches!(
+///         result,
+///         ChangeStatusResult {
+///             change_status: RepositoryChangeStatusWithError::UnpushedWork { .. },
+///             ..
+///         }
+///     )));
 /// }
 ///
 /// test_api(&api)
 /// ```
 pub fn list_repositories_with_uncommitted_changes<T, U>(api: &Api<T>) -> Result<U, GrassError>
 where
-    T: SupportsGit + SupportsDiscovery,
+    T: SupportsConfig + SupportsGit + SupportsDiscovery + Sync,
     U: FromIterator<ChangeStatusResult>,
 {
-    let git = api.get_git_strategy();
     let discovery = api.get_discovery_strategy();
 
     let categories: Vec<_> = discovery.list_categories()?;
     Ok(categories
         .iter()
         .filter_map(|category| discovery.list_repositories_in_category(category).ok())
-        .flat_map(|repositories| {
-            repositories
-                .map(|repository| (repository, git))
-                .map(location_result_to_change_status_result)
-                .filter(filter_away_up_to_date_repositories)
+        .flatten()
+        .parallel_map(api, |repository| {
+            location_result_to_change_status_result(api, repository)
         })
+        .filter(filter_away_up_to_date_repositories)
         .collect())
 }
 
@@ -233,7 +277,7 @@ where
 /// #     ChangeStatusResult,
 /// #     strategy::{
 /// #         alias::SupportsAlias,
-/// #         api::MockApiStrategy,
+/// #         api::{MockApiStrategy, SupportsConfig},
 /// #         discovery::SupportsDiscovery,
 /// #         git::{RepositoryChangeStatusWithError, SupportsGit},
 /// #     },
@@ -242,7 +286,9 @@ where
 /// #
 /// # let api = Api::from(MockApiStrategy::default());
 /// #
-/// fn test_api<T: SupportsGit + SupportsDiscovery + SupportsAlias>(api: &Api<T>) {
+/// fn test_api<T: SupportsConfig + SupportsGit + SupportsDiscovery + SupportsAlias + Sync>(
+///     api: &Api<T>,
+/// ) {
 ///     let repositories: HashSet<_> =
 ///         dev::list_repositories_with_change_status_in_category(api, "with_changes").unwrap();
 ///
@@ -266,12 +312,11 @@ pub fn list_repositories_with_change_status_in_category<T, U, V>(
     category: U,
 ) -> Result<V, GrassError>
 where
-    T: SupportsGit + SupportsAlias + SupportsDiscovery,
+    T: SupportsConfig + SupportsGit + SupportsAlias + SupportsDiscovery + Sync,
     U: Into<Category>,
     V: FromIterator<ChangeStatusResult>,
 {
     let alias = api.get_alias_strategy();
-    let git = api.get_git_strategy();
     let discovery = api.get_discovery_strategy();
 
     let category: Category = alias.resolve_alias(category.into().as_ref())?.into();
@@ -279,8 +324,9 @@ where
     let repositories = discovery.list_repositories_in_category::<Category>(category)?;
 
     Ok(repositories
-        .map(|repository| (repository, git))
-        .map(location_result_to_change_status_result)
+        .parallel_map(api, |repository| {
+            location_result_to_change_status_result(api, repository)
+        })
         .collect())
 }
 
@@ -298,7 +344,7 @@ where
 /// #     ChangeStatusResult,
 /// #     strategy::{
 /// #         alias::SupportsAlias,
-/// #         api::MockApiStrategy,
+/// #         api::{MockApiStrategy, SupportsConfig},
 /// #         discovery::SupportsDiscovery,
 /// #         git::{RepositoryChangeStatusWithError, SupportsGit},
 /// #     },
@@ -307,7 +353,9 @@ where
 /// #
 /// # let api = Api::from(MockApiStrategy::default());
 /// #
-/// fn test_api<T: SupportsGit + SupportsDiscovery + SupportsAlias>(api: &Api<T>) {
+/// fn test_api<T: SupportsConfig + SupportsGit + SupportsDiscovery + SupportsAlias + Sync>(
+///     api: &Api<T>,
+/// ) {
 ///     let repositories: HashSet<_> =
 ///         dev::list_repositories_with_uncommitted_changes_in_category(api, "with_changes").unwrap();
 ///
@@ -327,6 +375,14 @@ where
 ///             num_changes: 9
 ///         },
 ///     }));
+///
+///     assert!(repositories.iter().any(|result| matches!(
+///         result,
+///         ChangeStatusResult {
+///             change_status: RepositoryChangeStatusWithError::UnpushedWork { .. },
+///             ..
+///         }
+///     )));
 /// }
 ///
 /// test_api(&api)
@@ -336,12 +392,11 @@ pub fn list_repositories_with_uncommitted_changes_in_category<T, U, V>(
     category: U,
 ) -> Result<V, GrassError>
 where
-    T: SupportsGit + SupportsAlias + SupportsDiscovery,
+    T: SupportsConfig + SupportsGit + SupportsAlias + SupportsDiscovery + Sync,
     U: Into<Category>,
     V: FromIterator<ChangeStatusResult>,
 {
     let alias = api.get_alias_strategy();
-    let git = api.get_git_strategy();
     let discovery = api.get_discovery_strategy();
 
     let category: Category = alias.resolve_alias(category.into().as_ref())?.into();
@@ -349,8 +404,9 @@ where
     let repositories = discovery.list_repositories_in_category::<Category>(category)?;
 
     Ok(repositories
-        .map(|repository| (repository, git))
-        .map(location_result_to_change_status_result)
+        .parallel_map(api, |repository| {
+            location_result_to_change_status_result(api, repository)
+        })
         .filter(filter_away_up_to_date_repositories)
         .collect())
 }
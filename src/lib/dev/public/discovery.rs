@@ -25,14 +25,13 @@ where
         .get_discovery_strategy()
         .list_repositories_in_category(category)?;
 
-    Ok(iterator
-        .filter_map(|value| match value {
-            Ok(value) => Some(value),
-            Err(_) => None,
-        })
-        .collect())
+    Ok(iterator.filter_map(|value| value.ok()).collect())
 }
 
+/// List the repositories in a category, including the ones which could not be listed
+///
+/// Directories which the discovery filter of the category leaves out
+/// are reported as [DiscoveryStrategyError::RepositoryIgnored].
 pub fn list_repositories_in_category_with_errors<T, U, V>(
     api: &Api<T>,
     category: U,
@@ -82,7 +81,7 @@ pub fn list_categories<T: SupportsDiscovery, U: FromIterator<String>>(
 /// # Todo:
 ///
 /// - [ ] Return a generic crate wide error
-///       (<https://github.com/damymetzke/grass/issues/2>)
+///   (<https://github.com/damymetzke/grass/issues/2>)
 ///
 /// # Example
 ///
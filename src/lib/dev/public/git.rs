@@ -1,16 +1,26 @@
+use std::time::Duration;
+
 use crate::dev::{
     public::strategy::Api,
     strategy::{
         alias::{AliasStrategy, SupportsAlias},
-        git::{GitStrategy, GitStrategyError, SupportsGit},
+        api::SupportsConfig,
+        git::{
+            get_configured_remote_url, BranchInfo, CleanReport, CloneOptions, GitStrategy,
+            GitStrategyError, InitOptions, LfsStatus, PullMode, PullResult, RepositoryMetadata,
+            RepositorySize, Stash, Submodule, SupportsGit, Worktree,
+        },
     },
 };
 
-use super::api::RepositoryLocation;
+use super::api::{RepositoryLocation, WorktreeLocation};
 
-/// Clean a git repository, by removing untracked and ignored files
+/// Clean a git repository, by removing ignored files
 ///
 /// Common examples of such files are build artifacts, cached data, and downloaded packages.
+/// Tracked files and untracked files which are not ignored are never removed.
+///
+/// Returns which files were removed, and how much space was freed.
 ///
 /// # Example
 ///
@@ -21,7 +31,7 @@ use super::api::RepositoryLocation;
 /// // This will clean the "first" repository, under the "all_good" category
 /// grass::dev::clean_repository(&api, ("all_good", "first")).unwrap();
 /// ```
-pub fn clean_repository<T, U>(api: &Api<T>, repository: U) -> Result<(), GitStrategyError>
+pub fn clean_repository<T, U>(api: &Api<T>, repository: U) -> Result<CleanReport, GitStrategyError>
 where
     T: SupportsGit + SupportsAlias,
     U: Into<RepositoryLocation>,
@@ -32,37 +42,72 @@ where
 
 /// Clone a git repository from a remote.
 ///
-/// The remote must be a valid git remote.
-/// Currently only publically available remotes are supported.
+/// The remote must be a valid git remote, or the name of a remote repository.
+/// A remote without `:` or `/` is a name, which is expanded using the remote template
+/// of the category, see [crate::dev::config::GrassCategory::remote_template].
+/// The options choose the branch, the depth of the history, and whether submodules are cloned.
 ///
 /// # Example
 ///
 /// ```rust
 /// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::CloneOptions;
 /// # let api = use_mock_strategy();
 /// let api: Api<_> = api;
-/// // This will clone from the remote 'good_remote'.
+/// // This will clone from the remote 'https://example.com/other/project.git'.
 /// // This will be cloned to the category 'all_good', with the repository name 'new_repository'.
-/// grass::dev::clone_repository(&api, ("all_good", "new_repository"), "good_remote").unwrap();
-/// ```
+/// grass::dev::clone_repository(
+///     &api,
+///     ("all_good", "new_repository"),
+///     "https://example.com/other/project.git",
+///     &CloneOptions::default(),
+/// )
+/// .unwrap();
 ///
+/// // This will clone from 'https://example.com/all_good/api-server.git',
+/// // using the remote template of the category, and only fetch the latest commit.
+/// let options = CloneOptions {
+///     depth: Some(1),
+///     ..Default::default()
+/// };
+/// grass::dev::clone_repository(&api, ("all_good", "shallow"), "api-server", &options).unwrap();
 ///
-/// # Future
-/// - TODO: Add a way to authenticate remotes
+/// // The category 'with_changes' has no remote template.
+/// assert!(
+///     grass::dev::clone_repository(&api, ("with_changes", "new"), "api-server", &options)
+///         .is_err()
+/// );
+/// ```
+///
+/// The remote is authenticated using the `auth` settings of the category.
 pub fn clone_repository<T, U, V>(
     api: &Api<T>,
     repository: U,
     remote: V,
+    options: &CloneOptions,
 ) -> Result<(), GitStrategyError>
 where
-    T: SupportsGit + SupportsAlias,
+    T: SupportsConfig + SupportsGit + SupportsAlias,
     U: Into<RepositoryLocation>,
     V: AsRef<str>,
 {
-    api.get_git_strategy().clone(
-        api.get_alias_strategy().resolve_alias(repository.into())?,
-        remote,
-    )
+    let git = api.get_git_strategy();
+    let repository = api.get_alias_strategy().resolve_alias(repository.into())?;
+
+    let remote = match is_remote_name(remote.as_ref()) {
+        true => get_configured_remote_url(
+            api.get_config(),
+            &RepositoryLocation::new(repository.category.clone(), remote.as_ref()),
+        )?,
+        false => remote.as_ref().to_string(),
+    };
+
+    git.clone(repository, remote, options)
+}
+
+/// Whether a remote is only a name, instead of a URL or a path.
+fn is_remote_name(remote: &str) -> bool {
+    !remote.contains([':', '/', '\\'])
 }
 
 /// Clone a git repository from a remote, with an default generated name.
@@ -72,33 +117,528 @@ where
 /// - The repository name will be generated from the remote name.
 ///
 /// So the remote 'https://example.com/foo.git', will have the repository name 'foo'.
+/// When the remote is a name, that name is used.
 ///
 /// # Example
 ///
 /// ```rust
 /// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::CloneOptions;
 /// # let api = use_mock_strategy();
 /// let api: Api<_> = api;
-/// // This will clone from the remote 'good_remote'.
-/// // This will be cloned to the category 'all_good', with the repository name 'good_remote'.
-/// grass::dev::clone_repository_default(&api, "all_good", "good_remote").unwrap();
+/// // This will clone from the remote 'https://example.com/all_good/api-server.git'.
+/// // This will be cloned to the category 'all_good', with the repository name 'api-server'.
+/// grass::dev::clone_repository_default(&api, "all_good", "api-server", &CloneOptions::default())
+///     .unwrap();
 /// ```
 pub fn clone_repository_default<T, U, V>(
     api: &Api<T>,
     category: U,
     remote: V,
+    options: &CloneOptions,
 ) -> Result<(), GitStrategyError>
 where
-    T: SupportsGit + SupportsAlias,
+    T: SupportsConfig + SupportsGit + SupportsAlias,
     U: AsRef<str>,
     V: AsRef<str>,
 {
-    let repository = &remote.as_ref().to_string();
-    let repository = repository
+    let repository = get_repository_name(remote.as_ref());
+
+    clone_repository(api, (category.as_ref(), repository), &remote, options)
+}
+
+/// Generate a repository name from a remote, so 'https://example.com/foo.git' becomes 'foo'.
+fn get_repository_name(remote: &str) -> &str {
+    remote
         .split('/')
-        .last()
+        .next_back()
         .unwrap_or("repository")
-        .trim_end_matches(".git");
+        .trim_end_matches(".git")
+}
+
+/// Fork a git repository, from a remote to the remote of the category.
+///
+/// The remote is cloned, and renamed to 'upstream'.
+/// Then 'origin' is added, using the remote template of the category,
+/// see [crate::dev::config::GrassCategory::remote_template].
+/// When pushing, the current branch is pushed to 'origin', and tracks it afterwards.
+///
+/// The new remote has to exist already, when pushing.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// // This will clone from 'https://example.com/other/project.git',
+/// // and push to 'https://example.com/all_good/project.git'.
+/// grass::dev::fork_repository(
+///     &api,
+///     ("all_good", "project"),
+///     "https://example.com/other/project.git",
+///     true,
+/// )
+/// .unwrap();
+///
+/// // The category 'with_changes' has no remote template.
+/// assert!(grass::dev::fork_repository(
+///     &api,
+///     ("with_changes", "project"),
+///     "https://example.com/other/project.git",
+///     false,
+/// )
+/// .is_err());
+/// ```
+pub fn fork_repository<T, U, V>(
+    api: &Api<T>,
+    repository: U,
+    remote: V,
+    push: bool,
+) -> Result<(), GitStrategyError>
+where
+    T: SupportsConfig + SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+    V: AsRef<str>,
+{
+    let git = api.get_git_strategy();
+    let repository = api.get_alias_strategy().resolve_alias(repository.into())?;
+
+    // Resolved before cloning, so nothing is cloned without a remote template.
+    let origin = get_configured_remote_url(api.get_config(), &repository)?;
+
+    git.clone(repository.clone(), remote, &CloneOptions::default())?;
+    git.rename_remote(repository.clone(), "origin", "upstream")?;
+    git.add_remote(repository.clone(), "origin", &origin)?;
+
+    if push {
+        git.push_to_remote(repository, "origin")?;
+    }
+
+    Ok(())
+}
+
+/// Fork a git repository, with a default generated name.
+///
+/// Has the same behavior as [crate::dev::fork_repository],
+/// with the name generated like [crate::dev::clone_repository_default].
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// // This will be forked to the category 'all_good', with the repository name 'project'.
+/// grass::dev::fork_repository_default(
+///     &api,
+///     "all_good",
+///     "https://example.com/other/project.git",
+///     false,
+/// )
+/// .unwrap();
+/// ```
+pub fn fork_repository_default<T, U, V>(
+    api: &Api<T>,
+    category: U,
+    remote: V,
+    push: bool,
+) -> Result<(), GitStrategyError>
+where
+    T: SupportsConfig + SupportsGit + SupportsAlias,
+    U: AsRef<str>,
+    V: AsRef<str>,
+{
+    let repository = get_repository_name(remote.as_ref());
+
+    fork_repository(api, (category.as_ref(), repository), &remote, push)
+}
+
+/// Initialize a git repository, in the directory of an existing repository.
+///
+/// Use [crate::dev::create_repository] to create the directory first.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::InitOptions;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::init_repository(&api, ("all_good", "new"), &InitOptions::default()).unwrap();
+/// ```
+pub fn init_repository<T, U>(
+    api: &Api<T>,
+    repository: U,
+    options: &InitOptions,
+) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy().init(
+        api.get_alias_strategy().resolve_alias(repository.into())?,
+        options,
+    )
+}
+
+/// Fetch the remotes of a git repository.
+///
+/// Local branches are not changed.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::fetch_repository(&api, ("all_good", "first")).unwrap();
+/// ```
+pub fn fetch_repository<T, U>(api: &Api<T>, repository: U) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .fetch(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// Update the current branch of a git repository from its upstream.
+///
+/// Uncommitted changes are not checked, see [crate::dev::get_repository_change_status].
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::{PullMode, PullResult};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let result =
+///     grass::dev::pull_repository(&api, ("all_good", "second"), PullMode::default()).unwrap();
+///
+/// assert_eq!(result, PullResult::UpToDate);
+/// ```
+pub fn pull_repository<T, U>(
+    api: &Api<T>,
+    repository: U,
+    mode: PullMode,
+) -> Result<PullResult, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy().pull(
+        api.get_alias_strategy().resolve_alias(repository.into())?,
+        mode,
+    )
+}
+
+/// Stage every change of a git repository, and commit them.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::commit_repository(&api, ("with_changes", "third"), "Update notes").unwrap();
+/// ```
+pub fn commit_repository<T, U>(
+    api: &Api<T>,
+    repository: U,
+    message: &str,
+) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy().commit_all(
+        api.get_alias_strategy().resolve_alias(repository.into())?,
+        message,
+    )
+}
+
+/// Push the current branch of a git repository to its upstream.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::push_repository(&api, ("all_good", "first")).unwrap();
+/// ```
+pub fn push_repository<T, U>(api: &Api<T>, repository: U) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .push(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// List the worktrees of a git repository.
+///
+/// The main worktree, which is the repository directory itself, comes first.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let worktrees = grass::dev::list_worktrees(&api, ("all_good", "first")).unwrap();
+///
+/// assert_eq!(worktrees.len(), 2);
+/// assert!(worktrees[0].is_main);
+/// ```
+pub fn list_worktrees<T, U>(api: &Api<T>, repository: U) -> Result<Vec<Worktree>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .list_worktrees(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// Add a worktree to a git repository, with a branch checked out.
+///
+/// The branch is created when it does not exist yet.
+/// Use [crate::dev::get_worktree_path] to find where the worktree is created.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::add_worktree(&api, ("all_good", "first", "bugfix")).unwrap();
+/// ```
+pub fn add_worktree<T, U>(api: &Api<T>, worktree: U) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<WorktreeLocation>,
+{
+    api.get_git_strategy()
+        .add_worktree(api.get_alias_strategy().resolve_alias(worktree.into())?)
+}
+
+/// Remove the worktree of a git repository, which has a branch checked out.
+///
+/// Unless forced, worktrees with uncommitted changes are kept.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::remove_worktree(&api, ("all_good", "first", "feature"), false).unwrap();
+/// ```
+pub fn remove_worktree<T, U>(api: &Api<T>, worktree: U, force: bool) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<WorktreeLocation>,
+{
+    api.get_git_strategy().remove_worktree(
+        api.get_alias_strategy().resolve_alias(worktree.into())?,
+        force,
+    )
+}
+
+/// Get which branch a git repository has checked out, and its last commit.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use grass::dev::strategy::git::HeadState;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let metadata = grass::dev::get_repository_metadata(&api, ("all_good", "first")).unwrap();
+///
+/// assert_eq!(metadata.head, HeadState::Branch(String::from("main")));
+/// assert_eq!(metadata.last_commit.unwrap().id, "9f8e7d6");
+/// ```
+pub fn get_repository_metadata<T, U>(
+    api: &Api<T>,
+    repository: U,
+) -> Result<RepositoryMetadata, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .get_metadata(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// List the stashes of a git repository, the most recent stash first.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let stashes = grass::dev::list_stashes(&api, ("with_changes", "third")).unwrap();
+///
+/// assert_eq!(stashes.len(), 2);
+/// assert_eq!(stashes[0].message, "WIP on main: 1a2b3c4 Fix typo");
+/// ```
+pub fn list_stashes<T, U>(api: &Api<T>, repository: U) -> Result<Vec<Stash>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .list_stashes(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// List the submodules of a git repository, with whether they are initialized or changed.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let submodules = grass::dev::list_submodules(&api, ("with_changes", "third")).unwrap();
+///
+/// assert_eq!(submodules.len(), 2);
+/// assert_eq!(submodules[0].to_string(), "vendor/docs (uninitialized)");
+/// assert_eq!(submodules[1].to_string(), "vendor/library (modified commit)");
+/// ```
+pub fn list_submodules<T, U>(
+    api: &Api<T>,
+    repository: U,
+) -> Result<Vec<Submodule>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .list_submodules(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// Compute the disk usage of a repository.
+///
+/// The reclaimable size is what [clean_repository] would free.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let size = grass::dev::get_repository_size(&api, ("all_good", "first")).unwrap();
+///
+/// assert_eq!(size.total_bytes, 81920);
+/// assert_eq!(size.reclaimable_bytes, 4096);
+/// assert_eq!(size.git_bytes, 32768);
+/// ```
+pub fn get_repository_size<T, U>(
+    api: &Api<T>,
+    repository: U,
+) -> Result<RepositorySize, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .get_size(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
 
-    clone_repository(api, (category.as_ref(), repository), remote)
+/// Get the Git LFS status of a repository, `None` when the repository does not use LFS.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use std::path::PathBuf;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let status = grass::dev::get_lfs_status(&api, ("with_changes", "third")).unwrap();
+///
+/// assert_eq!(status.unwrap().missing_objects, vec![PathBuf::from("assets/logo.png")]);
+/// assert!(grass::dev::get_lfs_status(&api, ("all_good", "first")).unwrap().is_none());
+/// ```
+pub fn get_lfs_status<T, U>(
+    api: &Api<T>,
+    repository: U,
+) -> Result<Option<LfsStatus>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .get_lfs_status(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// Get the age at which stashes are considered stale, `None` when this is not configured.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # use std::time::Duration;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// assert_eq!(
+///     grass::dev::get_stale_stash_age(&api),
+///     Some(Duration::from_secs(30 * 24 * 60 * 60))
+/// );
+/// ```
+pub fn get_stale_stash_age<T: SupportsConfig>(api: &Api<T>) -> Option<Duration> {
+    api.get_config().get_stale_stash_age()
+}
+
+/// List the local branches of a git repository, with whether they are merged, gone or stale.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// let branches = grass::dev::list_branches(&api, ("with_changes", "first")).unwrap();
+///
+/// assert!(branches.iter().any(|branch| branch.is_merged));
+/// ```
+pub fn list_branches<T, U>(api: &Api<T>, repository: U) -> Result<Vec<BranchInfo>, GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy()
+        .list_branches(api.get_alias_strategy().resolve_alias(repository.into())?)
+}
+
+/// Delete a local branch of a git repository.
+///
+/// Unless forced, only branches which are merged into the default branch are deleted.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{Api, use_mock_strategy};
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// grass::dev::delete_branch(&api, ("with_changes", "first"), "old-feature", false).unwrap();
+/// ```
+pub fn delete_branch<T, U>(
+    api: &Api<T>,
+    repository: U,
+    branch: &str,
+    force: bool,
+) -> Result<(), GitStrategyError>
+where
+    T: SupportsGit + SupportsAlias,
+    U: Into<RepositoryLocation>,
+{
+    api.get_git_strategy().delete_branch(
+        api.get_alias_strategy().resolve_alias(repository.into())?,
+        branch,
+        force,
+    )
 }
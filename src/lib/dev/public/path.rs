@@ -7,7 +7,7 @@ use crate::dev::{
         alias::{AliasStrategy, AliasStrategyError, SupportsAlias},
         path::{PathStrategy, PathStrategyError, SupportsPath},
     },
-    Api, RepositoryLocation,
+    Api, RepositoryLocation, WorktreeLocation,
 };
 
 /// Either a path or an alias error
@@ -31,7 +31,7 @@ pub enum PathOrAliasError {
 /// # Todo:
 ///
 /// - [ ] Return a generic crate wide error
-///       (<https://github.com/damymetzke/grass/issues/2>)
+///   (<https://github.com/damymetzke/grass/issues/2>)
 ///
 /// # Example
 ///
@@ -64,3 +64,31 @@ pub fn get_repository_path<T: SupportsPath + SupportsAlias, U: Into<RepositoryLo
 
     Ok(result)
 }
+
+/// Get the path to a worktree of a repository.
+///
+/// Like [crate::dev::get_repository_path_next], the worktree does not have to exist.
+///
+/// # Example
+///
+/// ```rust
+/// # use grass::dev::{self, Api, use_mock_strategy};
+/// # use std::path::PathBuf;
+/// # let api = use_mock_strategy();
+/// let api: Api<_> = api;
+/// assert_eq!(
+///     dev::get_worktree_path(&api, ("all_good", "first", "feature")).unwrap(),
+///     PathBuf::from("/home/example/worktrees/all_good/first/feature")
+/// );
+/// ```
+pub fn get_worktree_path<T: SupportsPath + SupportsAlias, U: Into<WorktreeLocation>>(
+    api: &Api<T>,
+    worktree: U,
+) -> Result<PathBuf, PathOrAliasError> {
+    let path = api.get_path_strategy();
+    let alias = api.get_alias_strategy();
+
+    let result = path.get_worktree_directory(alias.resolve_alias(worktree.into())?)?;
+
+    Ok(result)
+}
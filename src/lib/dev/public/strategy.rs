@@ -7,7 +7,7 @@ use crate::dev::{
         alias::LocalAliasStrategy,
         api::{LocalApiStrategy, MockApiStrategy},
         discovery::LocalDiscoveryStrategy,
-        git::LocalGitStrategy,
+        git::ConfiguredGitStrategy,
         path::LocalPathStrategy,
     },
 };
@@ -87,9 +87,10 @@ where
     let alias_strategy = LocalAliasStrategy::new(&config);
     let path_strategy = LocalPathStrategy::new(&config);
     let discovery_strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
-    let git_strategy = LocalGitStrategy::new(&path_strategy);
+    let git_strategy = ConfiguredGitStrategy::new(&config, &path_strategy);
 
     let api_strategy = LocalApiStrategy::new(
+        &config,
         &alias_strategy,
         &discovery_strategy,
         &git_strategy,
@@ -1,4 +1,4 @@
-use crate::dev::config::GrassConfig;
+use crate::dev::config::{read_category, GrassConfig};
 
 use super::{Alias, AliasStrategy, AliasStrategyError, Result};
 
@@ -14,9 +14,9 @@ impl<'a> AliasStrategy for LocalAliasStrategy<'a> {
         let result = self
             .config
             .category
-            .iter()
-            .flat_map(|(_, category)| {
-                let category = category.borrow();
+            .values()
+            .flat_map(|category| {
+                let category = read_category(category);
                 let result: Vec<_> = category
                     .alias
                     .iter()
@@ -47,7 +47,7 @@ impl<'a> AliasStrategy for LocalAliasStrategy<'a> {
             }
         };
 
-        let category = category.borrow();
+        let category = read_category(category);
 
         let result = category
             .alias
@@ -63,7 +63,7 @@ impl<'a> AliasStrategy for LocalAliasStrategy<'a> {
 
     fn resolve_alias<T: super::ResolvesAlias>(&self, input: T) -> Result<T::Resolved> {
         input.resolve_alias(|input| match self.config.aliases.get(input) {
-            Some(alias) => Ok(Box::from((*alias.as_ref()).borrow().name.as_ref())),
+            Some(alias) => Ok(Box::from(read_category(alias).name.as_ref())),
             None => Ok(Box::from(input)),
         })
     }
@@ -1,6 +1,6 @@
 use std::{rc::Rc, sync::Arc};
 
-use crate::dev::{Category, RepositoryLocation};
+use crate::dev::{Category, RepositoryLocation, WorktreeLocation};
 
 use super::AliasStrategyError;
 
@@ -92,3 +92,17 @@ impl ResolvesAlias for RepositoryLocation {
         })
     }
 }
+
+impl ResolvesAlias for WorktreeLocation {
+    type Resolved = WorktreeLocation;
+
+    fn resolve_alias<F: FnOnce(&str) -> Result<Box<str>, AliasStrategyError>>(
+        &self,
+        resolver: F,
+    ) -> Result<Self::Resolved, AliasStrategyError> {
+        Ok(WorktreeLocation {
+            repository: self.repository.resolve_alias(resolver)?,
+            branch: self.branch.clone(),
+        })
+    }
+}
@@ -1,6 +1,8 @@
 mod local;
 mod mock;
 
+use crate::dev::{config::GrassConfig, public::strategy::AccessApi, Api};
+
 use super::{
     alias::SupportsAlias, discovery::SupportsDiscovery, git::SupportsGit, path::SupportsPath,
 };
@@ -8,6 +10,23 @@ use super::{
 pub use local::LocalApiStrategy;
 pub use mock::MockApiStrategy;
 
-pub trait SupportsAll: SupportsAlias + SupportsDiscovery + SupportsGit + SupportsPath {}
+/// Access to the configuration, for settings which are not part of a strategy.
+pub trait SupportsConfig {
+    fn get_config(&self) -> &GrassConfig;
+}
+
+impl<T: SupportsConfig> Api<T> {
+    pub(crate) fn get_config(&self) -> &GrassConfig {
+        self.get_strategy().get_config()
+    }
+}
+
+pub trait SupportsAll:
+    SupportsAlias + SupportsConfig + SupportsDiscovery + SupportsGit + SupportsPath + Sync
+{
+}
 
-impl<T: SupportsAlias + SupportsDiscovery + SupportsGit + SupportsPath> SupportsAll for T {}
+impl<T: SupportsAlias + SupportsConfig + SupportsDiscovery + SupportsGit + SupportsPath + Sync>
+    SupportsAll for T
+{
+}
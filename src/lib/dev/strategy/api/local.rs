@@ -1,27 +1,33 @@
-use crate::dev::strategy::{
-    alias::{LocalAliasStrategy, SupportsAlias},
-    discovery::LocalDiscoveryStrategy,
-    git::LocalGitStrategy,
-    path::LocalPathStrategy,
+use crate::dev::{
+    config::GrassConfig,
+    strategy::{
+        alias::{LocalAliasStrategy, SupportsAlias},
+        discovery::LocalDiscoveryStrategy,
+        git::ConfiguredGitStrategy,
+        path::LocalPathStrategy,
+    },
 };
 
-use super::{SupportsDiscovery, SupportsGit, SupportsPath};
+use super::{SupportsConfig, SupportsDiscovery, SupportsGit, SupportsPath};
 
 pub struct LocalApiStrategy<'a> {
+    config: &'a GrassConfig,
     alias_strategy: &'a LocalAliasStrategy<'a>,
     discovery_strategy: &'a LocalDiscoveryStrategy<'a, LocalPathStrategy<'a>>,
-    git_strategy: &'a LocalGitStrategy<'a, LocalPathStrategy<'a>>,
+    git_strategy: &'a ConfiguredGitStrategy<'a, LocalPathStrategy<'a>>,
     path_strategy: &'a LocalPathStrategy<'a>,
 }
 
 impl<'a> LocalApiStrategy<'a> {
     pub fn new(
+        config: &'a GrassConfig,
         api_strategy: &'a LocalAliasStrategy<'a>,
         discovery_strategy: &'a LocalDiscoveryStrategy<'a, LocalPathStrategy<'a>>,
-        git_strategy: &'a LocalGitStrategy<'a, LocalPathStrategy<'a>>,
+        git_strategy: &'a ConfiguredGitStrategy<'a, LocalPathStrategy<'a>>,
         path_strategy: &'a LocalPathStrategy<'a>,
     ) -> Self {
         Self {
+            config,
             alias_strategy: api_strategy,
             discovery_strategy,
             git_strategy,
@@ -30,6 +36,12 @@ impl<'a> LocalApiStrategy<'a> {
     }
 }
 
+impl<'a> SupportsConfig for LocalApiStrategy<'a> {
+    fn get_config(&self) -> &GrassConfig {
+        self.config
+    }
+}
+
 impl<'a> SupportsDiscovery for LocalApiStrategy<'a> {
     type Strategy = LocalDiscoveryStrategy<'a, LocalPathStrategy<'a>>;
 
@@ -39,7 +51,7 @@ impl<'a> SupportsDiscovery for LocalApiStrategy<'a> {
 }
 
 impl<'a> SupportsGit for LocalApiStrategy<'a> {
-    type Strategy = LocalGitStrategy<'a, LocalPathStrategy<'a>>;
+    type Strategy = ConfiguredGitStrategy<'a, LocalPathStrategy<'a>>;
 
     fn get_git_strategy(&self) -> &Self::Strategy {
         self.git_strategy
@@ -1,14 +1,58 @@
-use crate::dev::strategy::{
-    alias::{MockAliasStrategy, SupportsAlias},
-    discovery::MockDiscoveryStrategy,
-    git::MockGitStrategy,
-    path::MockPathStrategy,
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
 };
 
-use super::{SupportsDiscovery, SupportsGit, SupportsPath};
+use crate::dev::{
+    config::{GitBackend, GrassCategory, GrassConfig},
+    strategy::{
+        alias::{MockAliasStrategy, SupportsAlias},
+        discovery::MockDiscoveryStrategy,
+        git::MockGitStrategy,
+        path::MockPathStrategy,
+    },
+};
+
+use super::{SupportsConfig, SupportsDiscovery, SupportsGit, SupportsPath};
+
+/// The configuration used by the mocked strategies.
+///
+/// Only the category `all_good` has a remote template.
+/// Bulk operations use 2 workers, and stashes become stale after 30 days.
+fn get_mock_config() -> GrassConfig {
+    let category = |name: &str, remote_template: Option<&str>| {
+        let category = GrassCategory {
+            name: String::from(name),
+            remote_template: remote_template.map(String::from),
+            ..Default::default()
+        };
+        (String::from(name), Arc::new(RwLock::new(category)))
+    };
+
+    GrassConfig {
+        category: HashMap::from([
+            category(
+                "all_good",
+                Some("https://example.com/{category}/{repository}.git"),
+            ),
+            category("with_changes", None),
+            category("with_error", None),
+        ]),
+        aliases: HashMap::new(),
+        base_dir: PathBuf::from("/home/example/repositories"),
+        clean_profile: HashMap::new(),
+        workers: Some(2),
+        git_backend: GitBackend::default(),
+        stale_stash_days: Some(30),
+        default_branch: None,
+        recurse_submodules: false,
+        discover_categories: false,
+    }
+}
 
-#[derive(Default)]
 pub struct MockApiStrategy {
+    config: GrassConfig,
     alias_strategy: MockAliasStrategy,
     discovery_strategy: MockDiscoveryStrategy,
     git_strategy: MockGitStrategy,
@@ -21,6 +65,24 @@ impl MockApiStrategy {
     }
 }
 
+impl Default for MockApiStrategy {
+    fn default() -> Self {
+        Self {
+            config: get_mock_config(),
+            alias_strategy: MockAliasStrategy,
+            discovery_strategy: MockDiscoveryStrategy,
+            git_strategy: MockGitStrategy,
+            path_strategy: MockPathStrategy,
+        }
+    }
+}
+
+impl SupportsConfig for MockApiStrategy {
+    fn get_config(&self) -> &GrassConfig {
+        &self.config
+    }
+}
+
 impl SupportsAlias for MockApiStrategy {
     type Strategy = MockAliasStrategy;
 
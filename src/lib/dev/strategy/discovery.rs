@@ -30,6 +30,15 @@ pub enum DiscoveryStrategyError {
     RepositoryExists { context: Box<str>, reason: Box<str> },
     #[error("Repository does not exist:\nContext: {context}\nReason: {reason}")]
     RepositoryDoesNotExist { context: Box<str>, reason: Box<str> },
+    /// The directory exists, but the discovery filter of the category does not list it.
+    #[error("Repository '{repository}' is ignored by the discovery filter:\nReason: {reason}")]
+    RepositoryIgnored {
+        repository: RepositoryLocation,
+        reason: Box<str>,
+    },
+    /// The linked worktrees of a repository would lose track of it when it is moved.
+    #[error("Repository '{repository}' has linked worktrees:\nReason: Remove the worktrees before moving the repository")]
+    RepositoryHasWorktrees { repository: RepositoryLocation },
 }
 
 /// Methods of [DiscoveryStrategy] return this alias
@@ -141,9 +150,11 @@ pub trait DiscoveryStrategy {
     ///
     /// Discovery depends on the implementation.
     /// In the case of [LocalDiscoveryStrategy][^1], this means all folders in a specified folder.
+    /// The discovery depth and discovery filter of the category are applied.
     ///
     /// The result will be an iterator over the objects using a `Result`.
     /// Individual repositories may fail for one reason or another.
+    /// Directories which are filtered out are reported as [DiscoveryStrategyError::RepositoryIgnored].
     /// You are expected to filter these out if you want to ignore them.
     ///
     /// # Example:
@@ -191,7 +202,7 @@ pub trait DiscoveryStrategy {
     fn list_repositories_in_category<T>(
         &self,
         category: T,
-    ) -> Result<BoxedIterator<Result<RepositoryLocation>>>
+    ) -> Result<BoxedIterator<'_, Result<RepositoryLocation>>>
     where
         T: AsRef<str>;
 
@@ -199,6 +210,8 @@ pub trait DiscoveryStrategy {
     ///
     /// The implementation can differ per strategy.
     /// In the case of [LocalDiscoveryStrategy][^1], a configuration file is used.
+    /// When `discover_categories` is enabled,
+    /// undeclared directories in the base directory are categories as well.
     ///
     /// # Example
     ///
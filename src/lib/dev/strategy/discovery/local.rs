@@ -1,11 +1,16 @@
-use std::fs;
+use std::{
+    collections::BTreeSet,
+    fs::{self, DirEntry, File, ReadDir},
+    io::{self, Read},
+    path::Path,
+};
 
 use crate::dev::{
-    config::GrassConfig,
+    config::{GrassCategory, GrassConfig, GrassDiscoveryFilter},
     public::api::RepositoryLocation,
     strategy::{
         discovery::DiscoveryStrategyError,
-        path::{PathStrategy, PathStrategyError},
+        path::{PathStrategy, PathStrategyError, WORKTREES_DIRECTORY},
     },
 };
 
@@ -31,6 +36,179 @@ where
     }
 }
 
+fn read_directory_error(directory: &Path, error: io::Error) -> DiscoveryStrategyError {
+    DiscoveryStrategyError::FilesystemError {
+        context: format!(
+            "When trying to read the directory '{dir}' (searching for nested repositories)",
+            dir = directory
+                .to_str()
+                .unwrap_or("CANNOT DISPLAY DIRECTORY PATH")
+        ),
+        reason: error.to_string(),
+    }
+}
+
+/// Get the name of a directory, `None` when the entry is not a directory.
+fn get_directory_name(entry: io::Result<DirEntry>) -> Option<Result<String>> {
+    let entry = match entry {
+        Ok(entry) => entry,
+        Err(error) => {
+            return Some(Err(DiscoveryStrategyError::FilesystemError {
+                context: "When reading unknown entry".into(),
+                reason: error.to_string(),
+            }))
+        }
+    };
+
+    let metadata = match entry.metadata() {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            return Some(Err(DiscoveryStrategyError::FilesystemError {
+                context: format!(
+                    "When retrieving metadata for entry '{name}'",
+                    name = entry
+                        .path()
+                        .to_str()
+                        .unwrap_or("CANNOT DISPLAY DIRECTORY PATH")
+                ),
+                reason: error.to_string(),
+            }))
+        }
+    };
+
+    if !metadata.is_dir() {
+        return None;
+    };
+
+    let entry_path = entry.path();
+
+    let repository =
+        match entry_path
+            .file_name()
+            .ok_or_else(|| DiscoveryStrategyError::FilesystemError {
+                context: format!(
+                    "When retrieving directory name from path {path}",
+                    path = entry
+                        .path()
+                        .to_str()
+                        .unwrap_or("CANNOT_DISPLAY_DIRECTORY_PATH")
+                ),
+                reason: "No reason given".into(),
+            }) {
+            Ok(repository) => repository,
+            Err(error) => return Some(Err(error)),
+        };
+
+    let repository: String = match repository.to_str() {
+        Some(repository) => repository,
+        None => {
+            return Some(Err(DiscoveryStrategyError::FilesystemError {
+                context: format!(
+                    "When retrieving directory name from path {path}",
+                    path = entry
+                        .path()
+                        .to_str()
+                        .unwrap_or("CANNOT_DISPLAY_DIRECTORY_PATH")
+                ),
+                reason: "No reason given".into(),
+            }))
+        }
+    }
+    .into();
+
+    Some(Ok(repository))
+}
+
+/// Get why a directory is not searched for repositories, `None` when it is searched.
+fn get_skip_reason(filter: &GrassDiscoveryFilter, name: &str) -> Option<String> {
+    let is_hidden = name
+        .rsplit('/')
+        .next()
+        .is_some_and(|directory| directory.starts_with('.'));
+    if filter.ignore_hidden && is_hidden {
+        return Some(String::from("Hidden directories are ignored"));
+    }
+
+    filter
+        .get_exclude_pattern(name)
+        .map(|pattern| format!("Matches the exclude pattern '{}'", pattern))
+}
+
+/// Get why a repository is filtered out, `None` when it is listed.
+fn get_ignore_reason(filter: &GrassDiscoveryFilter, name: &str, path: &Path) -> Option<String> {
+    if filter.require_git && !path.join(".git").exists() {
+        return Some(String::from("The directory does not contain '.git'"));
+    }
+
+    match filter.is_included(name) {
+        true => None,
+        false => Some(String::from("Does not match any include pattern")),
+    }
+}
+
+/// Find the repositories in a directory, searching up to `depth` levels deep.
+///
+/// Directories containing `.git` are repositories, as are all directories at the last level.
+/// Nested repositories are named by their path, like `team/repo`.
+/// Directories which are filtered out are reported as [DiscoveryStrategyError::RepositoryIgnored].
+fn find_repositories(
+    category: &GrassCategory,
+    entries: ReadDir,
+    directory: &Path,
+    prefix: &str,
+    depth: usize,
+    repositories: &mut Vec<Result<RepositoryLocation>>,
+) {
+    let filter = &category.discovery_filter;
+    for entry in entries {
+        let name = match get_directory_name(entry) {
+            Some(Ok(name)) => name,
+            Some(Err(error)) => {
+                repositories.push(Err(error));
+                continue;
+            }
+            None => continue,
+        };
+
+        // Worktrees may be kept inside the directory of a category.
+        if prefix.is_empty() && name == WORKTREES_DIRECTORY {
+            continue;
+        }
+
+        let path = directory.join(&name);
+        let name = match prefix {
+            "" => name,
+            prefix => format!("{}/{}", prefix, name),
+        };
+
+        let reason = get_skip_reason(filter, &name).or_else(|| {
+            match depth <= 1 || path.join(".git").exists() {
+                true => get_ignore_reason(filter, &name, &path),
+                false => None,
+            }
+        });
+        if let Some(reason) = reason {
+            repositories.push(Err(DiscoveryStrategyError::RepositoryIgnored {
+                repository: RepositoryLocation::new(category.name.as_str(), name),
+                reason: reason.into(),
+            }));
+            continue;
+        }
+
+        if depth <= 1 || path.join(".git").exists() {
+            repositories.push(Ok(RepositoryLocation::new(category.name.as_str(), name)));
+            continue;
+        }
+
+        match fs::read_dir(&path) {
+            Ok(entries) => {
+                find_repositories(category, entries, &path, &name, depth - 1, repositories)
+            }
+            Err(error) => repositories.push(Err(read_directory_error(&path, error))),
+        }
+    }
+}
+
 impl<'a, T> DiscoveryStrategy for LocalDiscoveryStrategy<'a, T>
 where
     T: PathStrategy,
@@ -51,7 +229,7 @@ where
     where
         U: AsRef<str>,
     {
-        match self.config.get_by_category(category) {
+        match self.config.get_by_category_or_discovered(category) {
             Some(_) => Ok(DiscoveryExists::Exists),
             None => Ok(DiscoveryExists::CategoryNotFound),
         }
@@ -60,18 +238,18 @@ where
     fn list_repositories_in_category<U>(
         &self,
         category: U,
-    ) -> Result<BoxedIterator<Result<RepositoryLocation>>>
+    ) -> Result<BoxedIterator<'_, Result<RepositoryLocation>>>
     where
         U: AsRef<str>,
     {
-        let category = self.config.get_by_category(category).ok_or(
+        let category = self.config.get_by_category_or_discovered(category).ok_or(
             DiscoveryStrategyError::CategoryNotFound {
                 context: "When trying to ge tthe category from the configuration".into(),
                 reason: "Category doesn't exist".into(),
             },
         )?;
 
-        let base_dir = self.config.base_dir.join(&category.name);
+        let base_dir = category.get_directory(&self.config.base_dir);
 
         let directory =
             fs::read_dir(&base_dir).map_err(|error| DiscoveryStrategyError::FilesystemError {
@@ -82,84 +260,35 @@ where
                 reason: error.to_string(),
             })?;
 
-        Ok(Box::from(directory.into_iter().filter_map(move |entry| {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(error) => {
-                    return Some(Err(DiscoveryStrategyError::FilesystemError {
-                        context: "When reading unknown entry".into(),
-                        reason: error.to_string(),
-                    }))
-                }
-            };
-
-            let metadata = match entry.metadata() {
-                Ok(metadata) => metadata,
-                Err(error) => {
-                    return Some(Err(DiscoveryStrategyError::FilesystemError {
-                        context: format!(
-                            "When retrieving metadata for entry '{name}'",
-                            name = entry
-                                .path()
-                                .to_str()
-                                .unwrap_or("CANNOT DISPLAY DIRECTORY PATH")
-                        ),
-                        reason: error.to_string(),
-                    }))
-                }
-            };
-
-            if !metadata.is_dir() {
-                return None;
-            };
-
-            let entry_path = entry.path();
-
-            let repository = match entry_path.file_name().ok_or_else(|| {
-                DiscoveryStrategyError::FilesystemError {
-                    context: format!(
-                        "When retrieving directory name from path {path}",
-                        path = entry
-                            .path()
-                            .to_str()
-                            .unwrap_or("CANNOT_DISPLAY_DIRECTORY_PATH")
-                    ),
-                    reason: "No reason given".into(),
-                }
-            }) {
-                Ok(repository) => repository,
-                Err(error) => return Some(Err(error)),
-            };
-
-            let repository: String = match repository.to_str() {
-                Some(repository) => repository,
-                None => {
-                    return Some(Err(DiscoveryStrategyError::FilesystemError {
-                        context: format!(
-                            "When retrieving directory name from path {path}",
-                            path = entry
-                                .path()
-                                .to_str()
-                                .unwrap_or("CANNOT_DISPLAY_DIRECTORY_PATH")
-                        ),
-                        reason: "No reason given".into(),
-                    }))
-                }
-            }
-            .into();
+        let mut repositories = Vec::new();
+        find_repositories(
+            &category,
+            directory,
+            &base_dir,
+            "",
+            category.get_discovery_depth(),
+            &mut repositories,
+        );
 
-            Some(Ok(RepositoryLocation {
-                category: category.name.clone().into(),
-                repository,
-            }))
-        })))
+        Ok(Box::from(repositories.into_iter()))
     }
 
     fn list_categories<U>(&self) -> Result<U>
     where
         U: FromIterator<String>,
     {
-        Ok(self.config.category.keys().cloned().collect())
+        let discovered = match self.config.discover_categories {
+            true => self.config.list_undeclared_categories(),
+            false => Vec::new(),
+        };
+
+        Ok(self
+            .config
+            .category
+            .keys()
+            .cloned()
+            .chain(discovered)
+            .collect())
     }
 
     fn create_repository(&self, location: RepositoryLocation) -> Result<()> {
@@ -205,15 +334,154 @@ where
             });
         }
 
-        let old_repository_directory = self.path_strategy.get_directory(old_location)?;
+        let old_repository_directory = self.path_strategy.get_directory(old_location.clone())?;
+        if has_linked_worktrees(&old_repository_directory) {
+            return Err(DiscoveryStrategyError::RepositoryHasWorktrees {
+                repository: old_location,
+            });
+        }
+
+        let new_containing_directory = self
+            .path_strategy
+            .get_containing_directory(new_location.clone())?;
         let new_repository_directory = self.path_strategy.get_directory(new_location)?;
 
-        fs::rename(old_repository_directory, new_repository_directory)?;
+        // Nested repositories may be moved into a directory which does not exist yet.
+        fs::create_dir_all(new_containing_directory)?;
+        move_directory(&old_repository_directory, &new_repository_directory)?;
 
         Ok(())
     }
 }
 
+/// Whether git keeps track of linked worktrees, which are listed in `.git/worktrees`.
+fn has_linked_worktrees(repository_directory: &Path) -> bool {
+    fs::read_dir(repository_directory.join(".git/worktrees"))
+        .is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Move a directory, falling back to copying when it is moved to another file system.
+///
+/// The copy is verified before the source is removed, a partial copy is removed on failure.
+fn move_directory(source: &Path, target: &Path) -> Result<()> {
+    match fs::rename(source, target) {
+        Err(error) if error.kind() == io::ErrorKind::CrossesDevices => (),
+        result => return Ok(result?),
+    };
+
+    if let Err(error) = copy_directory(source, target).and_then(|_| verify_copy(source, target)) {
+        let _ = fs::remove_dir_all(target);
+        return Err(DiscoveryStrategyError::FilesystemError {
+            context: format!(
+                "When copying '{}' to '{}' on another file system",
+                source.display(),
+                target.display()
+            ),
+            reason: error.to_string(),
+        });
+    }
+
+    fs::remove_dir_all(source)?;
+    Ok(())
+}
+
+/// Recursively copy a directory, symbolic links are copied as links.
+fn copy_directory(source: &Path, target: &Path) -> io::Result<()> {
+    fs::create_dir(target)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &target)?;
+        } else if file_type.is_dir() {
+            copy_directory(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    fs::set_permissions(target, fs::metadata(source)?.permissions())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, target)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, _target: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Cannot copy symbolic link '{}'", source.display()),
+    ))
+}
+
+/// Check that a copy has the same entries as the original, comparing the content of files.
+fn verify_copy(source: &Path, target: &Path) -> io::Result<()> {
+    let mismatch = |path: &Path| {
+        io::Error::other(format!(
+            "The copy of '{}' does not match the original",
+            path.display()
+        ))
+    };
+    let list_names = |path: &Path| -> io::Result<BTreeSet<_>> {
+        fs::read_dir(path)?
+            .map(|entry| Ok(entry?.file_name()))
+            .collect()
+    };
+
+    let names = list_names(source)?;
+    if names != list_names(target)? {
+        return Err(mismatch(source));
+    }
+
+    for name in names {
+        let (source, target) = (source.join(&name), target.join(&name));
+        let source_metadata = fs::symlink_metadata(&source)?;
+        let target_metadata = fs::symlink_metadata(&target)?;
+
+        let matches = if source_metadata.file_type() != target_metadata.file_type() {
+            false
+        } else if source_metadata.is_symlink() {
+            fs::read_link(&source)? == fs::read_link(&target)?
+        } else if source_metadata.is_dir() {
+            verify_copy(&source, &target).is_ok()
+        } else {
+            source_metadata.len() == target_metadata.len()
+                && has_same_content(&source, &target, source_metadata.len())?
+        };
+
+        if !matches {
+            return Err(mismatch(&source));
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare the content of two files of the same size.
+fn has_same_content(source: &Path, target: &Path, size: u64) -> io::Result<bool> {
+    const BUFFER_SIZE: usize = 64 * 1024;
+    let (mut source, mut target) = (File::open(source)?, File::open(target)?);
+    let (mut source_buffer, mut target_buffer) = (vec![0; BUFFER_SIZE], vec![0; BUFFER_SIZE]);
+
+    let mut remaining = size;
+    while remaining > 0 {
+        let length = remaining.min(BUFFER_SIZE as u64) as usize;
+        source.read_exact(&mut source_buffer[..length])?;
+        target.read_exact(&mut target_buffer[..length])?;
+        if source_buffer[..length] != target_buffer[..length] {
+            return Ok(false);
+        }
+        remaining -= length as u64;
+    }
+
+    Ok(true)
+}
+
 impl From<PathStrategyError> for DiscoveryStrategyError {
     fn from(value: PathStrategyError) -> Self {
         match value {
@@ -229,3 +497,49 @@ impl From<PathStrategyError> for DiscoveryStrategyError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_directory() {
+        let root = tempfile::tempdir().unwrap();
+        let source = root.path().join("source");
+        let target = root.path().join("target");
+        fs::create_dir_all(source.join(".git/objects")).unwrap();
+        fs::write(source.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(source.join("README.md"), "# Hello").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("README.md", source.join("link")).unwrap();
+
+        copy_directory(&source, &target).unwrap();
+        verify_copy(&source, &target).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join("README.md")).unwrap(),
+            "# Hello"
+        );
+        assert!(target.join(".git/objects").is_dir());
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read_link(target.join("link")).unwrap(),
+            Path::new("README.md")
+        );
+
+        fs::write(target.join("README.md"), "# Changed content").unwrap();
+        assert!(verify_copy(&source, &target).is_err());
+
+        // Same size, so only the content shows the difference.
+        fs::write(target.join("README.md"), "# Hellp").unwrap();
+        assert!(verify_copy(&source, &target).is_err());
+
+        // Swapped file names, with the same entries and total size.
+        fs::write(target.join("README.md"), "# Hello").unwrap();
+        fs::write(source.join(".git/description"), "first").unwrap();
+        fs::write(source.join(".git/config"), "[core]").unwrap();
+        fs::write(target.join(".git/description"), "[core]").unwrap();
+        fs::write(target.join(".git/config"), "first").unwrap();
+        assert!(verify_copy(&source, &target).is_err());
+    }
+}
@@ -15,7 +15,8 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
 
         match repository {
             ("all_good" | "with_changes" | "with_error", "first" | "second")
-            | ("all_good" | "with_changes", "third") => Ok(DiscoveryExists::Exists),
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => Ok(DiscoveryExists::Exists),
             ("all_good" | "with_changes" | "with_error", _) => {
                 Ok(DiscoveryExists::RepositoryNotFound)
             }
@@ -36,7 +37,7 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
     fn list_repositories_in_category<T>(
         &self,
         category: T,
-    ) -> Result<BoxedIterator<Result<RepositoryLocation>>>
+    ) -> Result<BoxedIterator<'_, Result<RepositoryLocation>>>
     where
         T: AsRef<str>,
     {
@@ -53,6 +54,8 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
                 ("with_changes", "first"),
                 ("with_changes", "second"),
                 ("with_changes", "third"),
+                ("with_changes", "fourth"),
+                ("with_changes", "fifth"),
             ]
             .iter()
             .map(RepositoryLocation::from)
@@ -85,7 +88,8 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
     fn create_repository(&self, location: RepositoryLocation) -> Result<()> {
         match (location.category.as_ref(), location.repository.as_ref()) {
             ("all_good" | "with_changes" | "with_error", "first" | "second")
-            | ("all_good" | "with_changes", "third") => {
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => {
                 return Err(DiscoveryStrategyError::RepositoryExists {
                     context: "When mocking".into(),
                     reason: "Repository already exists".into(),
@@ -107,7 +111,8 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
             old_location.repository.as_ref(),
         ) {
             ("all_good" | "with_changes" | "with_error", "first" | "second")
-            | ("all_good" | "with_changes", "third") => (),
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => (),
             _ => {
                 return Err(DiscoveryStrategyError::RepositoryExists {
                     context: "When mocking".into(),
@@ -121,7 +126,8 @@ impl DiscoveryStrategy for MockDiscoveryStrategy {
             new_location.repository.as_ref(),
         ) {
             ("all_good" | "with_changes" | "with_error", "first" | "second")
-            | ("all_good" | "with_changes", "third") => {
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => {
                 return Err(DiscoveryStrategyError::RepositoryExists {
                     context: "When mocking".into(),
                     reason: "Repository already exists".into(),
@@ -1,14 +1,28 @@
+mod configured;
 mod local;
 mod mock;
-use std::fmt::Display;
+#[cfg(feature = "native-git")]
+mod native;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use thiserror::Error;
 
+pub use configured::ConfiguredGitStrategy;
 pub use local::LocalGitStrategy;
 pub use mock::MockGitStrategy;
+#[cfg(feature = "native-git")]
+pub use native::NativeGitStrategy;
 
 use crate::{
-    dev::{error::GrassError, public::api::RepositoryLocation},
+    dev::{
+        config::GrassConfig,
+        error::GrassError,
+        public::api::{RepositoryLocation, WorktreeLocation},
+    },
     support_strategy,
 };
 
@@ -48,6 +62,428 @@ pub enum GitStrategyError {
 /// [^strategy]: [crate::dev::strategy::git::GitStrategy]
 pub type Result<T> = std::result::Result<T, GitStrategyError>;
 
+/// The result of cleaning a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
+pub struct CleanReport {
+    /// Files which have been removed, relative to the repository root.
+    pub removed_files: Vec<PathBuf>,
+    /// The combined size of the removed files, in bytes.
+    pub freed_bytes: u64,
+}
+
+/// The disk usage of a repository, in bytes.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct RepositorySize {
+    /// Everything in the repository directory, including the `.git` directory.
+    pub total_bytes: u64,
+    /// Ignored files which cleaning the repository would remove.
+    pub reclaimable_bytes: u64,
+    /// The `.git` directory, which holds the history.
+    pub git_bytes: u64,
+}
+
+/// The kind of change made to a file, either in the index or in the worktree.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum FileChangeKind {
+    #[default]
+    Unmodified,
+    Modified,
+    TypeChanged,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+    /// The file is not tracked, this applies to both the index and the worktree.
+    Untracked,
+    /// The file has unresolved conflicts, this applies to both the index and the worktree.
+    Unmerged,
+}
+
+/// Describes the state of a submodule, when a changed file is a submodule.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct SubmoduleChange {
+    /// The submodule points to a different commit.
+    pub commit_changed: bool,
+    /// The submodule has changes to tracked files.
+    pub tracked_changes: bool,
+    /// The submodule has untracked files.
+    pub untracked_changes: bool,
+}
+
+impl SubmoduleChange {
+    /// Whether the worktree of the submodule has tracked or untracked changes.
+    pub fn has_dirty_contents(&self) -> bool {
+        self.tracked_changes || self.untracked_changes
+    }
+}
+
+impl Display for SubmoduleChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.commit_changed, self.has_dirty_contents()) {
+            (true, true) => write!(f, "modified commit, dirty contents"),
+            (true, false) => write!(f, "modified commit"),
+            (false, true) => write!(f, "dirty contents"),
+            (false, false) => write!(f, "up to date"),
+        }
+    }
+}
+
+/// A submodule of a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct Submodule {
+    /// Path of the submodule, relative to the repository root.
+    pub path: PathBuf,
+    /// Whether the submodule has been initialized, and its repository checked out.
+    pub initialized: bool,
+    /// The changes of the submodule, always unchanged when it is not initialized.
+    pub change: SubmoduleChange,
+}
+
+impl Display for Submodule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.initialized {
+            write!(f, "{} ({})", self.path.display(), self.change)
+        } else {
+            write!(f, "{} (uninitialized)", self.path.display())
+        }
+    }
+}
+
+/// A single changed file in a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct FileChange {
+    /// Path of the file, relative to the repository root.
+    pub path: PathBuf,
+    /// The path the file was renamed or copied from, if applicable.
+    pub original_path: Option<PathBuf>,
+    /// The change which is staged in the index.
+    pub staged: FileChangeKind,
+    /// The change in the worktree, which is not staged.
+    pub unstaged: FileChangeKind,
+    /// The state of the submodule, `None` if the file is not a submodule.
+    pub submodule: Option<SubmoduleChange>,
+}
+
+impl Display for FileChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            FileChangeKind::Unmodified => '.',
+            FileChangeKind::Modified => 'M',
+            FileChangeKind::TypeChanged => 'T',
+            FileChangeKind::Added => 'A',
+            FileChangeKind::Deleted => 'D',
+            FileChangeKind::Renamed => 'R',
+            FileChangeKind::Copied => 'C',
+            FileChangeKind::Untracked => '?',
+            FileChangeKind::Unmerged => 'U',
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Display for FileChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{} ", self.staged, self.unstaged)?;
+        if let Some(original_path) = &self.original_path {
+            write!(f, "{} -> ", original_path.display())?;
+        }
+        write!(f, "{}", self.path.display())?;
+        if let Some(submodule) = &self.submodule {
+            write!(f, " (submodule, {})", submodule)?;
+        }
+        Ok(())
+    }
+}
+
+/// An operation which can be left unfinished in a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum GitOperation {
+    Rebase,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Display for GitOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitOperation::Rebase => write!(f, "Rebase"),
+            GitOperation::Merge => write!(f, "Merge"),
+            GitOperation::CherryPick => write!(f, "Cherry-pick"),
+            GitOperation::Revert => write!(f, "Revert"),
+            GitOperation::Bisect => write!(f, "Bisect"),
+        }
+    }
+}
+
+/// Describes how a local branch relates to its upstream.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct BranchTracking {
+    /// The name of the local branch.
+    pub branch: String,
+    /// The name of the upstream branch.
+    ///
+    /// `None` when no upstream is configured, or when the upstream no longer exists.
+    pub upstream: Option<String>,
+    /// Number of commits on the local branch which are not on the upstream.
+    pub ahead: usize,
+    /// Number of commits on the upstream which are not on the local branch.
+    pub behind: usize,
+}
+
+impl BranchTracking {
+    /// Whether the branch contains work which only exists locally.
+    ///
+    /// This is the case when there are unpushed commits, or when there is no upstream at all.
+    pub fn has_unpushed_work(&self) -> bool {
+        self.upstream.is_none() || self.ahead > 0
+    }
+}
+
+impl Display for BranchTracking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.upstream {
+            None => write!(f, "{} (no upstream)", self.branch),
+            Some(upstream) => write!(
+                f,
+                "{} -> {} (ahead {}, behind {})",
+                self.branch, upstream, self.ahead, self.behind
+            ),
+        }
+    }
+}
+
+/// What the HEAD of a repository points to.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub enum HeadState {
+    /// A branch is checked out.
+    Branch(String),
+    /// A commit is checked out directly, identified by its abbreviated id.
+    Detached(String),
+    /// A branch is checked out, which has no commits yet.
+    Unborn(String),
+}
+
+impl Display for HeadState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadState::Branch(branch) => write!(f, "{}", branch),
+            HeadState::Detached(commit) => write!(f, "HEAD detached at {}", commit),
+            HeadState::Unborn(branch) => write!(f, "{} (no commits)", branch),
+        }
+    }
+}
+
+/// A short description of a commit.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct CommitSummary {
+    /// The abbreviated id of the commit.
+    pub id: String,
+    /// The first line of the commit message.
+    pub subject: String,
+    /// When the commit has been made.
+    pub time: SystemTime,
+}
+
+impl CommitSummary {
+    /// How long ago the commit has been made.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.time)
+            .unwrap_or_default()
+    }
+}
+
+/// Describes the current state of a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct RepositoryMetadata {
+    /// What is checked out.
+    pub head: HeadState,
+    /// The commit HEAD points to, `None` when there are no commits.
+    pub last_commit: Option<CommitSummary>,
+}
+
+/// Describes a local branch, to decide whether it can be cleaned up.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct BranchInfo {
+    /// The name of the local branch.
+    pub branch: String,
+    /// The name of the configured upstream branch, also set when it no longer exists.
+    pub upstream: Option<String>,
+    /// Whether the upstream is configured, but has been deleted from the remote.
+    pub upstream_gone: bool,
+    /// Whether this is the default branch of the repository.
+    pub is_default: bool,
+    /// Whether the branch is checked out.
+    pub is_current: bool,
+    /// Whether all commits of the branch are contained in the default branch.
+    ///
+    /// Always `false` for the default branch itself.
+    pub is_merged: bool,
+    /// When the last commit of the branch has been made.
+    pub last_commit: SystemTime,
+}
+
+impl BranchInfo {
+    /// How long ago the last commit of the branch has been made.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.last_commit)
+            .unwrap_or_default()
+    }
+
+    /// Whether the branch has had no commits for longer than the maximum age.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+impl Display for BranchInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.upstream, self.upstream_gone) {
+            (None, _) => write!(f, "{}", self.branch),
+            (Some(upstream), false) => write!(f, "{} -> {}", self.branch, upstream),
+            (Some(upstream), true) => write!(f, "{} -> {} (gone)", self.branch, upstream),
+        }
+    }
+}
+
+/// A worktree of a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct Worktree {
+    /// The directory of the worktree.
+    pub path: PathBuf,
+    /// The branch which is checked out, `None` when HEAD is detached.
+    pub branch: Option<String>,
+    /// Whether this is the main worktree, which is the repository directory itself.
+    pub is_main: bool,
+}
+
+impl Display for Worktree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let branch = self.branch.as_deref().unwrap_or("(detached HEAD)");
+        match self.is_main {
+            true => write!(f, "{} {} (main)", branch, self.path.display()),
+            false => write!(f, "{} {}", branch, self.path.display()),
+        }
+    }
+}
+
+/// A stash entry of a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct Stash {
+    /// The position in the stash list, where `0` is the most recent stash.
+    pub index: usize,
+    /// The message of the stash, like `WIP on main: 1a2b3c4 Add login page`.
+    pub message: String,
+    /// When the stash has been created.
+    pub created: SystemTime,
+}
+
+impl Stash {
+    /// How long ago the stash has been created.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.created)
+            .unwrap_or_default()
+    }
+
+    /// Whether the stash is older than the maximum age.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+}
+
+impl Display for Stash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stash@{{{}}}: {}", self.index, self.message)
+    }
+}
+
+/// How the current branch is updated from its upstream when pulling.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum PullMode {
+    /// Only update when no local commits have to be combined with the upstream.
+    #[default]
+    FastForwardOnly,
+    /// Merge the upstream into the current branch.
+    Merge,
+    /// Rebase the current branch onto the upstream.
+    Rebase,
+}
+
+/// The result of pulling a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub enum PullResult {
+    /// The current branch already contains the upstream.
+    UpToDate,
+    /// The current branch has been updated.
+    Updated {
+        /// The commit before pulling.
+        from: String,
+        /// The commit after pulling.
+        to: String,
+        /// Number of commits which have been pulled in.
+        num_commits: usize,
+    },
+    /// The current branch has no upstream, so only fetching was possible.
+    NoUpstream,
+}
+
+impl Display for PullResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PullResult::UpToDate => write!(f, "Up to date"),
+            PullResult::Updated {
+                from,
+                to,
+                num_commits,
+            } => write!(f, "Updated {}..{} ({} commits)", from, to, num_commits),
+            PullResult::NoUpstream => write!(f, "Fetched, no upstream to pull from"),
+        }
+    }
+}
+
+/// Options used when cloning a repository.
+///
+/// The default clones the full history of the default branch, without submodules.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct CloneOptions {
+    /// The branch to check out, instead of the default branch of the remote.
+    pub branch: Option<String>,
+    /// Only fetch this many commits of history, creating a shallow clone.
+    pub depth: Option<u32>,
+    /// Object filter for a partial clone, like `blob:none`.
+    pub filter: Option<String>,
+    /// Clone the submodules, and their submodules, as well.
+    pub recurse_submodules: bool,
+    /// Check out Git LFS files as pointers, without downloading their content.
+    ///
+    /// The native backend never downloads LFS content, so this is always the case there.
+    pub skip_lfs_smudge: bool,
+}
+
+/// The state of Git LFS in a repository.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct LfsStatus {
+    /// Files tracked by LFS, which are checked out as pointers because the content is missing.
+    ///
+    /// Paths are relative to the repository root.
+    pub missing_objects: Vec<PathBuf>,
+}
+
+/// Options used when initializing a repository.
+///
+/// The template of the category is always copied into the repository, when one is configured.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default)]
+pub struct InitOptions {
+    /// Name of the initial branch, instead of the configured default branch.
+    pub branch: Option<String>,
+    /// Add an 'origin' remote, with the URL from the remote template of the category.
+    pub add_remote: bool,
+}
+
 /// Describes the status of a repository.
 ///
 /// The status is related to whether or not there are changes.
@@ -57,11 +493,49 @@ pub enum RepositoryChangeStatus {
     UpToDate,
     /// No repository has been initialized.
     NoRepository,
+    /// An operation, like a rebase or merge, has been started but not finished.
+    ///
+    /// `num_conflicts` is the number of files with unresolved conflicts.
+    /// This status takes precedence over all other changes.
+    OperationInProgress {
+        operation: GitOperation,
+        num_conflicts: usize,
+    },
+    /// There are files with unresolved conflicts, outside of an operation.
+    ///
+    /// This status takes precedence over uncommitted changes.
+    Conflicts { num_conflicts: usize },
     /// A repository has been initialized, but there are uncommitted changes.
     ///
     /// `num_changes` is not strongly defined, this number may change between versions.
     /// It has no real meaning, and should only be used for generic estimates.
     UncommittedChanges { num_changes: usize },
+    /// All files have been committed, but submodules have changes of their own.
+    ///
+    /// Counts the submodules pointing to a different commit, with changes in their worktree,
+    /// and which are not initialized. A submodule can be counted more than once.
+    /// Uncommitted changes to other files take precedence over this status.
+    SubmoduleChanges {
+        modified_commits: usize,
+        dirty_contents: usize,
+        uninitialized: usize,
+    },
+    /// All changes have been committed, but the content of some Git LFS files is missing.
+    ///
+    /// These files are checked out as pointers, see [GitStrategy::get_lfs_status].
+    /// Uncommitted changes, including submodules, take precedence over this status.
+    MissingLfsObjects { num_files: usize },
+    /// All changes have been committed, but there are stashes which have been forgotten.
+    ///
+    /// Only reported when stale stashes are configured,
+    /// see [crate::dev::config::GrassConfig::get_stale_stash_age].
+    /// Uncommitted changes take precedence over this status.
+    StaleStashes { num_stashes: usize },
+    /// All changes have been committed, but some work only exists locally.
+    ///
+    /// `branches` contains each branch with unpushed commits, or without an upstream.
+    /// Uncommitted changes and stale stashes take precedence over this status.
+    UnpushedWork { branches: Vec<BranchTracking> },
     /// This repository has an unknown status.
     ///
     /// This is only applicable if the unkown status is within the expected behavior of the
@@ -82,9 +556,46 @@ impl Display for RepositoryChangeStatus {
         match self {
             RepositoryChangeStatus::UpToDate => write!(f, "Up to date"),
             RepositoryChangeStatus::NoRepository => write!(f, "Missing repository"),
+            RepositoryChangeStatus::OperationInProgress {
+                operation,
+                num_conflicts: 0,
+            } => write!(f, "{} in progress", operation),
+            RepositoryChangeStatus::OperationInProgress {
+                operation,
+                num_conflicts,
+            } => write!(f, "{} in progress ({} conflicts)", operation, num_conflicts),
+            RepositoryChangeStatus::Conflicts { num_conflicts } => {
+                write!(f, "({}) Unresolved conflicts", num_conflicts)
+            }
             RepositoryChangeStatus::UncommittedChanges { num_changes } => {
                 write!(f, "({}) Uncommitted changes", num_changes)
             }
+            RepositoryChangeStatus::SubmoduleChanges {
+                modified_commits,
+                dirty_contents,
+                uninitialized,
+            } => {
+                let counts = [
+                    (modified_commits, "modified commit"),
+                    (dirty_contents, "dirty"),
+                    (uninitialized, "uninitialized"),
+                ]
+                .into_iter()
+                .filter(|(count, _)| **count > 0)
+                .map(|(count, kind)| format!("{} {}", count, kind))
+                .collect::<Vec<_>>()
+                .join(", ");
+                write!(f, "Submodule changes ({})", counts)
+            }
+            RepositoryChangeStatus::MissingLfsObjects { num_files } => {
+                write!(f, "({}) Missing LFS objects", num_files)
+            }
+            RepositoryChangeStatus::StaleStashes { num_stashes } => {
+                write!(f, "({}) Stale stashes", num_stashes)
+            }
+            RepositoryChangeStatus::UnpushedWork { branches } => {
+                write!(f, "({}) Branches with unpushed work", branches.len())
+            }
             RepositoryChangeStatus::Unknown => write!(f, "Status unknown"),
         }
     }
@@ -99,11 +610,37 @@ pub enum RepositoryChangeStatusWithError {
     UpToDate,
     /// No repository has been initialized.
     NoRepository,
+    /// An operation, like a rebase or merge, has been started but not finished.
+    ///
+    /// `num_conflicts` is the number of files with unresolved conflicts.
+    /// This status takes precedence over all other changes.
+    OperationInProgress {
+        operation: GitOperation,
+        num_conflicts: usize,
+    },
+    /// There are files with unresolved conflicts, outside of an operation.
+    ///
+    /// This status takes precedence over uncommitted changes.
+    Conflicts { num_conflicts: usize },
     /// A repository has been initialized, but there are uncommitted changes.
     ///
     /// `num_changes` is not strongly defined, this number may change between versions.
     /// It has no real meaning, and should only be used for generic estimates.
     UncommittedChanges { num_changes: usize },
+    /// All files have been committed, but submodules have changes of their own.
+    SubmoduleChanges {
+        modified_commits: usize,
+        dirty_contents: usize,
+        uninitialized: usize,
+    },
+    /// All changes have been committed, but the content of some Git LFS files is missing.
+    MissingLfsObjects { num_files: usize },
+    /// All changes have been committed, but there are stashes which have been forgotten.
+    StaleStashes { num_stashes: usize },
+    /// All changes have been committed, but some work only exists locally.
+    ///
+    /// `branches` contains each branch with unpushed commits, or without an upstream.
+    UnpushedWork { branches: Vec<BranchTracking> },
     /// The status is unkown due to an error.
     Error { reason: GrassError },
     /// This repository has an unknown status.
@@ -127,10 +664,12 @@ pub enum RepositoryChangeStatusWithError {
 ///
 /// # Implementations
 ///
-/// | Strategy                                      | Description                   |
-/// | :-------------------------------------------- | :---------------------------- |
-/// | [crate::dev::strategy::git::LocalGitStrategy] | Access local git repositories |
-/// | [crate::dev::strategy::git::MockGitStrategy]  | Mocking implementation        |
+/// | Strategy                                           | Description                                                      |
+/// | :------------------------------------------------- | :--------------------------------------------------------------- |
+/// | [crate::dev::strategy::git::ConfiguredGitStrategy] | Use the backend selected by the `git_backend` configuration key |
+/// | [crate::dev::strategy::git::LocalGitStrategy]      | Access local git repositories                                    |
+/// | `crate::dev::strategy::git::NativeGitStrategy`     | Access local git repositories with libgit2, needs `native-git`   |
+/// | [crate::dev::strategy::git::MockGitStrategy]       | Mocking implementation                                           |
 ///
 /// # See
 ///
@@ -143,14 +682,34 @@ pub trait GitStrategy {
     /// It's purpose then is to clean out optional files,
     /// for example to optimize disk space.
     ///
+    /// Implementations may limit which ignored files are removed.
+    /// In the case of [LocalGitStrategy][^local], clean profiles from the configuration are used.
+    ///
     /// # Example
     ///
     /// ```rust
-    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// use grass::dev::strategy::git::{
+    ///     CleanReport, GitStrategy, GitStrategyError, MockGitStrategy,
+    /// };
+    /// use std::path::PathBuf;
     /// let strategy = MockGitStrategy;
     ///
     /// fn test_strategy<T: GitStrategy>(strategy: &T) {
-    ///     assert_eq!(strategy.clean(("all_good", "first")), Ok(()));
+    ///     assert_eq!(
+    ///         strategy.clean(("all_good", "first")),
+    ///         Ok(CleanReport {
+    ///             removed_files: vec![
+    ///                 PathBuf::from("node_modules/left-pad/index.js"),
+    ///                 PathBuf::from("target/debug/first"),
+    ///             ],
+    ///             freed_bytes: 4096,
+    ///         })
+    ///     );
+    ///
+    ///     assert_eq!(
+    ///         strategy.clean(("all_good", "second")),
+    ///         Ok(CleanReport::default())
+    ///     );
     ///
     ///     assert!(matches!(
     ///         strategy.clean(("with_error", "first")),
@@ -170,62 +729,185 @@ pub trait GitStrategy {
     ///
     /// test_strategy(&strategy);
     /// ```
-    fn clean<T>(&self, repository: T) -> Result<()>
+    ///
+    /// [^local]: [crate::dev::strategy::git::LocalGitStrategy]
+    fn clean<T>(&self, repository: T) -> Result<CleanReport>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Compute the disk usage of a repository, without changing anything.
+    ///
+    /// The reclaimable size uses the same files as [GitStrategy::clean] would remove.
+    /// Symbolic links are not followed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{
+    ///     GitStrategy, GitStrategyError, MockGitStrategy, RepositorySize,
+    /// };
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.get_size(("all_good", "first")),
+    ///         Ok(RepositorySize {
+    ///             total_bytes: 81920,
+    ///             reclaimable_bytes: 4096,
+    ///             git_bytes: 32768,
+    ///         })
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_size(("with_error", "first")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_size(("missing", "first")),
+    ///         Err(GitStrategyError::RepositoryNotFound { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_size<T>(&self, repository: T) -> Result<RepositorySize>
     where
         T: Into<RepositoryLocation>;
 
     /// Clone a remote repository.
     ///
-    /// # Todo:
+    /// Authentication is configured per category, with an SSH key or command,
+    /// a credential helper, or a token read from an environment variable.
     ///
-    /// - [ ] Support authentication:
-    ///     - [ ] SSH
-    ///     - [ ] Password
-    ///     - [ ] PAT
+    /// The options choose the branch, how much history is fetched, and whether submodules are cloned.
+    /// An option which the backend does not support results in an error, instead of being ignored.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// use grass::dev::strategy::git::{
+    ///     CloneOptions, GitStrategy, GitStrategyError, MockGitStrategy,
+    /// };
     /// let strategy = MockGitStrategy;
     ///
     /// fn test_strategy<T: GitStrategy>(strategy: &T) {
-    ///     assert_eq!(strategy.clone(("all_good", "new"), "good_remote"), Ok(()));
+    ///     let options = CloneOptions::default();
+    ///
+    ///     assert_eq!(
+    ///         strategy.clone(("all_good", "new"), "good_remote", &options),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert_eq!(
+    ///         strategy.clone(
+    ///             ("all_good", "new"),
+    ///             "good_remote",
+    ///             &CloneOptions {
+    ///                 branch: Some("develop".into()),
+    ///                 depth: Some(1),
+    ///                 ..Default::default()
+    ///             }
+    ///         ),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.clone(
+    ///             ("all_good", "new"),
+    ///             "good_remote",
+    ///             &CloneOptions {
+    ///                 branch: Some("missing".into()),
+    ///                 ..Default::default()
+    ///             }
+    ///         ),
+    ///         Err(GitStrategyError::RemoteFetchError { .. })
+    ///     ));
     ///
     ///     assert!(matches!(
-    ///         strategy.clone(("all_good", "first"), "good_remote"),
+    ///         strategy.clone(("all_good", "first"), "good_remote", &options),
     ///         Err(GitStrategyError::RepositryExists { .. })
     ///     ));
     ///
     ///     assert!(matches!(
-    ///         strategy.clone(("missing", "first"), "good_remote"),
+    ///         strategy.clone(("missing", "first"), "good_remote", &options),
     ///         Err(GitStrategyError::RepositoryNotFound { .. })
     ///     ));
     ///
     ///     assert!(matches!(
-    ///         strategy.clone(("all_good", "new"), "no_access"),
+    ///         strategy.clone(("all_good", "new"), "no_access", &options),
     ///         Err(GitStrategyError::RemoteAuthenticationError { .. })
     ///     ));
     ///
     ///     assert!(matches!(
-    ///         strategy.clone(("all_good", "new"), "bad_response"),
+    ///         strategy.clone(("all_good", "new"), "bad_response", &options),
     ///         Err(GitStrategyError::RemoteFetchError { .. })
     ///     ));
     /// }
     ///
     /// test_strategy(&strategy);
     /// ```
-    fn clone<T, U>(&self, repository: T, remote: U) -> Result<()>
+    fn clone<T, U>(&self, repository: T, remote: U, options: &CloneOptions) -> Result<()>
     where
         T: Into<RepositoryLocation>,
         U: AsRef<str>;
 
+    /// Initialize a git repository, in the existing directory of a repository.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{
+    ///     GitStrategy, GitStrategyError, InitOptions, MockGitStrategy,
+    /// };
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     let options = InitOptions {
+    ///         branch: Some("main".into()),
+    ///         add_remote: true,
+    ///     };
+    ///
+    ///     assert_eq!(strategy.init(("all_good", "new"), &options), Ok(()));
+    ///     assert_eq!(
+    ///         strategy.init(("with_changes", "second"), &InitOptions::default()),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.init(("all_good", "first"), &options),
+    ///         Err(GitStrategyError::RepositryExists { .. })
+    ///     ));
+    ///
+    ///     // There is no remote template for the category
+    ///     assert!(matches!(
+    ///         strategy.init(("with_changes", "new"), &options),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.init(("missing", "new"), &options),
+    ///         Err(GitStrategyError::RepositoryNotFound { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn init<T>(&self, repository: T, options: &InitOptions) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
     /// Get the change status for a repository.
     ///
+    /// When `recurse_submodules` is configured, changed files inside submodules count as
+    /// uncommitted changes of the repository.
+    ///
     /// # Example
     ///
     /// ```rust
-    /// use grass::dev::strategy::git::{GitStrategy, MockGitStrategy, RepositoryChangeStatus};
+    /// use grass::dev::strategy::git::{
+    ///     GitOperation, GitStrategy, MockGitStrategy, RepositoryChangeStatus,
+    /// };
     /// let strategy = MockGitStrategy;
     ///
     /// fn test_strategy<T: GitStrategy>(strategy: &T) {
@@ -243,6 +925,19 @@ pub trait GitStrategy {
     ///         strategy.get_changes(("with_changes", "third")),
     ///         Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 9 })
     ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_changes(("with_changes", "fourth")),
+    ///         Ok(RepositoryChangeStatus::UnpushedWork { .. })
+    ///     ));
+    ///
+    ///     assert_eq!(
+    ///         strategy.get_changes(("with_changes", "fifth")),
+    ///         Ok(RepositoryChangeStatus::OperationInProgress {
+    ///             operation: GitOperation::Rebase,
+    ///             num_conflicts: 2,
+    ///         })
+    ///     );
     /// }
     ///
     /// test_strategy(&strategy);
@@ -250,19 +945,639 @@ pub trait GitStrategy {
     fn get_changes<T>(&self, repository: T) -> Result<RepositoryChangeStatus>
     where
         T: Into<RepositoryLocation>;
-}
-
-support_strategy!(SupportsGit, get_git_strategy, GitStrategy);
 
-impl GitStrategyError {
-    pub fn with_message<T>(self, message: T) -> Self
+    /// Get every changed file in a repository.
+    ///
+    /// Ignored files are not included.
+    /// When `recurse_submodules` is configured, the changed files inside submodules are included,
+    /// with paths relative to the repository root.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{
+    ///     FileChange, FileChangeKind, GitStrategy, GitStrategyError, MockGitStrategy,
+    /// };
+    /// use std::path::PathBuf;
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.get_change_details(("with_changes", "first")), Ok(vec![]));
+    ///
+    ///     let changes = strategy.get_change_details(("with_changes", "third")).unwrap();
+    ///     assert_eq!(changes.len(), 9);
+    ///     assert_eq!(
+    ///         changes[0],
+    ///         FileChange {
+    ///             path: PathBuf::from("src/main.rs"),
+    ///             original_path: None,
+    ///             staged: FileChangeKind::Modified,
+    ///             unstaged: FileChangeKind::Unmodified,
+    ///             submodule: None,
+    ///         }
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_change_details(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_change_details<T>(&self, repository: T) -> Result<Vec<FileChange>>
     where
-        T: Into<String>,
-    {
-        use GitStrategyError::*;
+        T: Into<RepositoryLocation>;
 
-        let message = message.into();
-        match self {
+    /// Get the Git LFS state of a repository, `None` when the repository does not use LFS.
+    ///
+    /// A repository uses LFS when its `.gitattributes` assigns the `lfs` filter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, MockGitStrategy};
+    /// use std::path::PathBuf;
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.get_lfs_status(("all_good", "first")), Ok(None));
+    ///
+    ///     // The missing content is not reported, because of the uncommitted changes.
+    ///     let status = strategy.get_lfs_status(("with_changes", "third")).unwrap().unwrap();
+    ///     assert_eq!(status.missing_objects, vec![PathBuf::from("assets/logo.png")]);
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_lfs_status<T>(&self, repository: T) -> Result<Option<LfsStatus>>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// List the submodules of a repository, sorted by path.
+    ///
+    /// When `recurse_submodules` is configured, the submodules of submodules are listed as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// use std::path::PathBuf;
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.list_submodules(("all_good", "first")), Ok(vec![]));
+    ///
+    ///     let submodules = strategy.list_submodules(("with_changes", "third")).unwrap();
+    ///     assert_eq!(submodules.len(), 2);
+    ///     assert_eq!(submodules[0].path, PathBuf::from("vendor/docs"));
+    ///     assert!(!submodules[0].initialized);
+    ///     assert!(submodules[1].change.commit_changed);
+    ///
+    ///     assert!(matches!(
+    ///         strategy.list_submodules(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn list_submodules<T>(&self, repository: T) -> Result<Vec<Submodule>>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Get which branch is checked out, and the last commit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, HeadState, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     let metadata = strategy.get_metadata(("all_good", "first")).unwrap();
+    ///     assert_eq!(metadata.head, HeadState::Branch("main".into()));
+    ///     assert_eq!(metadata.last_commit.unwrap().subject, "Initial commit");
+    ///
+    ///     let metadata = strategy.get_metadata(("with_changes", "fifth")).unwrap();
+    ///     assert_eq!(metadata.head, HeadState::Detached("1a2b3c4".into()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_metadata(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_metadata<T>(&self, repository: T) -> Result<RepositoryMetadata>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Get how each local branch relates to its upstream.
+    ///
+    /// Every local branch is returned, including branches without an upstream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{BranchTracking, GitStrategy, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.get_branch_tracking(("with_changes", "fourth")),
+    ///         Ok(vec![
+    ///             BranchTracking {
+    ///                 branch: "feature".into(),
+    ///                 upstream: None,
+    ///                 ahead: 0,
+    ///                 behind: 0,
+    ///             },
+    ///             BranchTracking {
+    ///                 branch: "main".into(),
+    ///                 upstream: Some("origin/main".into()),
+    ///                 ahead: 2,
+    ///                 behind: 1,
+    ///             },
+    ///         ])
+    ///     );
+    ///
+    ///     assert_eq!(
+    ///         strategy.get_branch_tracking(("all_good", "first")),
+    ///         Ok(vec![BranchTracking {
+    ///             branch: "main".into(),
+    ///             upstream: Some("origin/main".into()),
+    ///             ahead: 0,
+    ///             behind: 0,
+    ///         }])
+    ///     );
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_branch_tracking<T>(&self, repository: T) -> Result<Vec<BranchTracking>>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// List the local branches of a repository, sorted by name.
+    ///
+    /// The default branch is the branch `origin/HEAD` points to,
+    /// or else a local `main` or `master` branch.
+    /// Without a default branch, no branch is considered merged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// use std::time::Duration;
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     let branches = strategy.list_branches(("with_changes", "first")).unwrap();
+    ///     let names: Vec<_> = branches.iter().map(|branch| branch.branch.as_str()).collect();
+    ///     assert_eq!(names, vec!["experiment", "main", "old-feature", "removed"]);
+    ///
+    ///     assert!(branches[0].is_stale(Duration::from_secs(90 * 24 * 60 * 60)));
+    ///     assert!(branches[1].is_default && branches[1].is_current);
+    ///     assert!(branches[2].is_merged);
+    ///     assert!(branches[3].upstream_gone);
+    ///
+    ///     assert!(matches!(
+    ///         strategy.list_branches(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn list_branches<T>(&self, repository: T) -> Result<Vec<BranchInfo>>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Delete a local branch of a repository.
+    ///
+    /// Unless forced, only branches which are merged into the default branch are deleted.
+    /// The branch which is checked out is never deleted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.delete_branch(("with_changes", "first"), "old-feature", false),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.delete_branch(("with_changes", "first"), "experiment", false),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///     assert_eq!(
+    ///         strategy.delete_branch(("with_changes", "first"), "experiment", true),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.delete_branch(("with_changes", "first"), "main", true),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///     assert!(matches!(
+    ///         strategy.delete_branch(("with_changes", "first"), "missing", false),
+    ///         Err(GitStrategyError::RepositoryNotFound { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn delete_branch<T>(&self, repository: T, branch: &str, force: bool) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Fetch the remotes of a repository, without changing any local branch.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.fetch(("all_good", "first")), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.fetch(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.fetch(("all_good", "third")),
+    ///         Err(GitStrategyError::RemoteAuthenticationError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn fetch<T>(&self, repository: T) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Fetch, and update the current branch from its upstream.
+    ///
+    /// Uncommitted changes are left alone, so check the change status beforehand.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{
+    ///     GitStrategy, GitStrategyError, MockGitStrategy, PullMode, PullResult,
+    /// };
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.pull(("all_good", "first"), PullMode::FastForwardOnly),
+    ///         Ok(PullResult::Updated {
+    ///             from: "1a2b3c4".into(),
+    ///             to: "5d6e7f8".into(),
+    ///             num_commits: 3,
+    ///         })
+    ///     );
+    ///
+    ///     assert_eq!(
+    ///         strategy.pull(("all_good", "second"), PullMode::FastForwardOnly),
+    ///         Ok(PullResult::UpToDate)
+    ///     );
+    ///
+    ///     // 'main' has diverged from 'origin/main'
+    ///     assert!(matches!(
+    ///         strategy.pull(("with_changes", "fourth"), PullMode::FastForwardOnly),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///     assert!(matches!(
+    ///         strategy.pull(("with_changes", "fourth"), PullMode::Rebase),
+    ///         Ok(PullResult::Updated { num_commits: 1, .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn pull<T>(&self, repository: T, mode: PullMode) -> Result<PullResult>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Stage every change, including untracked files, and commit them.
+    ///
+    /// Fails when there is nothing to commit, or when an operation like a rebase is in progress.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.commit_all(("with_changes", "third"), "Update notes"),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     // There is nothing to commit
+    ///     assert!(matches!(
+    ///         strategy.commit_all(("all_good", "first"), "Update notes"),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.commit_all(("with_changes", "fifth"), "Update notes"),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn commit_all<T>(&self, repository: T, message: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Push the current branch to its upstream.
+    ///
+    /// A branch without upstream is pushed to 'origin', and tracks the pushed branch afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.push(("all_good", "first")), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.push(("all_good", "third")),
+    ///         Err(GitStrategyError::RemoteAuthenticationError { .. })
+    ///     ));
+    ///
+    ///     // 'main' has diverged from 'origin/main', so the push is rejected
+    ///     assert!(matches!(
+    ///         strategy.push(("with_changes", "fourth")),
+    ///         Err(GitStrategyError::RemoteFetchError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn push<T>(&self, repository: T) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Push the current branch to a specific remote, and track the pushed branch afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.push_to_remote(("all_good", "first"), "upstream"), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.push_to_remote(("all_good", "first"), "missing"),
+    ///         Err(GitStrategyError::RemoteFetchError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn push_to_remote<T>(&self, repository: T, remote: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Add a remote to a repository.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.add_remote(("all_good", "first"), "fork", "https://example.com/fork.git"),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     // 'origin' already exists
+    ///     assert!(matches!(
+    ///         strategy.add_remote(("all_good", "first"), "origin", "https://example.com/fork.git"),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn add_remote<T>(&self, repository: T, name: &str, url: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Rename a remote of a repository, branches tracking the remote are updated as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.rename_remote(("all_good", "first"), "origin", "upstream"),
+    ///         Ok(())
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.rename_remote(("all_good", "first"), "missing", "upstream"),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn rename_remote<T>(&self, repository: T, from: &str, to: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// List the worktrees of a repository.
+    ///
+    /// The main worktree comes first, the other worktrees are sorted by path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy, Worktree};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.list_worktrees(("all_good", "first")),
+    ///         Ok(vec![
+    ///             Worktree {
+    ///                 path: "/home/example/repositories/all_good/first".into(),
+    ///                 branch: Some("main".into()),
+    ///                 is_main: true,
+    ///             },
+    ///             Worktree {
+    ///                 path: "/home/example/worktrees/all_good/first/feature".into(),
+    ///                 branch: Some("feature".into()),
+    ///                 is_main: false,
+    ///             },
+    ///         ])
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.list_worktrees(("with_error", "first")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn list_worktrees<T>(&self, repository: T) -> Result<Vec<Worktree>>
+    where
+        T: Into<RepositoryLocation>;
+
+    /// Add a worktree for a branch, in the directory given by the path strategy.
+    ///
+    /// An existing local branch is checked out.
+    /// Otherwise a branch is created, which tracks the remote branch with the same name if there
+    /// is exactly one, or starts at HEAD if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.add_worktree(("all_good", "first", "bugfix")), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.add_worktree(("all_good", "first", "feature")),
+    ///         Err(GitStrategyError::RepositryExists { .. })
+    ///     ));
+    ///
+    ///     // 'main' is already checked out in the main worktree
+    ///     assert!(matches!(
+    ///         strategy.add_worktree(("all_good", "first", "main")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn add_worktree<T>(&self, worktree: T) -> Result<()>
+    where
+        T: Into<WorktreeLocation>;
+
+    /// Remove the worktree which has the branch checked out.
+    ///
+    /// The branch itself is kept.
+    /// A worktree with uncommitted changes is only removed when forced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.remove_worktree(("all_good", "first", "feature"), false), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.remove_worktree(("with_changes", "first", "feature"), false),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///     assert_eq!(strategy.remove_worktree(("with_changes", "first", "feature"), true), Ok(()));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.remove_worktree(("all_good", "first", "main"), true),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.remove_worktree(("all_good", "second", "feature"), false),
+    ///         Err(GitStrategyError::RepositoryNotFound { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn remove_worktree<T>(&self, worktree: T, force: bool) -> Result<()>
+    where
+        T: Into<WorktreeLocation>;
+
+    /// List the stashes of a repository, the most recent stash first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::git::{GitStrategy, GitStrategyError, MockGitStrategy};
+    /// use std::time::Duration;
+    /// let strategy = MockGitStrategy;
+    ///
+    /// fn test_strategy<T: GitStrategy>(strategy: &T) {
+    ///     assert_eq!(strategy.list_stashes(("all_good", "first")), Ok(vec![]));
+    ///
+    ///     let stashes = strategy.list_stashes(("with_changes", "third")).unwrap();
+    ///     assert_eq!(stashes.len(), 2);
+    ///     assert_eq!(stashes[0].index, 0);
+    ///     assert_eq!(stashes[1].message, "On main: Try another layout");
+    ///     assert!(stashes[1].is_stale(Duration::from_secs(30 * 24 * 60 * 60)));
+    ///
+    ///     assert!(matches!(
+    ///         strategy.list_stashes(("with_changes", "second")),
+    ///         Err(GitStrategyError::RepositoryError { .. })
+    ///     ));
+    /// }
+    ///
+    /// test_strategy(&strategy);
+    /// ```
+    fn list_stashes<T>(&self, repository: T) -> Result<Vec<Stash>>
+    where
+        T: Into<RepositoryLocation>;
+}
+
+support_strategy!(SupportsGit, get_git_strategy, GitStrategy);
+
+/// Get the remote URL of a repository, from the remote template of its category.
+pub(crate) fn get_configured_remote_url(
+    config: &GrassConfig,
+    repository: &RepositoryLocation,
+) -> Result<String> {
+    config
+        .get_by_category(&repository.category)
+        .and_then(|category| category.get_remote_url(&repository.repository))
+        .ok_or_else(|| GitStrategyError::RepositoryError {
+            message: format!("Cannot get the remote URL of '{}'", repository),
+            reason: format!(
+                "No remote template is configured for the category '{}', add a 'remote_template' to it",
+                repository.category
+            ),
+        })
+}
+
+impl GitStrategyError {
+    pub fn with_message<T>(self, message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        use GitStrategyError::*;
+
+        let message = message.into();
+        match self {
             RepositoryNotFound { reason, .. } => RepositoryNotFound { message, reason },
             RepositoryError { reason, .. } => RepositoryError { message, reason },
             RepositryExists { reason, .. } => RepositryExists { message, reason },
@@ -315,9 +1630,37 @@ impl From<RepositoryChangeStatus> for RepositoryChangeStatusWithError {
         match value {
             RepositoryChangeStatus::UpToDate => RepositoryChangeStatusWithError::UpToDate,
             RepositoryChangeStatus::NoRepository => RepositoryChangeStatusWithError::NoRepository,
+            RepositoryChangeStatus::OperationInProgress {
+                operation,
+                num_conflicts,
+            } => RepositoryChangeStatusWithError::OperationInProgress {
+                operation,
+                num_conflicts,
+            },
+            RepositoryChangeStatus::Conflicts { num_conflicts } => {
+                RepositoryChangeStatusWithError::Conflicts { num_conflicts }
+            }
             RepositoryChangeStatus::UncommittedChanges { num_changes } => {
                 RepositoryChangeStatusWithError::UncommittedChanges { num_changes }
             }
+            RepositoryChangeStatus::SubmoduleChanges {
+                modified_commits,
+                dirty_contents,
+                uninitialized,
+            } => RepositoryChangeStatusWithError::SubmoduleChanges {
+                modified_commits,
+                dirty_contents,
+                uninitialized,
+            },
+            RepositoryChangeStatus::MissingLfsObjects { num_files } => {
+                RepositoryChangeStatusWithError::MissingLfsObjects { num_files }
+            }
+            RepositoryChangeStatus::StaleStashes { num_stashes } => {
+                RepositoryChangeStatusWithError::StaleStashes { num_stashes }
+            }
+            RepositoryChangeStatus::UnpushedWork { branches } => {
+                RepositoryChangeStatusWithError::UnpushedWork { branches }
+            }
             RepositoryChangeStatus::Unknown => RepositoryChangeStatusWithError::Unknown,
         }
     }
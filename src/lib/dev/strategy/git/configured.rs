@@ -0,0 +1,209 @@
+use crate::dev::{
+    config::{GitBackend, GrassConfig},
+    strategy::path::PathStrategy,
+    WorktreeLocation,
+};
+
+#[cfg(feature = "native-git")]
+use super::NativeGitStrategy;
+use super::{
+    BranchInfo, BranchTracking, CleanReport, CloneOptions, FileChange, GitStrategy, InitOptions,
+    LfsStatus, LocalGitStrategy, PullMode, PullResult, RepositoryChangeStatus, RepositoryLocation,
+    RepositoryMetadata, RepositorySize, Result, Stash, Submodule, Worktree,
+};
+
+/// Strategy which uses the git backend selected in the configuration.
+///
+/// See [crate::dev::config::GitBackend] for the available backends.
+pub enum ConfiguredGitStrategy<'a, T: PathStrategy> {
+    Cli(LocalGitStrategy<'a, T>),
+    #[cfg(feature = "native-git")]
+    Native(NativeGitStrategy<'a, T>),
+}
+
+impl<'a, T: PathStrategy> ConfiguredGitStrategy<'a, T> {
+    pub fn new(config: &'a GrassConfig, path_strategy: &'a T) -> Self {
+        match config.git_backend {
+            #[cfg(feature = "native-git")]
+            GitBackend::Native => Self::Native(NativeGitStrategy::new(config, path_strategy)),
+            // Loading the configuration fails when the native backend is not available.
+            #[cfg(not(feature = "native-git"))]
+            GitBackend::Native => Self::Cli(LocalGitStrategy::new(config, path_strategy)),
+            GitBackend::Cli => Self::Cli(LocalGitStrategy::new(config, path_strategy)),
+        }
+    }
+}
+
+macro_rules! dispatch {
+    ($self:ident, $strategy:ident => $call:expr) => {
+        match $self {
+            Self::Cli($strategy) => $call,
+            #[cfg(feature = "native-git")]
+            Self::Native($strategy) => $call,
+        }
+    };
+}
+
+impl<'a, T: PathStrategy> GitStrategy for ConfiguredGitStrategy<'a, T> {
+    fn clean<U>(&self, repository: U) -> Result<CleanReport>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.clean(repository))
+    }
+
+    fn get_size<U>(&self, repository: U) -> Result<RepositorySize>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_size(repository))
+    }
+
+    fn clone<U, V>(&self, repository: U, remote: V, options: &CloneOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+        V: AsRef<str>,
+    {
+        dispatch!(self, strategy => strategy.clone(repository, remote, options))
+    }
+
+    fn init<U>(&self, repository: U, options: &InitOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.init(repository, options))
+    }
+
+    fn get_changes<U>(&self, repository: U) -> Result<RepositoryChangeStatus>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_changes(repository))
+    }
+
+    fn get_change_details<U>(&self, repository: U) -> Result<Vec<FileChange>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_change_details(repository))
+    }
+
+    fn get_lfs_status<U>(&self, repository: U) -> Result<Option<LfsStatus>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_lfs_status(repository))
+    }
+
+    fn list_submodules<U>(&self, repository: U) -> Result<Vec<Submodule>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.list_submodules(repository))
+    }
+
+    fn get_metadata<U>(&self, repository: U) -> Result<RepositoryMetadata>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_metadata(repository))
+    }
+
+    fn get_branch_tracking<U>(&self, repository: U) -> Result<Vec<BranchTracking>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.get_branch_tracking(repository))
+    }
+
+    fn list_branches<U>(&self, repository: U) -> Result<Vec<BranchInfo>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.list_branches(repository))
+    }
+
+    fn delete_branch<U>(&self, repository: U, branch: &str, force: bool) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.delete_branch(repository, branch, force))
+    }
+
+    fn fetch<U>(&self, repository: U) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.fetch(repository))
+    }
+
+    fn pull<U>(&self, repository: U, mode: PullMode) -> Result<PullResult>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.pull(repository, mode))
+    }
+
+    fn commit_all<U>(&self, repository: U, message: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.commit_all(repository, message))
+    }
+
+    fn push<U>(&self, repository: U) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.push(repository))
+    }
+
+    fn push_to_remote<U>(&self, repository: U, remote: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.push_to_remote(repository, remote))
+    }
+
+    fn add_remote<U>(&self, repository: U, name: &str, url: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.add_remote(repository, name, url))
+    }
+
+    fn rename_remote<U>(&self, repository: U, from: &str, to: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.rename_remote(repository, from, to))
+    }
+
+    fn list_worktrees<U>(&self, repository: U) -> Result<Vec<Worktree>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.list_worktrees(repository))
+    }
+
+    fn add_worktree<U>(&self, worktree: U) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        dispatch!(self, strategy => strategy.add_worktree(worktree))
+    }
+
+    fn remove_worktree<U>(&self, worktree: U, force: bool) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        dispatch!(self, strategy => strategy.remove_worktree(worktree, force))
+    }
+
+    fn list_stashes<U>(&self, repository: U) -> Result<Vec<Stash>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        dispatch!(self, strategy => strategy.list_stashes(repository))
+    }
+}
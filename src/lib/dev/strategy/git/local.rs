@@ -1,164 +1,2107 @@
-use std::{fs, process::Command};
+use std::{
+    collections::BTreeSet,
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::OnceLock,
+    thread,
+    time::{Duration, UNIX_EPOCH},
+};
 
-use crate::dev::strategy::path::{PathStrategy, PathStrategyError};
+use sha2::{Digest, Sha256};
 
-use super::{GitStrategy, GitStrategyError, RepositoryChangeStatus, RepositoryLocation, Result};
+use crate::dev::{
+    config::{glob_matches, GrassConfig},
+    strategy::path::{PathStrategy, PathStrategyError},
+    Category, WorktreeLocation,
+};
 
-// I may be using this later, but for now allow it to exist
-#[allow(dead_code)]
+use super::{
+    get_configured_remote_url, BranchInfo, BranchTracking, CleanReport, CloneOptions,
+    CommitSummary, FileChange, FileChangeKind, GitOperation, GitStrategy, GitStrategyError,
+    HeadState, InitOptions, LfsStatus, PullMode, PullResult, RepositoryChangeStatus,
+    RepositoryLocation, RepositoryMetadata, RepositorySize, Result, Stash, Submodule,
+    SubmoduleChange, Worktree,
+};
+
+/// A record of `git status --porcelain=v2`, with only the fields which are used.
 #[derive(Debug)]
 enum GitInternalChangeRepresentation<'a> {
     Untracked {
         path: &'a str,
     },
-    Ignored {
-        path: &'a str,
-    },
+    Ignored,
     Ordinary {
         status: &'a str,
         sub_module: &'a str,
-        file_mode_head: &'a str,
-        file_mode_index: &'a str,
-        file_mode_worktree: &'a str,
-        object_name_head: &'a str,
-        object_name_index: &'a str,
         path: &'a str,
     },
     Moved {
         status: &'a str,
         sub_module: &'a str,
-        file_mode_head: &'a str,
-        file_mode_index: &'a str,
-        file_mode_worktree: &'a str,
-        object_name_head: &'a str,
-        object_name_index: &'a str,
-        score: &'a str,
         path: &'a str,
+        original_path: &'a str,
     },
+    Unmerged {
+        sub_module: &'a str,
+        path: &'a str,
+    },
+}
+
+impl<'a> GitInternalChangeRepresentation<'a> {
+    fn to_file_change(&self) -> Option<FileChange> {
+        match self {
+            GitInternalChangeRepresentation::Untracked { path } => Some(FileChange {
+                path: PathBuf::from(path),
+                original_path: None,
+                staged: FileChangeKind::Untracked,
+                unstaged: FileChangeKind::Untracked,
+                submodule: None,
+            }),
+            GitInternalChangeRepresentation::Ignored => None,
+            GitInternalChangeRepresentation::Ordinary {
+                status,
+                sub_module,
+                path,
+            } => {
+                let (staged, unstaged) = parse_status_pair(status);
+                Some(FileChange {
+                    path: PathBuf::from(path),
+                    original_path: None,
+                    staged,
+                    unstaged,
+                    submodule: parse_submodule(sub_module),
+                })
+            }
+            GitInternalChangeRepresentation::Moved {
+                status,
+                sub_module,
+                path,
+                original_path,
+            } => {
+                let (staged, unstaged) = parse_status_pair(status);
+                Some(FileChange {
+                    path: PathBuf::from(path),
+                    original_path: Some(PathBuf::from(original_path)),
+                    staged,
+                    unstaged,
+                    submodule: parse_submodule(sub_module),
+                })
+            }
+            GitInternalChangeRepresentation::Unmerged { sub_module, path } => Some(FileChange {
+                path: PathBuf::from(path),
+                original_path: None,
+                staged: FileChangeKind::Unmerged,
+                unstaged: FileChangeKind::Unmerged,
+                submodule: parse_submodule(sub_module),
+            }),
+        }
+    }
+}
+
+fn parse_change_kind(value: char) -> FileChangeKind {
+    match value {
+        '.' => FileChangeKind::Unmodified,
+        'T' => FileChangeKind::TypeChanged,
+        'A' => FileChangeKind::Added,
+        'D' => FileChangeKind::Deleted,
+        'R' => FileChangeKind::Renamed,
+        'C' => FileChangeKind::Copied,
+        _ => FileChangeKind::Modified,
+    }
+}
+
+/// Parse the `XY` field, where `X` is the staged and `Y` the unstaged state.
+fn parse_status_pair(status: &str) -> (FileChangeKind, FileChangeKind) {
+    let mut chars = status.chars();
+    (
+        parse_change_kind(chars.next().unwrap_or('.')),
+        parse_change_kind(chars.next().unwrap_or('.')),
+    )
+}
+
+/// Parse the submodule field, which is `N...` for regular files, or `S<c><m><u>` for submodules.
+fn parse_submodule(sub_module: &str) -> Option<SubmoduleChange> {
+    let flags: Box<[_]> = sub_module.strip_prefix('S')?.chars().collect();
+    let [commit, tracked, untracked] = flags.as_ref() else {
+        return None;
+    };
+
+    Some(SubmoduleChange {
+        commit_changed: *commit == 'C',
+        tracked_changes: *tracked == 'M',
+        untracked_changes: *untracked == 'U',
+    })
+}
+
+/// Parse the output of `git status --porcelain=v2 -z`.
+///
+/// Records are separated by NUL, and paths are not quoted.
+/// The original path of a moved file is the record after it.
+fn parse_porcelain_v2(output: &str) -> Vec<GitInternalChangeRepresentation<'_>> {
+    let mut records = output.split('\0');
+    let mut changes = Vec::new();
+
+    while let Some(record) = records.next() {
+        let change = match record.split_at_checked(2) {
+            Some(("? ", path)) => Some(GitInternalChangeRepresentation::Untracked { path }),
+            Some(("! ", _)) => Some(GitInternalChangeRepresentation::Ignored),
+            Some(("1 ", content)) => match *content.splitn(8, ' ').collect::<Box<[_]>>() {
+                [status, sub_module, _, _, _, _, _, path] => {
+                    Some(GitInternalChangeRepresentation::Ordinary {
+                        status,
+                        sub_module,
+                        path,
+                    })
+                }
+                _ => None,
+            },
+            Some(("2 ", content)) => match *content.splitn(9, ' ').collect::<Box<[_]>>() {
+                [status, sub_module, _, _, _, _, _, _, path] => {
+                    Some(GitInternalChangeRepresentation::Moved {
+                        status,
+                        sub_module,
+                        path,
+                        original_path: records.next().unwrap_or_default(),
+                    })
+                }
+                _ => None,
+            },
+            Some(("u ", content)) => match *content.splitn(10, ' ').collect::<Box<[_]>>() {
+                [_, sub_module, _, _, _, _, _, _, _, path] => {
+                    Some(GitInternalChangeRepresentation::Unmerged { sub_module, path })
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        changes.extend(change);
+    }
+
+    changes
+}
+
+/// Find an operation which has been started, but not finished.
+///
+/// Git tracks these operations using files in the git directory.
+fn get_operation_in_progress(repository_path: &Path) -> Result<Option<GitOperation>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args(["rev-parse", "--git-dir"])
+        .output()
+        .map_err(|error| GitStrategyError::RepositoryError {
+            message: String::from("Could not find the git directory"),
+            reason: error.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from("Error when running git rev-parse"),
+            reason: String::from_utf8(output.stderr)
+                .unwrap_or(String::from("stderr is not valid utf8")),
+        });
+    }
+
+    let git_directory =
+        String::from_utf8(output.stdout).map_err(|_| GitStrategyError::UnknownError {
+            message: String::from("Cannot parse git output"),
+            reason: String::from("Output is not valid utf8"),
+        })?;
+    // A relative path is relative to the repository, joining leaves absolute paths as is.
+    let git_directory = repository_path.join(git_directory.trim_end());
+
+    let markers = [
+        ("rebase-merge", GitOperation::Rebase),
+        ("rebase-apply", GitOperation::Rebase),
+        ("MERGE_HEAD", GitOperation::Merge),
+        ("CHERRY_PICK_HEAD", GitOperation::CherryPick),
+        ("REVERT_HEAD", GitOperation::Revert),
+        ("BISECT_LOG", GitOperation::Bisect),
+    ];
+
+    Ok(markers
+        .into_iter()
+        .find(|(marker, _)| git_directory.join(marker).exists())
+        .map(|(_, operation)| operation))
+}
+
+/// Run a git command which only touches the local repository, and return its output.
+fn run_git(repository_path: &Path, args: &[&str], message: &str) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args(args)
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|error| GitStrategyError::RepositoryError {
+            message: String::from(message),
+            reason: error.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from(message),
+            reason: String::from_utf8(output.stderr)
+                .unwrap_or(String::from("stderr is not valid utf8")),
+        });
+    }
+
+    String::from_utf8(output.stdout)
+        .map(|output| output.trim_end().to_string())
+        .map_err(|_| GitStrategyError::UnknownError {
+            message: String::from("Cannot parse git output"),
+            reason: String::from("Output is not valid utf8"),
+        })
+}
+
+fn get_porcelain_status(repository_path: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args(["status", "--porcelain=v2", "-z"])
+        .output()
+        .map_err(|error| GitStrategyError::RepositoryError {
+            message: String::from("Could not get the repository status"),
+            reason: error.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from("Error when running git status"),
+            reason: String::from_utf8(output.stderr)
+                .unwrap_or(String::from("stderr is not valid utf8")),
+        });
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| GitStrategyError::UnknownError {
+        message: String::from("Cannot parse git output"),
+        reason: String::from("Output is not valid utf8"),
+    })
+}
+
+/// Get the changed files of a repository.
+///
+/// When recursing, the changed files of submodules with dirty contents are included.
+/// Without the LFS extension, changed LFS files are compared with their pointer.
+fn get_file_changes(
+    repository_path: &Path,
+    recurse: bool,
+    is_git_lfs_installed: bool,
+) -> Result<Vec<FileChange>> {
+    let output = get_porcelain_status(repository_path)?;
+    let mut changes: Vec<_> = parse_porcelain_v2(&output)
+        .iter()
+        .filter_map(GitInternalChangeRepresentation::to_file_change)
+        .collect();
+
+    if !is_git_lfs_installed {
+        let smudged = get_smudged_lfs_files(repository_path, &changes)?;
+        changes.retain(|change| !smudged.contains(&change.path));
+    }
+
+    if recurse {
+        let submodules: Vec<_> = changes
+            .iter()
+            .filter(|change| change.submodule.is_some_and(|sub| sub.has_dirty_contents()))
+            .map(|change| change.path.clone())
+            .collect();
+
+        for submodule in submodules {
+            let nested = get_file_changes(
+                &repository_path.join(&submodule),
+                true,
+                is_git_lfs_installed,
+            )?;
+            changes.extend(
+                nested
+                    .into_iter()
+                    .map(|change| prefix_file_change(&submodule, change)),
+            );
+        }
+    }
+
+    Ok(changes)
+}
+
+/// The first line of every Git LFS pointer file.
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are small, so larger files are never read to check for a pointer.
+const LFS_POINTER_MAX_SIZE: u64 = 1024;
+
+/// A Git LFS pointer, which git stores instead of the content of a file.
+#[derive(Debug, PartialEq, Eq)]
+struct LfsPointer {
+    /// SHA-256 of the content, as lowercase hex.
+    oid: String,
+    size: u64,
+}
+
+/// Parse a Git LFS pointer, which identifies the content it points to.
+fn parse_lfs_pointer(content: &[u8]) -> Option<LfsPointer> {
+    let content = std::str::from_utf8(content).ok()?;
+    let mut lines = content.lines();
+    if lines.next()? != LFS_POINTER_VERSION {
+        return None;
+    }
+
+    let (mut oid, mut size) = (None, None);
+    for line in lines {
+        if let Some(value) = line.strip_prefix("oid sha256:") {
+            oid = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.parse().ok();
+        }
+    }
+
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+/// Whether a checked out file is a Git LFS pointer, instead of the content.
+pub(super) fn is_lfs_pointer_file(path: &Path) -> bool {
+    fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.len() <= LFS_POINTER_MAX_SIZE)
+        && fs::read(path).is_ok_and(|content| parse_lfs_pointer(&content).is_some())
+}
+
+/// Whether a change could be caused by a missing LFS filter, only modified files qualify.
+pub(super) fn is_changed_by_lfs(change: &FileChange) -> bool {
+    change.staged == FileChangeKind::Unmodified
+        && change.unstaged == FileChangeKind::Modified
+        && change.submodule.is_none()
+}
+
+/// Whether a file contains the content of the LFS pointer stored by git.
+///
+/// Without the LFS filter, git compares the content with the pointer, which is always a change.
+/// The file is only hashed when the size matches.
+pub(super) fn is_smudged_lfs_file(path: &Path, pointer: &[u8]) -> bool {
+    parse_lfs_pointer(pointer).is_some_and(|pointer| {
+        fs::metadata(path).is_ok_and(|metadata| metadata.len() == pointer.size)
+            && get_file_sha256(path).is_ok_and(|oid| oid == pointer.oid)
+    })
+}
+
+/// Get the SHA-256 of a file, which Git LFS uses to identify content.
+fn get_file_sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Git compares LFS files correctly when the LFS extension is installed.
+fn is_git_lfs_installed() -> bool {
+    Command::new("git")
+        .args(["lfs", "version"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// List the files in the index which use the LFS filter, with the object of their pointer.
+///
+/// Attributes from every `.gitattributes` and `.git/info/attributes` are taken into account.
+fn list_lfs_files(repository_path: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let output = run_git(
+        repository_path,
+        &["ls-files", "-z", "--stage", ":(attr:filter=lfs)"],
+        "Cannot list the LFS files of the repository",
+    )?;
+
+    // Each entry is formatted as '<mode> <object> <stage>\t<path>'.
+    Ok(output
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+            let object = info.split(' ').nth(1)?;
+            Some((PathBuf::from(path), object.to_string()))
+        })
+        .collect())
+}
+
+/// Read the content of multiple objects, using a single `git cat-file --batch`.
+///
+/// The content of missing objects is empty.
+fn read_objects(repository_path: &Path, objects: &[&str]) -> Result<Vec<Vec<u8>>> {
+    let to_error = |error: io::Error| GitStrategyError::RepositoryError {
+        message: String::from("Could not read objects from the repository"),
+        reason: error.to_string(),
+    };
+
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args(["cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(to_error)?;
+
+    // Written from another thread, so a full stdout cannot block writing to stdin.
+    let input: String = objects
+        .iter()
+        .map(|object| format!("{}\n", object))
+        .collect();
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| to_error(io::ErrorKind::BrokenPipe.into()))?;
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output().map_err(to_error)?;
+    writer
+        .join()
+        .unwrap_or_else(|_| Err(io::ErrorKind::BrokenPipe.into()))
+        .map_err(to_error)?;
+
+    if !output.status.success() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from("Error when running git cat-file"),
+            reason: String::from_utf8(output.stderr)
+                .unwrap_or(String::from("stderr is not valid utf8")),
+        });
+    }
+
+    Ok(parse_cat_file_batch(&output.stdout, objects.len()))
+}
+
+/// Parse the output of `git cat-file --batch`.
+///
+/// Each object starts with '<object> <type> <size>\n', followed by the content and a newline.
+/// Missing objects only have the line '<object> missing\n'.
+fn parse_cat_file_batch(mut output: &[u8], num_objects: usize) -> Vec<Vec<u8>> {
+    let mut result = Vec::with_capacity(num_objects);
+
+    while let Some(end) = output.iter().position(|byte| *byte == b'\n') {
+        let header = String::from_utf8_lossy(&output[..end]);
+        output = &output[end + 1..];
+
+        let size = header
+            .rsplit_once(' ')
+            .and_then(|(_, size)| size.parse::<usize>().ok())
+            .filter(|_| !header.ends_with(" missing"));
+        match size {
+            Some(size) if size < output.len() => {
+                result.push(output[..size].to_vec());
+                output = &output[size + 1..];
+            }
+            _ => result.push(Vec::new()),
+        }
+    }
+
+    result.resize(num_objects, Vec::new());
+    result
+}
+
+/// Find the changed LFS files, which contain the content of their pointer.
+fn get_smudged_lfs_files(
+    repository_path: &Path,
+    changes: &[FileChange],
+) -> Result<BTreeSet<PathBuf>> {
+    let candidates: BTreeSet<_> = changes
+        .iter()
+        .filter(|change| is_changed_by_lfs(change))
+        .map(|change| change.path.as_path())
+        .collect();
+    if candidates.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+
+    let pointers: Vec<_> = list_lfs_files(repository_path)?
+        .into_iter()
+        .filter(|(path, _)| candidates.contains(path.as_path()))
+        .collect();
+    if pointers.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+
+    let objects: Vec<_> = pointers.iter().map(|(_, object)| object.as_str()).collect();
+    let contents = read_objects(repository_path, &objects)?;
+
+    Ok(pointers
+        .into_iter()
+        .zip(contents)
+        .filter(|((path, _), pointer)| is_smudged_lfs_file(&repository_path.join(path), pointer))
+        .map(|((path, _), _)| path)
+        .collect())
+}
+
+/// Make the paths of a change inside a submodule relative to the parent repository.
+pub(super) fn prefix_file_change(prefix: &Path, change: FileChange) -> FileChange {
+    FileChange {
+        path: prefix.join(&change.path),
+        original_path: change.original_path.map(|path| prefix.join(path)),
+        ..change
+    }
+}
+
+/// List the submodules, with their changes taken from the changed files of the repository.
+fn get_submodules(
+    repository_path: &Path,
+    changes: &[FileChange],
+    recurse: bool,
+) -> Result<Vec<Submodule>> {
+    if !repository_path.join(".gitmodules").exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["submodule", "status"];
+    if recurse {
+        args.push("--recursive");
+    }
+    let output = run_git(repository_path, &args, "Could not list the submodules")?;
+
+    let mut submodules: Vec<_> = output
+        .lines()
+        .filter_map(|line| parse_submodule_status(line, changes))
+        .collect();
+    submodules.sort();
+    Ok(submodules)
+}
+
+/// Parse a line of `git submodule status`, formatted as `<state><commit> <path> (<describe>)`.
+///
+/// The state is `-` when not initialized, `+` when the commit differs, and `U` for conflicts.
+fn parse_submodule_status(line: &str, changes: &[FileChange]) -> Option<Submodule> {
+    let mut chars = line.chars();
+    let state = chars.next()?;
+    let (_, path) = chars.as_str().split_once(' ')?;
+    let path = path
+        .strip_suffix(')')
+        .and_then(|path| path.rsplit_once(" ("))
+        .map_or(path, |(path, _)| path);
+    let path = PathBuf::from(path);
+
+    let mut change = changes
+        .iter()
+        .find(|change| change.path == path)
+        .and_then(|change| change.submodule)
+        .unwrap_or_default();
+    change.commit_changed |= state == '+';
+
+    Some(Submodule {
+        path,
+        initialized: state != '-',
+        change,
+    })
+}
+
+/// Summarize the submodules as a change status, `None` when no submodule has changes.
+pub(super) fn get_submodule_change_status(
+    submodules: &[Submodule],
+) -> Option<RepositoryChangeStatus> {
+    let count = |predicate: fn(&Submodule) -> bool| {
+        submodules
+            .iter()
+            .filter(|submodule| predicate(submodule))
+            .count()
+    };
+
+    let modified_commits = count(|submodule| submodule.change.commit_changed);
+    let dirty_contents = count(|submodule| submodule.change.has_dirty_contents());
+    let uninitialized = count(|submodule| !submodule.initialized);
+
+    if modified_commits + dirty_contents + uninitialized == 0 {
+        return None;
+    }
+
+    Some(RepositoryChangeStatus::SubmoduleChanges {
+        modified_commits,
+        dirty_contents,
+        uninitialized,
+    })
 }
 
 pub struct LocalGitStrategy<'a, T: PathStrategy> {
+    config: &'a GrassConfig,
     path_strategy: &'a T,
+    git_lfs_installed: OnceLock<bool>,
 }
 
 impl<'a, T: PathStrategy> LocalGitStrategy<'a, T> {
-    pub fn new(path_strategy: &'a T) -> Self {
-        Self { path_strategy }
+    pub fn new(config: &'a GrassConfig, path_strategy: &'a T) -> Self {
+        Self {
+            config,
+            path_strategy,
+            git_lfs_installed: OnceLock::new(),
+        }
+    }
+
+    /// Whether the LFS extension is installed, this is only checked once.
+    fn is_git_lfs_installed(&self) -> bool {
+        *self.git_lfs_installed.get_or_init(is_git_lfs_installed)
+    }
+
+    /// Run a git push, authenticated for the category.
+    fn run_push(&self, category: &Category, repository_path: &Path, args: &[&str]) -> Result<()> {
+        let output = Command::new("git")
+            .args(self.get_remote_config_args(category)?)
+            .arg("-C")
+            .arg(repository_path)
+            .args(args)
+            .env("LC_ALL", "C")
+            .output()
+            .map_err(|error| GitStrategyError::RemoteFetchError {
+                message: String::from("Could not push repository"),
+                reason: error.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(remote_error("Error when running git push", output.stderr));
+        }
+
+        Ok(())
+    }
+
+    /// Arguments which configure authentication, for commands which access a remote.
+    ///
+    /// These should be passed to git before the subcommand.
+    fn get_remote_config_args(&self, category: &Category) -> Result<Vec<String>> {
+        let category = match self.config.get_by_category(category) {
+            Some(category) => category,
+            None => return Ok(Vec::new()),
+        };
+        let auth = &category.auth;
+
+        let mut result = Vec::new();
+
+        let ssh_command = auth.ssh_command.clone().or_else(|| {
+            auth.ssh_key.as_ref().map(|ssh_key| {
+                format!(
+                    "ssh -i {} -o IdentitiesOnly=yes",
+                    shell_quote(&ssh_key.to_string_lossy())
+                )
+            })
+        });
+        if let Some(ssh_command) = ssh_command {
+            result.push(String::from("-c"));
+            result.push(format!("core.sshCommand={}", ssh_command));
+        }
+
+        let mut credential_helpers = Vec::new();
+        if let Some(token_env) = &auth.token_env {
+            read_token(token_env)?;
+
+            // The token is read by the helper, so it never shows up in the arguments.
+            credential_helpers.push(format!(
+                "!f() {{ echo username={}; echo \"password=${}\"; }}; f",
+                shell_quote(auth.token_username.as_deref().unwrap_or("git")),
+                token_env
+            ));
+        }
+        if let Some(credential_helper) = &auth.credential_helper {
+            credential_helpers.push(credential_helper.clone());
+        }
+
+        if !credential_helpers.is_empty() {
+            // An empty value resets the helpers which are configured elsewhere.
+            result.push(String::from("-c"));
+            result.push(String::from("credential.helper="));
+        }
+        for credential_helper in credential_helpers {
+            result.push(String::from("-c"));
+            result.push(format!("credential.helper={}", credential_helper));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Read a token from an environment variable.
+pub(super) fn read_token(token_env: &str) -> Result<String> {
+    if token_env.is_empty()
+        || !token_env
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_')
+    {
+        return Err(GitStrategyError::RemoteAuthenticationError {
+            message: String::from("Cannot use the configured token"),
+            reason: format!("'{}' is not a valid environment variable name", token_env),
+        });
+    }
+
+    env::var(token_env).map_err(|_| GitStrategyError::RemoteAuthenticationError {
+        message: String::from("Cannot use the configured token"),
+        reason: format!("The environment variable '{}' is not set", token_env),
+    })
+}
+
+/// Quote a value, so it is interpreted literally by a POSIX shell.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Map a failed command which accessed a remote to an error.
+///
+/// The command should run with `LC_ALL=C`, so the messages are not translated.
+fn remote_error(message: &str, stderr: Vec<u8>) -> GitStrategyError {
+    const AUTHENTICATION_FAILURES: [&str; 7] = [
+        "Authentication failed",
+        "Permission denied",
+        "could not read Username",
+        "could not read Password",
+        "terminal prompts disabled",
+        "The requested URL returned error: 401",
+        "The requested URL returned error: 403",
+    ];
+
+    let reason = String::from_utf8(stderr).unwrap_or(String::from("stderr is not valid utf8"));
+
+    if AUTHENTICATION_FAILURES
+        .iter()
+        .any(|failure| reason.contains(failure))
+    {
+        GitStrategyError::RemoteAuthenticationError {
+            message: String::from(message),
+            reason,
+        }
+    } else {
+        GitStrategyError::RemoteFetchError {
+            message: String::from(message),
+            reason,
+        }
+    }
+}
+
+/// Whether a file should be removed, according to the clean patterns.
+///
+/// Patterns ending in `/` only match directories, so they never match the file name itself.
+fn matches_clean_patterns(path: &str, patterns: &[String]) -> bool {
+    let components: Box<[_]> = path.split('/').collect();
+    components.iter().enumerate().any(|(index, component)| {
+        let is_file_name = index == components.len() - 1;
+        let component: Box<[char]> = component.chars().collect();
+        patterns.iter().any(|pattern| {
+            let (pattern, directory_only) = match pattern.strip_suffix('/') {
+                Some(pattern) => (pattern, true),
+                None => (pattern.as_str(), false),
+            };
+            if directory_only && is_file_name {
+                return false;
+            }
+            let pattern: Box<[char]> = pattern.chars().collect();
+            glob_matches(&pattern, &component)
+        })
+    })
+}
+
+/// List the ignored files of a repository, separated by `\0`.
+///
+/// Nested repositories are listed as directories, ending with `/`.
+fn list_ignored_files(repository_path: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository_path)
+        .args([
+            "ls-files",
+            "-z",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+        ])
+        .output()
+        .map_err(|error| GitStrategyError::RepositoryError {
+            message: String::from("Could not list ignored files"),
+            reason: error.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from("Error when running git ls-files"),
+            reason: String::from_utf8(output.stderr)
+                .unwrap_or(String::from("stderr is not valid utf8")),
+        });
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| GitStrategyError::UnknownError {
+        message: String::from("Cannot parse git output"),
+        reason: String::from("Output is not valid utf8"),
+    })
+}
+
+/// Get the clean patterns of a category, `None` when every ignored file may be removed.
+pub(super) fn get_configured_clean_patterns(
+    config: &GrassConfig,
+    category: &Category,
+) -> Result<Option<Vec<String>>> {
+    config
+        .get_clean_patterns(category)
+        .map_err(|profile| GitStrategyError::UnknownError {
+            message: String::from("Cannot resolve the clean profiles for the category"),
+            reason: format!("Clean profile '{}' is not defined", profile),
+        })
+}
+
+/// Whether an ignored file is removed when cleaning, based on the clean patterns.
+fn is_removable(file: &str, patterns: Option<&[String]>) -> bool {
+    // Nested repositories are listed as directories, these are never removed.
+    if file.is_empty() || file.ends_with('/') {
+        return false;
+    }
+
+    patterns.is_none_or(|patterns| matches_clean_patterns(file, patterns))
+}
+
+/// Remove ignored files, which are relative to the repository.
+///
+/// When patterns are given, only matching files are removed.
+/// Directories which are empty afterwards are removed as well.
+pub(super) fn remove_ignored_files<'b>(
+    repository_path: &Path,
+    files: impl IntoIterator<Item = &'b str>,
+    patterns: Option<&[String]>,
+) -> Result<CleanReport> {
+    let mut report = CleanReport::default();
+    let mut errors = Vec::new();
+    let mut directories = BTreeSet::new();
+
+    for file in files {
+        if !is_removable(file, patterns) {
+            continue;
+        }
+
+        let file_path = repository_path.join(file);
+        let size = fs::symlink_metadata(&file_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        if let Err(error) = fs::remove_file(&file_path) {
+            errors.push(format!("{}: {}", file, error));
+            continue;
+        }
+
+        directories.extend(Path::new(file).ancestors().skip(1).map(Path::to_path_buf));
+        report.removed_files.push(PathBuf::from(file));
+        report.freed_bytes += size;
+    }
+
+    // Deepest directories come last, and only empty directories can be removed.
+    for directory in directories.iter().rev() {
+        if directory.as_os_str().is_empty() {
+            continue;
+        }
+        fs::remove_dir(repository_path.join(directory)).ok();
+    }
+
+    if !errors.is_empty() {
+        return Err(GitStrategyError::FileSystemError {
+            message: String::from("Could not remove some of the ignored files"),
+            reason: format!("{} file(s) could not be removed", errors.len()),
+            reasons: errors,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Compute the size of a repository, the ignored files are relative to the repository.
+///
+/// Only files matching the patterns count as reclaimable, like in `remove_ignored_files`.
+pub(super) fn get_repository_size<'b>(
+    repository_path: &Path,
+    files: impl IntoIterator<Item = &'b str>,
+    patterns: Option<&[String]>,
+) -> RepositorySize {
+    let reclaimable_bytes = files
+        .into_iter()
+        .filter(|file| is_removable(file, patterns))
+        .filter_map(|file| fs::symlink_metadata(repository_path.join(file)).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    RepositorySize {
+        total_bytes: get_directory_size(repository_path),
+        reclaimable_bytes,
+        git_bytes: get_directory_size(&repository_path.join(".git")),
+    }
+}
+
+/// Sum the sizes of all files in a directory, without following symbolic links.
+fn get_directory_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| get_directory_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+impl<'a, T: PathStrategy> GitStrategy for LocalGitStrategy<'a, T> {
+    fn clean<U>(&self, repository: U) -> Result<CleanReport>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let patterns = get_configured_clean_patterns(self.config, &repository.category)?;
+        let output = list_ignored_files(&repository_path)?;
+
+        remove_ignored_files(&repository_path, output.split('\0'), patterns.as_deref())
+    }
+
+    fn get_size<U>(&self, repository: U) -> Result<RepositorySize>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let patterns = get_configured_clean_patterns(self.config, &repository.category)?;
+        let output = match repository_path.join(".git").exists() {
+            true => list_ignored_files(&repository_path)?,
+            false => String::new(),
+        };
+
+        Ok(get_repository_size(
+            &repository_path,
+            output.split('\0'),
+            patterns.as_deref(),
+        ))
+    }
+
+    fn clone<U, V>(&self, repository: U, remote: V, options: &CloneOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+        V: AsRef<str>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repo_path = self.path_strategy.get_directory(repository.clone())?;
+
+        fs::create_dir_all(&repo_path).map_err(|error| GitStrategyError::FileSystemError {
+            message: String::from("Cannot create directory to clone into"),
+            reason: error.to_string(),
+            reasons: vec![],
+        })?;
+
+        let repo_path = repo_path.to_str().ok_or(GitStrategyError::UnknownError {
+            message: String::from("Cannot resolve path"),
+            reason: String::from("String conversion failed"),
+        })?;
+
+        let mut clone_args = vec![];
+        if let Some(branch) = &options.branch {
+            clone_args.push(format!("--branch={}", branch));
+        }
+        if let Some(depth) = options.depth {
+            clone_args.push(format!("--depth={}", depth));
+        }
+        if let Some(filter) = &options.filter {
+            clone_args.push(format!("--filter={}", filter));
+        }
+        if options.recurse_submodules {
+            clone_args.push(String::from("--recurse-submodules"));
+        }
+
+        let mut command = Command::new("git");
+        command
+            .args(self.get_remote_config_args(&repository.category)?)
+            .args(["-C", repo_path, "clone"])
+            .args(clone_args)
+            .args(["--", remote.as_ref(), "."])
+            .env("LC_ALL", "C");
+        if options.skip_lfs_smudge {
+            command.env("GIT_LFS_SKIP_SMUDGE", "1");
+        }
+
+        let output = command
+            .output()
+            .map_err(|error| GitStrategyError::RemoteFetchError {
+                message: String::from("Could not clone repository"),
+                reason: error.to_string(),
+            })?;
+
+        if !matches!(output.status.code(), Some(0)) {
+            return Err(remote_error("Error when running git clone", output.stderr));
+        }
+
+        Ok(())
+    }
+
+    fn init<U>(&self, repository: U, options: &InitOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+        let settings = get_init_settings(self.config, &repository_path, &repository, options)?;
+
+        let mut args = vec![String::from("init"), String::from("--quiet")];
+        if let Some(branch) = &settings.branch {
+            args.push(format!("--initial-branch={}", branch));
+        }
+        let args: Vec<_> = args.iter().map(String::as_str).collect();
+        run_git(
+            &repository_path,
+            &args,
+            "Could not initialize the repository",
+        )?;
+
+        if let Some(template) = &settings.template {
+            apply_template(template, &repository_path)?;
+        }
+
+        if let Some(remote) = &settings.remote {
+            run_git(
+                &repository_path,
+                &["remote", "add", "origin", remote],
+                "Could not add the remote",
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn get_changes<U>(&self, repository: U) -> Result<RepositoryChangeStatus>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_location = repository.into();
+        let repository_path = self
+            .path_strategy
+            .get_directory(repository_location.clone())?;
+
+        if !repository_path.join(".git").exists() {
+            return Ok(RepositoryChangeStatus::NoRepository);
+        }
+
+        let recurse = self.config.recurse_submodules;
+        let changes = get_file_changes(&repository_path, recurse, self.is_git_lfs_installed())?;
+
+        let num_conflicts = changes
+            .iter()
+            .filter(|change| change.staged == FileChangeKind::Unmerged)
+            .count();
+
+        if let Some(operation) = get_operation_in_progress(&repository_path)? {
+            return Ok(RepositoryChangeStatus::OperationInProgress {
+                operation,
+                num_conflicts,
+            });
+        }
+
+        if num_conflicts > 0 {
+            return Ok(RepositoryChangeStatus::Conflicts { num_conflicts });
+        }
+
+        if changes.iter().any(|change| change.submodule.is_none()) {
+            return Ok(RepositoryChangeStatus::UncommittedChanges {
+                num_changes: changes.len(),
+            });
+        }
+
+        let submodules = get_submodules(&repository_path, &changes, recurse)?;
+        if let Some(change_status) = get_submodule_change_status(&submodules) {
+            return Ok(change_status);
+        }
+
+        if let Some(lfs_status) = self.get_lfs_status(repository_location.clone())? {
+            if !lfs_status.missing_objects.is_empty() {
+                return Ok(RepositoryChangeStatus::MissingLfsObjects {
+                    num_files: lfs_status.missing_objects.len(),
+                });
+            }
+        }
+
+        if let Some(max_age) = self.config.get_stale_stash_age() {
+            let num_stashes =
+                count_stale_stashes(&self.list_stashes(repository_location.clone())?, max_age);
+            if num_stashes > 0 {
+                return Ok(RepositoryChangeStatus::StaleStashes { num_stashes });
+            }
+        }
+
+        let branches: Vec<_> = self
+            .get_branch_tracking(repository_location)?
+            .into_iter()
+            .filter(BranchTracking::has_unpushed_work)
+            .collect();
+
+        if !branches.is_empty() {
+            return Ok(RepositoryChangeStatus::UnpushedWork { branches });
+        }
+
+        Ok(RepositoryChangeStatus::UpToDate)
+    }
+
+    fn get_change_details<U>(&self, repository: U) -> Result<Vec<FileChange>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot list the changes of the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        get_file_changes(
+            &repository_path,
+            self.config.recurse_submodules,
+            self.is_git_lfs_installed(),
+        )
+    }
+
+    fn get_lfs_status<U>(&self, repository: U) -> Result<Option<LfsStatus>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot get the LFS status of the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let mut lfs_files: Vec<_> = list_lfs_files(&repository_path)?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        if lfs_files.is_empty() {
+            return Ok(None);
+        }
+        // Conflicted files have an entry for each stage.
+        lfs_files.dedup();
+
+        Ok(Some(LfsStatus {
+            missing_objects: lfs_files
+                .into_iter()
+                .filter(|path| is_lfs_pointer_file(&repository_path.join(path)))
+                .collect(),
+        }))
+    }
+
+    fn list_submodules<U>(&self, repository: U) -> Result<Vec<Submodule>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot list the submodules of the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let recurse = self.config.recurse_submodules;
+        let changes = get_file_changes(&repository_path, recurse, self.is_git_lfs_installed())?;
+        get_submodules(&repository_path, &changes, recurse)
+    }
+
+    fn get_metadata<U>(&self, repository: U) -> Result<RepositoryMetadata>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let message = "Cannot get the metadata of the repository";
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from(message),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let branch = run_git(
+            &repository_path,
+            &["symbolic-ref", "--quiet", "--short", "HEAD"],
+            message,
+        )
+        .ok();
+
+        let last_commit = if has_ref(&repository_path, "HEAD") {
+            let output = run_git(
+                &repository_path,
+                &["log", "-1", "--format=%h%x00%ct%x00%s"],
+                message,
+            )?;
+            Some(
+                parse_commit_summary(&output).ok_or_else(|| GitStrategyError::UnknownError {
+                    message: String::from(message),
+                    reason: format!("Unexpected output of git log: {}", output),
+                })?,
+            )
+        } else {
+            None
+        };
+
+        let head = match (branch, &last_commit) {
+            (Some(branch), Some(_)) => HeadState::Branch(branch),
+            (Some(branch), None) => HeadState::Unborn(branch),
+            (None, Some(commit)) => HeadState::Detached(commit.id.clone()),
+            (None, None) => {
+                return Err(GitStrategyError::RepositoryError {
+                    message: String::from(message),
+                    reason: String::from("HEAD does not point to a branch or commit"),
+                })
+            }
+        };
+
+        Ok(RepositoryMetadata { head, last_commit })
+    }
+
+    fn get_branch_tracking<U>(&self, repository: U) -> Result<Vec<BranchTracking>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        // The tracking information is translated, so force untranslated output.
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&repository_path)
+            .args([
+                "for-each-ref",
+                "--format=%(refname:short)%00%(upstream:short)%00%(upstream:track,nobracket)",
+                "refs/heads",
+            ])
+            .env("LC_ALL", "C")
+            .output()
+            .map_err(|error| GitStrategyError::RepositoryError {
+                message: String::from("Could not list branches"),
+                reason: error.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Error when running git for-each-ref"),
+                reason: String::from_utf8(output.stderr)
+                    .unwrap_or(String::from("stderr is not valid utf8")),
+            });
+        }
+
+        let output =
+            String::from_utf8(output.stdout).map_err(|_| GitStrategyError::UnknownError {
+                message: String::from("Cannot parse git output"),
+                reason: String::from("Output is not valid utf8"),
+            })?;
+
+        Ok(output.lines().filter_map(parse_branch_tracking).collect())
+    }
+
+    fn list_branches<U>(&self, repository: U) -> Result<Vec<BranchInfo>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot list branches"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let output = run_git(
+            &repository_path,
+            &[
+                "for-each-ref",
+                "--format=%(HEAD)%00%(refname:short)%00%(upstream:short)%00%(upstream:track,nobracket)%00%(committerdate:unix)",
+                "refs/heads",
+            ],
+            "Could not list branches",
+        )?;
+        let mut branches: Vec<_> = output.lines().filter_map(parse_branch_info).collect();
+
+        let Some(default_branch) = get_default_branch(&repository_path) else {
+            return Ok(branches);
+        };
+
+        // Compare against the remote branch, when the default branch is not checked out locally.
+        let local_default = format!("refs/heads/{}", default_branch);
+        let target = match has_ref(&repository_path, &local_default) {
+            true => local_default,
+            false => format!("refs/remotes/origin/{}", default_branch),
+        };
+
+        let merged = run_git(
+            &repository_path,
+            &[
+                "for-each-ref",
+                &format!("--merged={}", target),
+                "--format=%(refname:short)",
+                "refs/heads",
+            ],
+            "Could not list merged branches",
+        )?;
+        let merged: BTreeSet<_> = merged.lines().collect();
+
+        for branch in branches.iter_mut() {
+            branch.is_default = branch.branch == default_branch;
+            branch.is_merged = !branch.is_default && merged.contains(branch.branch.as_str());
+        }
+
+        Ok(branches)
     }
-}
 
-impl<'a, T: PathStrategy> GitStrategy for LocalGitStrategy<'a, T> {
-    fn clean<U>(&self, _repository: U) -> Result<()>
+    fn delete_branch<U>(&self, repository: U, branch: &str, force: bool) -> Result<()>
     where
         U: Into<RepositoryLocation>,
     {
-        // TODO: This should be implemented later, I want to rethink cleaning first.
-        // TODO: See <https://github.com/damymetzke/grass/issues/7>.
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let branches = self.list_branches(repository)?;
+        check_branch_deletable(&branches, branch, force)?;
+
+        run_git(
+            &repository_path,
+            &["branch", "-D", branch],
+            "Could not delete the branch",
+        )?;
+
         Ok(())
     }
 
-    fn clone<U, V>(&self, repository: U, remote: V) -> Result<()>
+    fn fetch<U>(&self, repository: U) -> Result<()>
     where
         U: Into<RepositoryLocation>,
-        V: AsRef<str>,
     {
         let repository: RepositoryLocation = repository.into();
-        let repo_path = self.path_strategy.get_directory(repository.clone())?;
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
 
-        fs::create_dir_all(&repo_path).map_err(|error| GitStrategyError::FileSystemError {
-            message: String::from("Cannot create directory to clone into"),
-            reason: error.to_string(),
-            reasons: vec![],
-        })?;
-
-        let repo_path = repo_path.to_str().ok_or(GitStrategyError::UnknownError {
-            message: String::from("Cannot resolve path"),
-            reason: String::from("String conversion failed"),
-        })?;
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot fetch the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
 
         let output = Command::new("git")
-            .args(["-C", repo_path, "clone", remote.as_ref(), "."])
+            .args(self.get_remote_config_args(&repository.category)?)
+            .arg("-C")
+            .arg(&repository_path)
+            .arg("fetch")
+            .env("LC_ALL", "C")
             .output()
             .map_err(|error| GitStrategyError::RemoteFetchError {
-                message: String::from("Could not clone repository"),
+                message: String::from("Could not fetch repository"),
                 reason: error.to_string(),
             })?;
 
-        if !matches!(output.status.code(), Some(0)) {
-            return Err(GitStrategyError::RemoteFetchError {
-                message: String::from("Error when running git clone"),
-                reason: String::from_utf8(output.stderr)
-                    .unwrap_or(String::from("stderr is not valid utf8")),
+        if !output.status.success() {
+            return Err(remote_error("Error when running git fetch", output.stderr));
+        }
+
+        Ok(())
+    }
+
+    fn pull<U>(&self, repository: U, mode: PullMode) -> Result<PullResult>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        self.fetch(repository.clone())?;
+
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        // Fails for a detached head, or a branch without upstream.
+        if run_git(
+            &repository_path,
+            &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+            "Could not find the upstream",
+        )
+        .is_err()
+        {
+            return Ok(PullResult::NoUpstream);
+        }
+
+        let num_commits = run_git(
+            &repository_path,
+            &["rev-list", "--count", "HEAD..@{u}"],
+            "Could not count the commits to pull",
+        )?
+        .parse()
+        .map_err(|_| GitStrategyError::UnknownError {
+            message: String::from("Cannot parse git output"),
+            reason: String::from("The number of commits is not a number"),
+        })?;
+
+        if num_commits == 0 {
+            return Ok(PullResult::UpToDate);
+        }
+
+        let from = run_git(
+            &repository_path,
+            &["rev-parse", "--short", "HEAD"],
+            "Could not resolve the current commit",
+        )?;
+
+        let result = match mode {
+            PullMode::FastForwardOnly => run_git(
+                &repository_path,
+                &["merge", "--ff-only", "@{u}"],
+                "Cannot fast-forward the current branch",
+            ),
+            PullMode::Merge => run_git(
+                &repository_path,
+                &["merge", "--no-edit", "@{u}"],
+                "Cannot merge the upstream",
+            ),
+            PullMode::Rebase => run_git(
+                &repository_path,
+                &["rebase", "@{u}"],
+                "Cannot rebase onto the upstream",
+            ),
+        };
+
+        // Leave the repository as it was, instead of in the middle of resolving conflicts.
+        if let Err(error) = result {
+            let operation = match mode {
+                PullMode::FastForwardOnly => None,
+                PullMode::Merge => Some("merge"),
+                PullMode::Rebase => Some("rebase"),
+            };
+            if let Some(operation) = operation {
+                let _ = run_git(
+                    &repository_path,
+                    &[operation, "--abort"],
+                    "Could not abort the operation",
+                );
+            }
+            return Err(error);
+        }
+
+        let to = run_git(
+            &repository_path,
+            &["rev-parse", "--short", "HEAD"],
+            "Could not resolve the current commit",
+        )?;
+
+        Ok(PullResult::Updated {
+            from,
+            to,
+            num_commits,
+        })
+    }
+
+    fn commit_all<U>(&self, repository: U, message: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot commit the changes"),
+                reason: String::from("No repository has been initialized"),
             });
         }
 
+        if let Some(operation) = get_operation_in_progress(&repository_path)? {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot commit the changes"),
+                reason: format!("{} in progress", operation),
+            });
+        }
+
+        if get_porcelain_status(&repository_path)?.is_empty() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot commit the changes"),
+                reason: String::from("There is nothing to commit"),
+            });
+        }
+
+        run_git(
+            &repository_path,
+            &["add", "--all"],
+            "Could not stage the changes",
+        )?;
+        run_git(
+            &repository_path,
+            &["commit", "--quiet", "--message", message],
+            "Could not commit the changes",
+        )?;
+
         Ok(())
     }
 
-    fn get_changes<U>(&self, repository: U) -> Result<RepositoryChangeStatus>
+    fn push<U>(&self, repository: U) -> Result<()>
     where
         U: Into<RepositoryLocation>,
     {
-        let repository_location = repository.into();
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot push the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let has_upstream = run_git(
+            &repository_path,
+            &["rev-parse", "--verify", "--quiet", "@{upstream}"],
+            "Could not resolve the upstream",
+        )
+        .is_ok();
+
+        if !has_upstream {
+            return self.push_to_remote(repository, "origin");
+        }
+
+        self.run_push(&repository.category, &repository_path, &["push", "--quiet"])
+    }
+
+    fn push_to_remote<U>(&self, repository: U, remote: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot push the repository"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let branch = run_git(
+            &repository_path,
+            &["symbolic-ref", "--quiet", "--short", "HEAD"],
+            "Cannot push the repository",
+        )
+        .map_err(|_| GitStrategyError::RepositoryError {
+            message: String::from("Cannot push the repository"),
+            reason: String::from("HEAD is detached"),
+        })?;
+
+        self.run_push(
+            &repository.category,
+            &repository_path,
+            &["push", "--quiet", "--set-upstream", remote, &branch],
+        )
+    }
+
+    fn add_remote<U>(&self, repository: U, name: &str, url: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot add the remote"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        run_git(
+            &repository_path,
+            &["remote", "add", name, url],
+            "Cannot add the remote",
+        )
+        .map(|_| ())
+    }
+
+    fn rename_remote<U>(&self, repository: U, from: &str, to: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot rename the remote"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        run_git(
+            &repository_path,
+            &["remote", "rename", from, to],
+            "Cannot rename the remote",
+        )
+        .map(|_| ())
+    }
+
+    fn list_worktrees<U>(&self, repository: U) -> Result<Vec<Worktree>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot list worktrees"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let output = run_git(
+            &repository_path,
+            &["worktree", "list", "--porcelain"],
+            "Could not list worktrees",
+        )?;
+
+        Ok(parse_worktree_list(&output))
+    }
+
+    fn add_worktree<U>(&self, worktree: U) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        let worktree: WorktreeLocation = worktree.into();
         let repository_path = self
             .path_strategy
-            .get_directory(repository_location.clone())?;
+            .get_directory(worktree.repository.clone())?;
+        let worktree_path = self
+            .path_strategy
+            .get_worktree_directory(worktree.clone())?;
+        let branch = worktree.branch.as_str();
 
-        let output = Command::new("git")
-            .arg("-C")
-            .arg(repository_path)
-            .arg("status")
-            .arg("--porcelain=v2")
-            .output()
-            .map_err(|error| GitStrategyError::RemoteFetchError {
-                message: String::from("Could not clone repository"),
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot add a worktree"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        if worktree_path.exists() {
+            return Err(GitStrategyError::RepositryExists {
+                message: String::from("Cannot add a worktree"),
+                reason: format!("The directory '{}' already exists", worktree_path.display()),
+            });
+        }
+
+        if let Some(parent) = worktree_path.parent() {
+            fs::create_dir_all(parent).map_err(|error| GitStrategyError::FileSystemError {
+                message: String::from("Cannot create directory for the worktree"),
                 reason: error.to_string(),
+                reasons: vec![],
             })?;
+        }
 
-        let output =
-            String::from_utf8(output.stdout).map_err(|_| GitStrategyError::UnknownError {
-                message: String::from("Cannot parse git output"),
-                reason: String::from("Output is not valid utf8"),
+        let worktree_path = worktree_path
+            .to_str()
+            .ok_or(GitStrategyError::UnknownError {
+                message: String::from("Cannot resolve path"),
+                reason: String::from("String conversion failed"),
             })?;
 
-        let output: Box<_> = output.split('\n').filter_map(|line| {
-            if line.chars().all(|char| char.is_whitespace()) {
-                return None;
-            };
+        let local_branch = format!("refs/heads/{}", branch);
+        let has_local_branch = run_git(
+            &repository_path,
+            &["rev-parse", "--verify", "--quiet", &local_branch],
+            "Could not resolve the branch",
+        )
+        .is_ok();
 
-            if let Some(path) = line.strip_prefix("? ") {
-                return Some(GitInternalChangeRepresentation::Untracked { path });
-            };
+        let remote_branches: Vec<_> = match has_local_branch {
+            true => vec![],
+            false => run_git(
+                &repository_path,
+                &[
+                    "for-each-ref",
+                    "--format=%(refname:strip=2)",
+                    "refs/remotes/",
+                ],
+                "Could not list remote branches",
+            )?
+            .lines()
+            .filter(|remote_branch| {
+                remote_branch
+                    .split_once('/')
+                    .is_some_and(|(_, name)| name == branch)
+            })
+            .map(String::from)
+            .collect(),
+        };
 
-            if let Some(path) = line.strip_prefix("! ") {
-                return Some(GitInternalChangeRepresentation::Ignored { path });
-            };
+        let args = match remote_branches.as_slice() {
+            [remote_branch] if !has_local_branch => vec![
+                "worktree",
+                "add",
+                "--track",
+                "-b",
+                branch,
+                worktree_path,
+                remote_branch,
+            ],
+            _ if has_local_branch => vec!["worktree", "add", worktree_path, branch],
+            _ => vec!["worktree", "add", "-b", branch, worktree_path],
+        };
 
-            if let Some(content) = line.strip_prefix("1 ") {
-                let parts: Box<_> = content.splitn(8, ' ').collect();
-                if let[status, sub_module, file_mode_head, file_mode_index, file_mode_worktree, object_name_head, object_name_index, path] = parts.as_ref() {
-                    return Some(GitInternalChangeRepresentation::Ordinary { status, sub_module, file_mode_head, file_mode_index, file_mode_worktree, object_name_head, object_name_index, path })
-                }
-            };
+        run_git(&repository_path, &args, "Could not add the worktree")?;
 
-            if let Some(content) = line.strip_prefix("2 ") {
-                let parts: Box<_> = content.splitn(9, ' ').collect();
-                if let[status, sub_module, file_mode_head, file_mode_index, file_mode_worktree, object_name_head, object_name_index, score, path] = parts.as_ref() {
-                    return Some(GitInternalChangeRepresentation::Moved { status, sub_module, file_mode_head, file_mode_index, file_mode_worktree, object_name_head, object_name_index, score, path })
-                }
-            };
+        Ok(())
+    }
 
-            None
-        }).filter(|change| !matches!(change, GitInternalChangeRepresentation::Ignored{..})).collect();
+    fn remove_worktree<U>(&self, worktree: U, force: bool) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let worktrees = self.list_worktrees(repository)?;
+        let worktree = find_worktree(&worktrees, &branch)?;
+
+        let worktree_path = worktree
+            .path
+            .to_str()
+            .ok_or(GitStrategyError::UnknownError {
+                message: String::from("Cannot resolve path"),
+                reason: String::from("String conversion failed"),
+            })?;
+
+        let mut args = vec!["worktree", "remove"];
+        if force {
+            args.push("--force");
+        }
+        args.push(worktree_path);
+
+        run_git(&repository_path, &args, "Could not remove the worktree")?;
+
+        Ok(())
+    }
+
+    fn list_stashes<U>(&self, repository: U) -> Result<Vec<Stash>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot list stashes"),
+                reason: String::from("No repository has been initialized"),
+            });
+        }
+
+        let output = run_git(
+            &repository_path,
+            &["stash", "list", "--format=%gd%x00%ct%x00%gs"],
+            "Could not list stashes",
+        )?;
+
+        Ok(output.lines().filter_map(parse_stash).collect())
+    }
+}
+
+pub(super) fn count_stale_stashes(stashes: &[Stash], max_age: Duration) -> usize {
+    stashes
+        .iter()
+        .filter(|stash| stash.is_stale(max_age))
+        .count()
+}
+
+/// Parse a single line of `git stash list`, see `list_stashes` for the format.
+fn parse_stash(line: &str) -> Option<Stash> {
+    let parts: Box<[_]> = line.splitn(3, '\0').collect();
+    let [selector, timestamp, message] = parts.as_ref() else {
+        return None;
+    };
+
+    Some(Stash {
+        index: selector
+            .strip_prefix("stash@{")?
+            .strip_suffix('}')?
+            .parse()
+            .ok()?,
+        message: message.to_string(),
+        created: UNIX_EPOCH + Duration::from_secs(timestamp.parse().ok()?),
+    })
+}
+
+/// Parse the output of `git log -1 --format=%h%x00%ct%x00%s`.
+fn parse_commit_summary(output: &str) -> Option<CommitSummary> {
+    let parts: Box<[_]> = output.splitn(3, '\0').collect();
+    let [id, timestamp, subject] = parts.as_ref() else {
+        return None;
+    };
+
+    Some(CommitSummary {
+        id: id.to_string(),
+        subject: subject.to_string(),
+        time: UNIX_EPOCH + Duration::from_secs(timestamp.parse().ok()?),
+    })
+}
 
-        if output.is_empty() {
-            return Ok(RepositoryChangeStatus::UpToDate);
+/// What to set up when initializing a repository, combining the options with the configuration.
+pub(super) struct InitSettings {
+    pub branch: Option<String>,
+    pub template: Option<PathBuf>,
+    pub remote: Option<String>,
+}
+
+/// Check whether a repository can be initialized, and resolve what to set up.
+///
+/// This is done before initializing, so nothing is changed when the options cannot be applied.
+pub(super) fn get_init_settings(
+    config: &GrassConfig,
+    repository_path: &Path,
+    repository: &RepositoryLocation,
+    options: &InitOptions,
+) -> Result<InitSettings> {
+    if !repository_path.is_dir() {
+        return Err(GitStrategyError::RepositoryNotFound {
+            message: String::from("Cannot initialize the repository"),
+            reason: format!(
+                "The directory '{}' does not exist",
+                repository_path.display()
+            ),
+        });
+    }
+
+    if repository_path.join(".git").exists() {
+        return Err(GitStrategyError::RepositryExists {
+            message: String::from("Cannot initialize the repository"),
+            reason: String::from("A repository has already been initialized"),
+        });
+    }
+
+    let remote = match options.add_remote {
+        true => Some(get_configured_remote_url(config, repository)?),
+        false => None,
+    };
+
+    Ok(InitSettings {
+        branch: options
+            .branch
+            .clone()
+            .or_else(|| config.default_branch.clone()),
+        template: config
+            .get_by_category(&repository.category)
+            .and_then(|category| category.template.clone()),
+        remote,
+    })
+}
+
+/// Copy a template into a new repository, without replacing existing files.
+///
+/// The `hooks` directory of the template is copied to `.git/hooks`.
+pub(super) fn apply_template(template: &Path, repository_path: &Path) -> Result<()> {
+    let to_error = |error: std::io::Error| GitStrategyError::FileSystemError {
+        message: format!("Cannot apply the template '{}'", template.display()),
+        reason: error.to_string(),
+        reasons: vec![],
+    };
+
+    for entry in fs::read_dir(template).map_err(to_error)? {
+        let entry = entry.map_err(to_error)?;
+        let target = match entry.file_name().to_str() {
+            Some("hooks") => repository_path.join(".git/hooks"),
+            Some(".git") => continue,
+            _ => repository_path.join(entry.file_name()),
+        };
+        copy_new_files(&entry.path(), &target).map_err(to_error)?;
+    }
+
+    Ok(())
+}
+
+fn copy_new_files(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_new_files(&entry.path(), &target.join(entry.file_name()))?;
         }
+    } else if !target.exists() {
+        // Copying keeps the permissions, so hooks stay executable.
+        fs::copy(source, target)?;
+    }
+
+    Ok(())
+}
+
+/// Check whether a branch can be deleted, see `delete_branch` for the rules.
+pub(super) fn check_branch_deletable(
+    branches: &[BranchInfo],
+    branch: &str,
+    force: bool,
+) -> Result<()> {
+    match branches.iter().find(|info| info.branch == branch) {
+        Some(BranchInfo {
+            is_current: true, ..
+        }) => Err(GitStrategyError::RepositoryError {
+            message: String::from("Cannot delete the branch"),
+            reason: format!("The branch '{}' is checked out", branch),
+        }),
+        Some(BranchInfo {
+            is_merged: false, ..
+        }) if !force => Err(GitStrategyError::RepositoryError {
+            message: String::from("Cannot delete the branch"),
+            reason: format!(
+                "The branch '{}' is not merged into the default branch",
+                branch
+            ),
+        }),
+        Some(_) => Ok(()),
+        None => Err(GitStrategyError::RepositoryNotFound {
+            message: String::from("Cannot delete the branch"),
+            reason: format!("The branch '{}' does not exist", branch),
+        }),
+    }
+}
+
+fn has_ref(repository_path: &Path, reference: &str) -> bool {
+    run_git(
+        repository_path,
+        &["rev-parse", "--verify", "--quiet", reference],
+        "Could not resolve reference",
+    )
+    .is_ok()
+}
+
+/// Get the default branch, from `origin/HEAD` or else a local `main` or `master` branch.
+fn get_default_branch(repository_path: &Path) -> Option<String> {
+    let origin_head = run_git(
+        repository_path,
+        &["symbolic-ref", "--quiet", "refs/remotes/origin/HEAD"],
+        "Could not resolve origin/HEAD",
+    );
+
+    if let Some(branch) = origin_head
+        .ok()
+        .and_then(|head| head.strip_prefix("refs/remotes/origin/").map(String::from))
+    {
+        return Some(branch);
+    }
+
+    ["main", "master"]
+        .into_iter()
+        .find(|branch| has_ref(repository_path, &format!("refs/heads/{}", branch)))
+        .map(String::from)
+}
+
+/// Parse a single line of `git for-each-ref`, see `list_branches` for the format.
+///
+/// Whether the branch is the default branch, or merged, is not part of the line.
+fn parse_branch_info(line: &str) -> Option<BranchInfo> {
+    let parts: Box<[_]> = line.splitn(5, '\0').collect();
+    let [head, branch, upstream, track, timestamp] = parts.as_ref() else {
+        return None;
+    };
+
+    Some(BranchInfo {
+        branch: branch.to_string(),
+        upstream: match *upstream {
+            "" => None,
+            upstream => Some(upstream.to_string()),
+        },
+        upstream_gone: *track == "gone",
+        is_default: false,
+        is_current: *head == "*",
+        is_merged: false,
+        last_commit: UNIX_EPOCH + Duration::from_secs(timestamp.parse().ok()?),
+    })
+}
+
+/// Find the worktree which can be removed for a branch.
+///
+/// The main worktree is never returned, because it cannot be removed.
+pub(super) fn find_worktree<'b>(worktrees: &'b [Worktree], branch: &str) -> Result<&'b Worktree> {
+    match worktrees
+        .iter()
+        .find(|worktree| worktree.branch.as_deref() == Some(branch))
+    {
+        Some(Worktree { is_main: true, .. }) => Err(GitStrategyError::RepositoryError {
+            message: String::from("Cannot remove the worktree"),
+            reason: format!(
+                "The branch '{}' is checked out in the main worktree",
+                branch
+            ),
+        }),
+        Some(worktree) => Ok(worktree),
+        None => Err(GitStrategyError::RepositoryNotFound {
+            message: String::from("Cannot remove the worktree"),
+            reason: format!("No worktree has the branch '{}' checked out", branch),
+        }),
+    }
+}
 
-        Ok(RepositoryChangeStatus::UncommittedChanges {
-            num_changes: output.len(),
+/// Parse the output of `git worktree list --porcelain`.
+///
+/// The main worktree is listed first by git, the other worktrees are sorted by path.
+fn parse_worktree_list(output: &str) -> Vec<Worktree> {
+    let mut result: Vec<_> = output
+        .split("\n\n")
+        .enumerate()
+        .filter_map(|(index, block)| {
+            let mut worktree = Worktree {
+                is_main: index == 0,
+                ..Default::default()
+            };
+            for line in block.lines() {
+                if let Some(path) = line.strip_prefix("worktree ") {
+                    worktree.path = PathBuf::from(path);
+                } else if let Some(branch) = line.strip_prefix("branch ") {
+                    let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                    worktree.branch = Some(branch.to_string());
+                } else if line == "bare" {
+                    return None;
+                }
+            }
+            match worktree.path.as_os_str().is_empty() {
+                true => None,
+                false => Some(worktree),
+            }
         })
+        .collect();
+
+    // Sorting puts the main worktree first, after that by path.
+    result.sort_by(|left, right| {
+        right
+            .is_main
+            .cmp(&left.is_main)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+
+    result
+}
+
+/// Parse a single line of `git for-each-ref`, see `get_branch_tracking` for the format.
+fn parse_branch_tracking(line: &str) -> Option<BranchTracking> {
+    let parts: Box<[_]> = line.splitn(3, '\0').collect();
+    let [branch, upstream, track] = parts.as_ref() else {
+        return None;
+    };
+
+    let mut result = BranchTracking {
+        branch: branch.to_string(),
+        upstream: match (*upstream, *track) {
+            ("", _) | (_, "gone") => None,
+            (upstream, _) => Some(upstream.to_string()),
+        },
+        ahead: 0,
+        behind: 0,
+    };
+
+    for part in track.split(", ") {
+        if let Some(ahead) = part.strip_prefix("ahead ") {
+            result.ahead = ahead.parse().unwrap_or(0);
+        } else if let Some(behind) = part.strip_prefix("behind ") {
+            result.behind = behind.parse().unwrap_or(0);
+        }
     }
+
+    Some(result)
 }
 
 impl From<PathStrategyError> for GitStrategyError {
@@ -184,3 +2127,301 @@ impl From<PathStrategyError> for GitStrategyError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_clean_patterns() {
+        let patterns = [String::from("node_modules/"), String::from("*.pyc")];
+
+        assert!(matches_clean_patterns(
+            "node_modules/left-pad/index.js",
+            &patterns
+        ));
+        assert!(matches_clean_patterns(
+            "frontend/node_modules/left-pad/index.js",
+            &patterns
+        ));
+        assert!(matches_clean_patterns(
+            "src/__pycache__/main.pyc",
+            &patterns
+        ));
+
+        assert!(!matches_clean_patterns("node_modules", &patterns));
+        assert!(!matches_clean_patterns("target/debug/grass", &patterns));
+        assert!(!matches_clean_patterns("main.pyc.bak", &patterns));
+    }
+
+    #[test]
+    fn test_parse_lfs_pointer() {
+        let pointer = "version https://git-lfs.github.com/spec/v1\n\
+            oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+            size 12345\n";
+
+        assert_eq!(
+            parse_lfs_pointer(pointer.as_bytes()),
+            Some(LfsPointer {
+                oid: String::from(
+                    "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393"
+                ),
+                size: 12345,
+            })
+        );
+        assert_eq!(parse_lfs_pointer(b"size 12345\n"), None);
+        assert_eq!(
+            parse_lfs_pointer(b"version https://git-lfs.github.com/spec/v1\nsize 12345\n"),
+            None
+        );
+        assert_eq!(parse_lfs_pointer(&[0xff, 0xfe, 0x00]), None);
+    }
+
+    #[test]
+    fn test_parse_cat_file_batch() {
+        let output = b"1234 blob 5\nhello\n5678 missing\nabcd blob 0\n\n";
+
+        assert_eq!(
+            parse_cat_file_batch(output, 3),
+            vec![b"hello".to_vec(), Vec::new(), Vec::new()]
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/home/me/.ssh/id"), "'/home/me/.ssh/id'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let output = "worktree /repositories/work/app\n\
+                      HEAD 1a2b3c4d\n\
+                      branch refs/heads/main\n\
+                      \n\
+                      worktree /worktrees/work/app/feature/login\n\
+                      HEAD 5d6e7f8a\n\
+                      branch refs/heads/feature/login\n\
+                      \n\
+                      worktree /worktrees/work/app/bisect\n\
+                      HEAD 9a8b7c6d\n\
+                      detached\n\
+                      prunable gitdir file points to non-existent location\n";
+
+        assert_eq!(
+            parse_worktree_list(output),
+            vec![
+                Worktree {
+                    path: PathBuf::from("/repositories/work/app"),
+                    branch: Some(String::from("main")),
+                    is_main: true,
+                },
+                Worktree {
+                    path: PathBuf::from("/worktrees/work/app/bisect"),
+                    branch: None,
+                    is_main: false,
+                },
+                Worktree {
+                    path: PathBuf::from("/worktrees/work/app/feature/login"),
+                    branch: Some(String::from("feature/login")),
+                    is_main: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_branch_info() {
+        assert_eq!(
+            parse_branch_info("*\x00main\x00origin/main\x00ahead 1\x001700000000"),
+            Some(BranchInfo {
+                branch: String::from("main"),
+                upstream: Some(String::from("origin/main")),
+                upstream_gone: false,
+                is_default: false,
+                is_current: true,
+                is_merged: false,
+                last_commit: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            })
+        );
+        assert_eq!(
+            parse_branch_info(" \x00removed\x00origin/removed\x00gone\x001700000000"),
+            Some(BranchInfo {
+                branch: String::from("removed"),
+                upstream: Some(String::from("origin/removed")),
+                upstream_gone: true,
+                is_default: false,
+                is_current: false,
+                is_merged: false,
+                last_commit: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            })
+        );
+        assert_eq!(parse_branch_info(" \x00feature\x00\x00"), None);
+    }
+
+    #[test]
+    fn test_parse_stash() {
+        assert_eq!(
+            parse_stash("stash@{1}\x001700000000\x00On main: Try another layout"),
+            Some(Stash {
+                index: 1,
+                message: String::from("On main: Try another layout"),
+                created: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            })
+        );
+        assert_eq!(parse_stash("stash@{x}\x001700000000\x00message"), None);
+        assert_eq!(parse_stash(""), None);
+    }
+
+    #[test]
+    fn test_remote_error() {
+        assert!(matches!(
+            remote_error(
+                "message",
+                b"git@github.com: Permission denied (publickey).".to_vec()
+            ),
+            GitStrategyError::RemoteAuthenticationError { .. }
+        ));
+        assert!(matches!(
+            remote_error(
+                "message",
+                b"fatal: repository 'https://example.com/missing' not found".to_vec()
+            ),
+            GitStrategyError::RemoteFetchError { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_porcelain_v2() {
+        let output = "1 .M N... 100644 100644 100644 abc abc src/main.rs\0\
+                      1 M. SC.. 160000 160000 160000 abc def vendor/library\0\
+                      2 R. N... 100644 100644 100644 abc abc R100 new file.rs\0old file.rs\0\
+                      u UU N... 100644 100644 100644 100644 abc def ghi src/conflict.rs\0\
+                      ? h\u{e9}llo w\u{f6}rld.txt\0\
+                      ! target/\0";
+
+        let changes: Vec<_> = parse_porcelain_v2(output)
+            .iter()
+            .filter_map(GitInternalChangeRepresentation::to_file_change)
+            .collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                FileChange {
+                    path: PathBuf::from("src/main.rs"),
+                    original_path: None,
+                    staged: FileChangeKind::Unmodified,
+                    unstaged: FileChangeKind::Modified,
+                    submodule: None,
+                },
+                FileChange {
+                    path: PathBuf::from("vendor/library"),
+                    original_path: None,
+                    staged: FileChangeKind::Modified,
+                    unstaged: FileChangeKind::Unmodified,
+                    submodule: Some(SubmoduleChange {
+                        commit_changed: true,
+                        tracked_changes: false,
+                        untracked_changes: false,
+                    }),
+                },
+                FileChange {
+                    path: PathBuf::from("new file.rs"),
+                    original_path: Some(PathBuf::from("old file.rs")),
+                    staged: FileChangeKind::Renamed,
+                    unstaged: FileChangeKind::Unmodified,
+                    submodule: None,
+                },
+                FileChange {
+                    path: PathBuf::from("src/conflict.rs"),
+                    original_path: None,
+                    staged: FileChangeKind::Unmerged,
+                    unstaged: FileChangeKind::Unmerged,
+                    submodule: None,
+                },
+                FileChange {
+                    path: PathBuf::from("héllo wörld.txt"),
+                    original_path: None,
+                    staged: FileChangeKind::Untracked,
+                    unstaged: FileChangeKind::Untracked,
+                    submodule: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_submodule_status() {
+        let changes = [FileChange {
+            path: PathBuf::from("vendor/library"),
+            submodule: Some(SubmoduleChange {
+                untracked_changes: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            parse_submodule_status("-abc123 vendor/docs", &changes),
+            Some(Submodule {
+                path: PathBuf::from("vendor/docs"),
+                initialized: false,
+                change: SubmoduleChange::default(),
+            })
+        );
+        assert_eq!(
+            parse_submodule_status("+def456 vendor/library (v1.0-2-gdef456)", &changes),
+            Some(Submodule {
+                path: PathBuf::from("vendor/library"),
+                initialized: true,
+                change: SubmoduleChange {
+                    commit_changed: true,
+                    tracked_changes: false,
+                    untracked_changes: true,
+                },
+            })
+        );
+        assert_eq!(
+            parse_submodule_status(" abc123 my lib (heads/main)", &[]),
+            Some(Submodule {
+                path: PathBuf::from("my lib"),
+                initialized: true,
+                change: SubmoduleChange::default(),
+            })
+        );
+        assert_eq!(parse_submodule_status("", &[]), None);
+    }
+
+    #[test]
+    fn test_parse_branch_tracking() {
+        assert_eq!(
+            parse_branch_tracking("main\0origin/main\0ahead 2, behind 1"),
+            Some(BranchTracking {
+                branch: "main".into(),
+                upstream: Some("origin/main".into()),
+                ahead: 2,
+                behind: 1,
+            })
+        );
+        assert_eq!(
+            parse_branch_tracking("feature\0\0"),
+            Some(BranchTracking {
+                branch: "feature".into(),
+                upstream: None,
+                ahead: 0,
+                behind: 0,
+            })
+        );
+        assert_eq!(
+            parse_branch_tracking("old\0origin/old\0gone"),
+            Some(BranchTracking {
+                branch: "old".into(),
+                upstream: None,
+                ahead: 0,
+                behind: 0,
+            })
+        );
+        assert_eq!(parse_branch_tracking("invalid"), None);
+    }
+}
@@ -1,6 +1,16 @@
-use crate::dev::public::api::RepositoryLocation;
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
-use super::{GitStrategy, GitStrategyError, RepositoryChangeStatus, Result};
+use crate::dev::public::api::{RepositoryLocation, WorktreeLocation};
+
+use super::{
+    BranchInfo, BranchTracking, CleanReport, CloneOptions, CommitSummary, FileChange,
+    FileChangeKind, GitOperation, GitStrategy, GitStrategyError, HeadState, InitOptions, LfsStatus,
+    PullMode, PullResult, RepositoryChangeStatus, RepositoryMetadata, RepositorySize, Result,
+    Stash, Submodule, SubmoduleChange, Worktree,
+};
 
 /// Strategy used for mocking
 ///
@@ -12,13 +22,16 @@ use super::{GitStrategy, GitStrategyError, RepositoryChangeStatus, Result};
 /// One indentation are the repositories.
 ///
 /// - all_good (all of these are working and have no changes)
-///   - first
+///   - first (80 KiB, cleaning removes 2 files, freeing 4096 bytes, pulling updates 3 commits)
 ///   - second
-///   - third
+///   - third (fetching and pushing fail to authenticate)
 /// - with_changes
-///   - first (no changes)
+///   - first (no changes, branches 'old-feature' is merged, 'removed' is gone, 'experiment' is stale)
 ///   - second (no repository)
-///   - third (9 uncommitted changes)
+///   - third (9 uncommitted changes, 2 stashes of which one is stale)
+///   - fourth (unpushed commits on 'main', which has diverged so pushing is rejected,
+///     and 'feature' without upstream, 1 stash)
+///   - fifth (rebase in progress, with 2 conflicts)
 /// - with_error
 ///   - first (invalid repository)
 ///   - second (unsufficient file permissions)
@@ -30,18 +43,36 @@ use super::{GitStrategy, GitStrategyError, RepositoryChangeStatus, Result};
 /// - good_remote (no errors)
 /// - no_access (authentication error)
 /// - bad_response (invalid response)
+/// - https://example.com/... (no errors)
+///
+/// Only the category all_good has a remote template, `https://example.com/all_good/{repository}.git`.
+///
+/// # Stashes
+///
+/// Stashes older than 30 days are considered stale.
+///
+/// # Workers
+///
+/// Bulk operations use 2 workers, so they are run on multiple threads.
 #[derive(Default)]
 pub struct MockGitStrategy;
 
 impl GitStrategy for MockGitStrategy {
-    fn clean<T>(&self, repository: T) -> Result<()>
+    fn clean<T>(&self, repository: T) -> Result<CleanReport>
     where
         T: Into<RepositoryLocation>,
     {
         let repository: RepositoryLocation = repository.into();
         let repository = (repository.category.as_ref(), repository.repository.as_str());
         match repository {
-            ("all_good" | "with_changes", _) => Ok(()),
+            ("all_good", "first") => Ok(CleanReport {
+                removed_files: vec![
+                    PathBuf::from("node_modules/left-pad/index.js"),
+                    PathBuf::from("target/debug/first"),
+                ],
+                freed_bytes: 4096,
+            }),
+            ("all_good" | "with_changes", _) => Ok(CleanReport::default()),
             ("with_error", "first") => Err(GitStrategyError::RepositoryError {
                 message: "Mocked error".into(),
                 reason: "invalid repository".into(),
@@ -58,7 +89,40 @@ impl GitStrategy for MockGitStrategy {
         }
     }
 
-    fn clone<T, U>(&self, repository: T, remote: U) -> Result<()>
+    fn get_size<T>(&self, repository: T) -> Result<RepositorySize>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first") => Ok(RepositorySize {
+                total_bytes: 81920,
+                reclaimable_bytes: 4096,
+                git_bytes: 32768,
+            }),
+            ("with_changes", "second") => Ok(RepositorySize {
+                total_bytes: 4096,
+                reclaimable_bytes: 0,
+                git_bytes: 0,
+            }),
+            ("all_good" | "with_changes", _) => Ok(RepositorySize {
+                total_bytes: 40960,
+                reclaimable_bytes: 0,
+                git_bytes: 24576,
+            }),
+            ("with_error", _) => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "invalid repository".into(),
+            }),
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn clone<T, U>(&self, repository: T, remote: U, options: &CloneOptions) -> Result<()>
     where
         T: Into<RepositoryLocation>,
         U: AsRef<str>,
@@ -67,7 +131,8 @@ impl GitStrategy for MockGitStrategy {
         let repository = (repository.category.as_ref(), repository.repository.as_str());
         match repository {
             ("all_good" | "with_changes" | "with_error", "first" | "second")
-            | ("all_good" | "with_changes", "third") => Err(GitStrategyError::RepositryExists {
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => Err(GitStrategyError::RepositryExists {
                 message: "Mocked error".into(),
                 reason: "Can't fetch repository because it already exists locally".into(),
             }),
@@ -78,8 +143,16 @@ impl GitStrategy for MockGitStrategy {
             }),
         }?;
 
+        if options.branch.as_deref() == Some("missing") {
+            return Err(GitStrategyError::RemoteFetchError {
+                message: "Mocked error".into(),
+                reason: "Remote branch missing not found".into(),
+            });
+        }
+
         match remote.as_ref() {
             "good_remote" => Ok(()),
+            remote if remote.starts_with("https://example.com/") => Ok(()),
             "no_access" => Err(GitStrategyError::RemoteAuthenticationError {
                 message: "Mocked error".into(),
                 reason: "You are not authorized to access this remote".into(),
@@ -95,6 +168,34 @@ impl GitStrategy for MockGitStrategy {
         }
     }
 
+    fn init<T>(&self, repository: T, options: &InitOptions) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good" | "with_changes" | "with_error", "first")
+            | ("all_good" | "with_error", "second")
+            | ("all_good" | "with_changes", "third")
+            | ("with_changes", "fourth" | "fifth") => Err(GitStrategyError::RepositryExists {
+                message: "Mocked error".into(),
+                reason: "A repository has already been initialized".into(),
+            }),
+            ("with_changes" | "with_error", _) if options.add_remote => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "No remote template is configured for the category".into(),
+                })
+            }
+            ("all_good" | "with_changes" | "with_error", _) => Ok(()),
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "Category does not exist".into(),
+            }),
+        }
+    }
+
     fn get_changes<T>(&self, repository: T) -> Result<RepositoryChangeStatus>
     where
         T: Into<RepositoryLocation>,
@@ -108,6 +209,88 @@ impl GitStrategy for MockGitStrategy {
             ("with_changes", "third") => {
                 Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 9 })
             }
+            ("with_changes", "fifth") => Ok(RepositoryChangeStatus::OperationInProgress {
+                operation: GitOperation::Rebase,
+                num_conflicts: 2,
+            }),
+            ("with_changes", "fourth") => Ok(RepositoryChangeStatus::UnpushedWork {
+                branches: self
+                    .get_branch_tracking(repository)?
+                    .into_iter()
+                    .filter(BranchTracking::has_unpushed_work)
+                    .collect(),
+            }),
+            ("with_error", "first") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "invalid repository".into(),
+            }),
+            ("with_error", "second") => Err(GitStrategyError::FileSystemError {
+                message: "Mocked error".into(),
+                reason: "insufficient permission".into(),
+                reasons: vec![],
+            }),
+            ("all_good" | "with_changes" | "with_error", _) => {
+                Err(GitStrategyError::RepositoryNotFound {
+                    message: "Mocked error".into(),
+                    reason: "repository not found".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "category not found".into(),
+            }),
+        }
+    }
+
+    fn get_change_details<T>(&self, repository: T) -> Result<Vec<FileChange>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        use FileChangeKind::*;
+
+        let change = |path: &str, staged, unstaged| FileChange {
+            path: PathBuf::from(path),
+            original_path: None,
+            staged,
+            unstaged,
+            submodule: None,
+        };
+
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third") | ("with_changes", "first" | "fourth") => {
+                Ok(vec![])
+            }
+            ("with_changes", "second") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "no repository".into(),
+            }),
+            ("with_changes", "fifth") => Ok(vec![
+                change("src/main.rs", Unmerged, Unmerged),
+                change("src/lib.rs", Unmerged, Unmerged),
+            ]),
+            ("with_changes", "third") => Ok(vec![
+                change("src/main.rs", Modified, Unmodified),
+                change("src/lib.rs", Modified, Modified),
+                change("src/new.rs", Added, Unmodified),
+                change("src/removed.rs", Unmodified, Deleted),
+                change("run", Unmodified, TypeChanged),
+                FileChange {
+                    original_path: Some(PathBuf::from("README")),
+                    ..change("README.md", Renamed, Unmodified)
+                },
+                FileChange {
+                    submodule: Some(SubmoduleChange {
+                        commit_changed: true,
+                        tracked_changes: false,
+                        untracked_changes: false,
+                    }),
+                    ..change("vendor/library", Modified, Unmodified)
+                },
+                change("notes.txt", Untracked, Untracked),
+                change("todo.txt", Untracked, Untracked),
+            ]),
             ("with_error", "first") => Err(GitStrategyError::RepositoryError {
                 message: "Mocked error".into(),
                 reason: "invalid repository".into(),
@@ -129,4 +312,567 @@ impl GitStrategy for MockGitStrategy {
             }),
         }
     }
+
+    fn get_lfs_status<T>(&self, repository: T) -> Result<Option<LfsStatus>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third")
+            | ("with_changes", "first" | "fourth" | "fifth") => Ok(None),
+            ("with_changes", "third") => Ok(Some(LfsStatus {
+                missing_objects: vec![PathBuf::from("assets/logo.png")],
+            })),
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn list_submodules<T>(&self, repository: T) -> Result<Vec<Submodule>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third")
+            | ("with_changes", "first" | "fourth" | "fifth") => Ok(vec![]),
+            ("with_changes", "third") => Ok(vec![
+                Submodule {
+                    path: PathBuf::from("vendor/docs"),
+                    initialized: false,
+                    change: SubmoduleChange::default(),
+                },
+                Submodule {
+                    path: PathBuf::from("vendor/library"),
+                    initialized: true,
+                    change: SubmoduleChange {
+                        commit_changed: true,
+                        ..Default::default()
+                    },
+                },
+            ]),
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn get_metadata<T>(&self, repository: T) -> Result<RepositoryMetadata>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        const DAY: u64 = 24 * 60 * 60;
+        let repository: RepositoryLocation = repository.into();
+        let commit = |id: &str, subject: &str, age| {
+            Some(CommitSummary {
+                id: id.into(),
+                subject: subject.into(),
+                time: SystemTime::now() - Duration::from_secs(age),
+            })
+        };
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third") => Ok(RepositoryMetadata {
+                head: HeadState::Branch("main".into()),
+                last_commit: commit("9f8e7d6", "Initial commit", DAY),
+            }),
+            ("with_changes", "first" | "third" | "fourth") => Ok(RepositoryMetadata {
+                head: HeadState::Branch("main".into()),
+                last_commit: commit("5e6f7a8", "Add feature", 3 * DAY),
+            }),
+            ("with_changes", "fifth") => Ok(RepositoryMetadata {
+                head: HeadState::Detached("1a2b3c4".into()),
+                last_commit: commit("1a2b3c4", "Fix typo", 60 * 60),
+            }),
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn get_branch_tracking<T>(&self, repository: T) -> Result<Vec<BranchTracking>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third") | ("with_changes", "first" | "third") => {
+                Ok(vec![BranchTracking {
+                    branch: "main".into(),
+                    upstream: Some("origin/main".into()),
+                    ahead: 0,
+                    behind: 0,
+                }])
+            }
+            ("with_changes", "fourth") => Ok(vec![
+                BranchTracking {
+                    branch: "feature".into(),
+                    upstream: None,
+                    ahead: 0,
+                    behind: 0,
+                },
+                BranchTracking {
+                    branch: "main".into(),
+                    upstream: Some("origin/main".into()),
+                    ahead: 2,
+                    behind: 1,
+                },
+            ]),
+            ("with_changes", "second") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "no repository".into(),
+            }),
+            ("with_error", "first") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "invalid repository".into(),
+            }),
+            ("with_error", "second") => Err(GitStrategyError::FileSystemError {
+                message: "Mocked error".into(),
+                reason: "insufficient permission".into(),
+                reasons: vec![],
+            }),
+            ("all_good" | "with_changes" | "with_error", _) => {
+                Err(GitStrategyError::RepositoryNotFound {
+                    message: "Mocked error".into(),
+                    reason: "repository not found".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "category not found".into(),
+            }),
+        }
+    }
+
+    fn list_branches<T>(&self, repository: T) -> Result<Vec<BranchInfo>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        const DAY: u64 = 24 * 60 * 60;
+        let repository: RepositoryLocation = repository.into();
+        let branch = |name: &str, upstream: Option<&str>, age| BranchInfo {
+            branch: name.into(),
+            upstream: upstream.map(String::from),
+            upstream_gone: false,
+            is_default: false,
+            is_current: false,
+            is_merged: false,
+            last_commit: SystemTime::now() - Duration::from_secs(age),
+        };
+        let main = BranchInfo {
+            is_default: true,
+            is_current: true,
+            ..branch("main", Some("origin/main"), DAY)
+        };
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("with_changes", "first") => Ok(vec![
+                branch("experiment", None, 200 * DAY),
+                main,
+                BranchInfo {
+                    is_merged: true,
+                    ..branch("old-feature", Some("origin/old-feature"), 10 * DAY)
+                },
+                BranchInfo {
+                    upstream_gone: true,
+                    ..branch("removed", Some("origin/removed"), 5 * DAY)
+                },
+            ]),
+            ("with_changes", "fourth") => Ok(vec![branch("feature", None, DAY), main]),
+            ("all_good", "first" | "second" | "third") | ("with_changes", "third" | "fifth") => {
+                Ok(vec![main])
+            }
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn delete_branch<T>(&self, repository: T, branch: &str, force: bool) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let branches = self.list_branches(repository)?;
+
+        match branches.iter().find(|info| info.branch == branch) {
+            Some(BranchInfo {
+                is_current: true, ..
+            }) => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "cannot delete the branch which is checked out".into(),
+            }),
+            Some(BranchInfo {
+                is_merged: false, ..
+            }) if !force => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "branch is not merged".into(),
+            }),
+            Some(_) => Ok(()),
+            None => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "branch not found".into(),
+            }),
+        }
+    }
+
+    fn fetch<T>(&self, repository: T) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second")
+            | ("with_changes", "first" | "third" | "fourth" | "fifth") => Ok(()),
+            ("all_good", "third") => Err(GitStrategyError::RemoteAuthenticationError {
+                message: "Mocked error".into(),
+                reason: "You are not authorized to access this remote".into(),
+            }),
+            ("with_changes", "second") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "no repository".into(),
+            }),
+            ("with_error", "first") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "invalid repository".into(),
+            }),
+            ("with_error", "second") => Err(GitStrategyError::FileSystemError {
+                message: "Mocked error".into(),
+                reason: "insufficient permission".into(),
+                reasons: vec![],
+            }),
+            ("all_good" | "with_changes" | "with_error", _) => {
+                Err(GitStrategyError::RepositoryNotFound {
+                    message: "Mocked error".into(),
+                    reason: "repository not found".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "category not found".into(),
+            }),
+        }
+    }
+
+    fn pull<T>(&self, repository: T, mode: PullMode) -> Result<PullResult>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        self.fetch(repository.clone())?;
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match (repository, mode) {
+            (("all_good", "first"), _) => Ok(PullResult::Updated {
+                from: "1a2b3c4".into(),
+                to: "5d6e7f8".into(),
+                num_commits: 3,
+            }),
+            (("with_changes", "fourth"), PullMode::FastForwardOnly) => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "Not possible to fast-forward".into(),
+                })
+            }
+            (("with_changes", "fourth"), _) => Ok(PullResult::Updated {
+                from: "9a8b7c6".into(),
+                to: "5d4e3f2".into(),
+                num_commits: 1,
+            }),
+            (("with_changes", "fifth"), _) => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "rebase in progress".into(),
+            }),
+            _ => Ok(PullResult::UpToDate),
+        }
+    }
+
+    fn commit_all<T>(&self, repository: T, _message: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        match self.get_changes(repository)? {
+            RepositoryChangeStatus::UncommittedChanges { .. } => Ok(()),
+            RepositoryChangeStatus::NoRepository => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "no repository".into(),
+            }),
+            RepositoryChangeStatus::OperationInProgress { .. }
+            | RepositoryChangeStatus::Conflicts { .. } => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "rebase in progress".into(),
+            }),
+            _ => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "nothing to commit".into(),
+            }),
+        }
+    }
+
+    fn push<T>(&self, repository: T) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        self.fetch(repository.clone())?;
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("with_changes", "fourth") => Err(GitStrategyError::RemoteFetchError {
+                message: "Mocked error".into(),
+                reason: "Updates were rejected, the remote contains work you do not have".into(),
+            }),
+            ("with_changes", "fifth") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "HEAD is detached during the rebase".into(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn push_to_remote<T>(&self, repository: T, remote: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        if !matches!(remote, "origin" | "upstream") {
+            return Err(GitStrategyError::RemoteFetchError {
+                message: "Mocked error".into(),
+                reason: format!("'{}' does not appear to be a git repository", remote),
+            });
+        }
+
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "third") => Err(GitStrategyError::RemoteAuthenticationError {
+                message: "Mocked error".into(),
+                reason: "You are not authorized to access this remote".into(),
+            }),
+            ("with_changes", "fourth") => Err(GitStrategyError::RemoteFetchError {
+                message: "Mocked error".into(),
+                reason: "Updates were rejected, the remote contains work you do not have".into(),
+            }),
+            ("with_changes", "fifth") => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "HEAD is detached during the rebase".into(),
+            }),
+            ("with_changes", "second") | ("with_error", _) => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn add_remote<T>(&self, repository: T, name: &str, _url: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("with_changes", "second") | ("with_error", _) => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            // Only the existing repositories have an 'origin'.
+            ("all_good", "first" | "second" | "third")
+            | ("with_changes", "first" | "third" | "fourth" | "fifth")
+                if name == "origin" =>
+            {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "remote origin already exists".into(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn rename_remote<T>(&self, repository: T, from: &str, _to: &str) -> Result<()>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("with_changes", "second") | ("with_error", _) => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ if from != "origin" => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: format!("No such remote '{}'", from),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn list_worktrees<T>(&self, repository: T) -> Result<Vec<Worktree>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let main = Worktree {
+            path: PathBuf::from("/home/example/repositories")
+                .join(repository.category.as_ref() as &str)
+                .join(&repository.repository),
+            branch: Some("main".into()),
+            is_main: true,
+        };
+        let feature = Worktree {
+            path: PathBuf::from("/home/example/worktrees")
+                .join(repository.category.as_ref() as &str)
+                .join(&repository.repository)
+                .join("feature"),
+            branch: Some("feature".into()),
+            is_main: false,
+        };
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good" | "with_changes", "first") => Ok(vec![main, feature]),
+            ("all_good", "second" | "third") | ("with_changes", "third" | "fourth" | "fifth") => {
+                Ok(vec![main])
+            }
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
+
+    fn add_worktree<T>(&self, worktree: T) -> Result<()>
+    where
+        T: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        let worktrees = self.list_worktrees(repository)?;
+
+        match worktrees
+            .iter()
+            .find(|worktree| worktree.branch.as_ref() == Some(&branch))
+        {
+            Some(Worktree { is_main: true, .. }) => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "branch is already checked out".into(),
+            }),
+            Some(_) => Err(GitStrategyError::RepositryExists {
+                message: "Mocked error".into(),
+                reason: "worktree already exists".into(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn remove_worktree<T>(&self, worktree: T, force: bool) -> Result<()>
+    where
+        T: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        let worktrees = self.list_worktrees(repository.clone())?;
+
+        match worktrees
+            .iter()
+            .find(|worktree| worktree.branch.as_ref() == Some(&branch))
+        {
+            Some(Worktree { is_main: true, .. }) => Err(GitStrategyError::RepositoryError {
+                message: "Mocked error".into(),
+                reason: "cannot remove the main worktree".into(),
+            }),
+            Some(_) if repository.category.0 == "with_changes" && !force => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "worktree contains modified or untracked files".into(),
+                })
+            }
+            Some(_) => Ok(()),
+            None => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "no worktree has the branch checked out".into(),
+            }),
+        }
+    }
+
+    fn list_stashes<T>(&self, repository: T) -> Result<Vec<Stash>>
+    where
+        T: Into<RepositoryLocation>,
+    {
+        const DAY: u64 = 24 * 60 * 60;
+        let repository: RepositoryLocation = repository.into();
+        let stash = |index, message: &str, age| Stash {
+            index,
+            message: message.into(),
+            created: SystemTime::now() - Duration::from_secs(age),
+        };
+
+        let repository = (repository.category.as_ref(), repository.repository.as_str());
+        match repository {
+            ("all_good", "first" | "second" | "third") | ("with_changes", "first" | "fifth") => {
+                Ok(vec![])
+            }
+            ("with_changes", "third") => Ok(vec![
+                stash(0, "WIP on main: 1a2b3c4 Fix typo", 60 * 60),
+                stash(1, "On main: Try another layout", 90 * DAY),
+            ]),
+            ("with_changes", "fourth") => Ok(vec![stash(0, "On feature: Debug output", DAY)]),
+            ("with_changes", "second") | ("with_error", "first" | "second") => {
+                Err(GitStrategyError::RepositoryError {
+                    message: "Mocked error".into(),
+                    reason: "invalid repository".into(),
+                })
+            }
+            _ => Err(GitStrategyError::RepositoryNotFound {
+                message: "Mocked error".into(),
+                reason: "cannot find repository".into(),
+            }),
+        }
+    }
 }
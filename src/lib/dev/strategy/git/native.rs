@@ -0,0 +1,1597 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use git2::{
+    build::{CheckoutBuilder, RepoBuilder},
+    AttrCheckFlags, Branch, BranchType, Cred, CredentialType, ErrorClass, ErrorCode, FetchOptions,
+    IndexAddOption, Oid, PushOptions, RebaseOptions, RemoteCallbacks, Repository,
+    RepositoryInitOptions, RepositoryState, Status, StatusEntry, StatusOptions, SubmoduleIgnore,
+    SubmoduleStatus, SubmoduleUpdateOptions, WorktreeAddOptions, WorktreePruneOptions,
+};
+
+use crate::dev::{config::GrassConfig, strategy::path::PathStrategy, Category, WorktreeLocation};
+
+use super::{
+    local::{
+        apply_template, check_branch_deletable, count_stale_stashes, find_worktree,
+        get_configured_clean_patterns, get_init_settings, get_repository_size,
+        get_submodule_change_status, is_changed_by_lfs, is_lfs_pointer_file, is_smudged_lfs_file,
+        prefix_file_change, read_token, remove_ignored_files,
+    },
+    BranchInfo, BranchTracking, CleanReport, CloneOptions, CommitSummary, FileChange,
+    FileChangeKind, GitOperation, GitStrategy, GitStrategyError, HeadState, InitOptions, LfsStatus,
+    PullMode, PullResult, RepositoryChangeStatus, RepositoryLocation, RepositoryMetadata,
+    RepositorySize, Result, Stash, Submodule, SubmoduleChange, Worktree,
+};
+
+fn repository_error(message: &str, error: git2::Error) -> GitStrategyError {
+    GitStrategyError::RepositoryError {
+        message: String::from(message),
+        reason: String::from(error.message()),
+    }
+}
+
+/// Map a failed operation which accessed a remote to an error.
+fn remote_error(message: &str, error: git2::Error) -> GitStrategyError {
+    let is_authentication_error = error.code() == ErrorCode::Auth
+        || error.class() == ErrorClass::Ssh
+        || (error.class() == ErrorClass::Http
+            && (error.message().contains("401") || error.message().contains("403")));
+
+    if is_authentication_error {
+        GitStrategyError::RemoteAuthenticationError {
+            message: String::from(message),
+            reason: String::from(error.message()),
+        }
+    } else {
+        GitStrategyError::RemoteFetchError {
+            message: String::from(message),
+            reason: String::from(error.message()),
+        }
+    }
+}
+
+fn authentication_error(message: &str) -> git2::Error {
+    git2::Error::new(ErrorCode::Auth, ErrorClass::Callback, message)
+}
+
+/// Open a repository, which must be located directly in the given directory.
+fn open_repository(repository_path: &Path, message: &str) -> Result<Repository> {
+    if !repository_path.join(".git").exists() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from(message),
+            reason: String::from("No repository has been initialized"),
+        });
+    }
+
+    Repository::open(repository_path).map_err(|error| repository_error(message, error))
+}
+
+/// Get the branch from the contents of a HEAD file, `None` when HEAD is detached.
+fn parse_head_branch(head: &str) -> Option<String> {
+    head.trim_end()
+        .strip_prefix("ref: refs/heads/")
+        .map(String::from)
+}
+
+/// Remove trailing separators, so paths compare the same as the paths reported by git.
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components().collect()
+}
+
+/// List the worktrees, together with the name git uses for linked worktrees.
+fn get_worktrees(repository: &Repository) -> Result<Vec<(Option<String>, Worktree)>> {
+    let mut result = vec![];
+
+    if let Some(workdir) = repository.workdir() {
+        let branch = repository
+            .find_reference("HEAD")
+            .ok()
+            .and_then(|head| head.symbolic_target().map(String::from))
+            .and_then(|head| parse_head_branch(&format!("ref: {}", head)));
+        result.push((
+            None,
+            Worktree {
+                path: normalize_path(workdir),
+                branch,
+                is_main: true,
+            },
+        ));
+    }
+
+    let names = repository
+        .worktrees()
+        .map_err(|error| repository_error("Could not list worktrees", error))?;
+
+    for name in names.iter().flatten() {
+        let worktree = repository
+            .find_worktree(name)
+            .map_err(|error| repository_error("Could not open the worktree", error))?;
+        // Read HEAD directly, so worktrees with a missing directory are listed as well.
+        let branch =
+            fs::read_to_string(repository.path().join("worktrees").join(name).join("HEAD"))
+                .ok()
+                .and_then(|head| parse_head_branch(&head));
+
+        result.push((
+            Some(String::from(name)),
+            Worktree {
+                path: normalize_path(worktree.path()),
+                branch,
+                is_main: false,
+            },
+        ));
+    }
+
+    result.sort_by(|(_, left), (_, right)| {
+        right
+            .is_main
+            .cmp(&left.is_main)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+
+    Ok(result)
+}
+
+/// Ask a credential helper for a username and password, using the git credential protocol.
+///
+/// The helper is interpreted the same way git does for `credential.helper`.
+fn run_credential_helper(
+    helper: &str,
+    url: &str,
+    username: Option<&str>,
+) -> std::result::Result<Cred, git2::Error> {
+    let command = match helper.strip_prefix('!') {
+        Some(command) => String::from(command),
+        None if Path::new(helper).is_absolute() => String::from(helper),
+        None => format!("git credential-{}", helper),
+    };
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", command))
+        .arg(&command)
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|error| authentication_error(&error.to_string()))?;
+
+    let mut input = format!("url={}\n", url);
+    if let Some(username) = username {
+        input.push_str(&format!("username={}\n", username));
+    }
+    input.push('\n');
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|error| authentication_error(&error.to_string()))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| authentication_error(&error.to_string()))?;
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let mut result_username = username.map(String::from);
+    let mut result_password = None;
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix("username=") {
+            result_username = Some(String::from(value));
+        } else if let Some(value) = line.strip_prefix("password=") {
+            result_password = Some(String::from(value));
+        }
+    }
+
+    match (result_username, result_password) {
+        (Some(username), Some(password)) => Cred::userpass_plaintext(&username, &password),
+        _ => Err(authentication_error(
+            "The credential helper did not return a username and password",
+        )),
+    }
+}
+
+fn to_file_change(repository: &Repository, entry: &StatusEntry) -> Option<FileChange> {
+    let status = entry.status();
+    if status.is_ignored() || status == Status::CURRENT {
+        return None;
+    }
+
+    let path = PathBuf::from(String::from_utf8_lossy(entry.path_bytes()).as_ref());
+
+    let (path, original_path) = match entry.head_to_index() {
+        Some(delta) if status.is_index_renamed() => (
+            delta
+                .new_file()
+                .path()
+                .map(Path::to_path_buf)
+                .unwrap_or(path),
+            delta.old_file().path().map(Path::to_path_buf),
+        ),
+        _ => (path, None),
+    };
+
+    let submodule = path
+        .to_str()
+        .filter(|path| repository.find_submodule(path).is_ok())
+        .and_then(|path| {
+            repository
+                .submodule_status(path, SubmoduleIgnore::Unspecified)
+                .ok()
+        })
+        .map(to_submodule_change);
+
+    let (staged, unstaged) = if status.is_conflicted() {
+        (FileChangeKind::Unmerged, FileChangeKind::Unmerged)
+    } else if status.is_wt_new() {
+        (FileChangeKind::Untracked, FileChangeKind::Untracked)
+    } else {
+        let staged = if status.is_index_new() {
+            FileChangeKind::Added
+        } else if status.is_index_modified() {
+            FileChangeKind::Modified
+        } else if status.is_index_deleted() {
+            FileChangeKind::Deleted
+        } else if status.is_index_renamed() {
+            FileChangeKind::Renamed
+        } else if status.is_index_typechange() {
+            FileChangeKind::TypeChanged
+        } else {
+            FileChangeKind::Unmodified
+        };
+        let unstaged = if status.is_wt_modified() {
+            FileChangeKind::Modified
+        } else if status.is_wt_deleted() {
+            FileChangeKind::Deleted
+        } else if status.is_wt_renamed() {
+            FileChangeKind::Renamed
+        } else if status.is_wt_typechange() {
+            FileChangeKind::TypeChanged
+        } else {
+            FileChangeKind::Unmodified
+        };
+        (staged, unstaged)
+    };
+
+    Some(FileChange {
+        path,
+        original_path,
+        staged,
+        unstaged,
+        submodule,
+    })
+}
+
+/// Map the status of a submodule to the flags of `git status --porcelain=v2`.
+fn to_submodule_change(status: SubmoduleStatus) -> SubmoduleChange {
+    SubmoduleChange {
+        commit_changed: status
+            .intersects(SubmoduleStatus::WD_MODIFIED | SubmoduleStatus::INDEX_MODIFIED),
+        tracked_changes: status
+            .intersects(SubmoduleStatus::WD_INDEX_MODIFIED | SubmoduleStatus::WD_WD_MODIFIED),
+        untracked_changes: status.contains(SubmoduleStatus::WD_UNTRACKED),
+    }
+}
+
+/// Get the changed files of a repository.
+///
+/// When recursing, the changed files of submodules with dirty contents are included.
+fn get_file_changes(repository: &Repository, recurse: bool) -> Result<Vec<FileChange>> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(false)
+        .renames_head_to_index(true);
+
+    let statuses = repository
+        .statuses(Some(&mut options))
+        .map_err(|error| repository_error("Could not get the repository status", error))?;
+
+    let mut changes: Vec<_> = statuses
+        .iter()
+        .filter_map(|entry| to_file_change(repository, &entry))
+        .collect();
+
+    // libgit2 does not run the LFS filter, so changed LFS files are compared with their pointer.
+    let workdir = repository.workdir().unwrap_or_else(|| repository.path());
+    if changes.iter().any(is_changed_by_lfs) {
+        let index = repository
+            .index()
+            .map_err(|error| repository_error("Could not read the index", error))?;
+
+        changes.retain(|change| {
+            !is_changed_by_lfs(change)
+                || !uses_lfs_filter(repository, &change.path)
+                || !index
+                    .get_path(&change.path, 0)
+                    .and_then(|entry| repository.find_blob(entry.id).ok())
+                    .is_some_and(|blob| {
+                        is_smudged_lfs_file(&workdir.join(&change.path), blob.content())
+                    })
+        });
+    }
+
+    if recurse {
+        let submodules: Vec<_> = changes
+            .iter()
+            .filter(|change| change.submodule.is_some_and(|sub| sub.has_dirty_contents()))
+            .map(|change| change.path.clone())
+            .collect();
+
+        for submodule in submodules {
+            let nested = open_submodule(repository, &submodule)?;
+            changes.extend(
+                get_file_changes(&nested, true)?
+                    .into_iter()
+                    .map(|change| prefix_file_change(&submodule, change)),
+            );
+        }
+    }
+
+    Ok(changes)
+}
+
+/// List the LFS files which are checked out as pointers, if the repository uses LFS.
+fn get_lfs_status(repository: &Repository) -> Result<Option<LfsStatus>> {
+    let workdir = repository.workdir().unwrap_or_else(|| repository.path());
+    let index = repository
+        .index()
+        .map_err(|error| repository_error("Could not read the index", error))?;
+
+    let mut lfs_files: Vec<_> = index
+        .iter()
+        .map(|entry| PathBuf::from(String::from_utf8_lossy(&entry.path).as_ref()))
+        .filter(|path| uses_lfs_filter(repository, path))
+        .collect();
+    if lfs_files.is_empty() {
+        return Ok(None);
+    }
+    // Conflicted files have an entry for each stage.
+    lfs_files.dedup();
+
+    Ok(Some(LfsStatus {
+        missing_objects: lfs_files
+            .into_iter()
+            .filter(|path| is_lfs_pointer_file(&workdir.join(path)))
+            .collect(),
+    }))
+}
+
+/// Whether the attributes assign the `lfs` filter to a file.
+fn uses_lfs_filter(repository: &Repository, path: &Path) -> bool {
+    repository
+        .get_attr(path, "filter", AttrCheckFlags::default())
+        .is_ok_and(|filter| filter == Some("lfs"))
+}
+
+/// List the ignored files of a repository, relative to the repository.
+fn get_ignored_files(repository: &Repository) -> Result<Vec<String>> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(false)
+        .include_ignored(true)
+        .recurse_ignored_dirs(true);
+
+    let statuses = repository
+        .statuses(Some(&mut options))
+        .map_err(|error| repository_error("Could not list ignored files", error))?;
+
+    Ok(statuses
+        .iter()
+        .filter(|entry| entry.status().is_ignored())
+        .map(|entry| String::from_utf8_lossy(entry.path_bytes()).to_string())
+        .collect())
+}
+
+fn open_submodule(repository: &Repository, path: &Path) -> Result<Repository> {
+    let workdir = repository.workdir().unwrap_or_else(|| repository.path());
+    Repository::open(workdir.join(path))
+        .map_err(|error| repository_error("Could not open the submodule", error))
+}
+
+/// List the submodules, sorted by path.
+fn get_submodules(repository: &Repository, recurse: bool) -> Result<Vec<Submodule>> {
+    let mut result = Vec::new();
+    let submodules = repository
+        .submodules()
+        .map_err(|error| repository_error("Could not list the submodules", error))?;
+
+    for submodule in submodules {
+        let path = submodule.path().to_path_buf();
+        let status = repository
+            .submodule_status(
+                submodule.name().unwrap_or_default(),
+                SubmoduleIgnore::Unspecified,
+            )
+            .map_err(|error| repository_error("Could not get the submodule status", error))?;
+        let initialized = !status.contains(SubmoduleStatus::WD_UNINITIALIZED);
+
+        if recurse && initialized {
+            let nested = get_submodules(&open_submodule(repository, &path)?, true)?;
+            result.extend(nested.into_iter().map(|nested| Submodule {
+                path: path.join(&nested.path),
+                ..nested
+            }));
+        }
+
+        result.push(Submodule {
+            path,
+            initialized,
+            change: if initialized {
+                to_submodule_change(status)
+            } else {
+                SubmoduleChange::default()
+            },
+        });
+    }
+
+    result.sort();
+    Ok(result)
+}
+
+/// Get the full name of the checked out branch, failing when HEAD is detached.
+fn get_head_branch(repository: &Repository) -> Result<String> {
+    repository
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.name().map(String::from))
+        .ok_or(GitStrategyError::RepositoryError {
+            message: String::from("Cannot push the repository"),
+            reason: String::from("HEAD is detached"),
+        })
+}
+
+fn get_operation_in_progress(repository: &Repository) -> Option<GitOperation> {
+    match repository.state() {
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => Some(GitOperation::Merge),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some(GitOperation::Revert),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+            Some(GitOperation::CherryPick)
+        }
+        RepositoryState::Bisect => Some(GitOperation::Bisect),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailbox
+        | RepositoryState::ApplyMailboxOrRebase => Some(GitOperation::Rebase),
+    }
+}
+
+fn get_branch_tracking(repository: &Repository) -> Result<Vec<BranchTracking>> {
+    let branches = repository
+        .branches(Some(BranchType::Local))
+        .map_err(|error| repository_error("Could not list branches", error))?;
+
+    let mut result = Vec::new();
+    for branch in branches {
+        let (branch, _) =
+            branch.map_err(|error| repository_error("Could not list branches", error))?;
+
+        let mut tracking = BranchTracking {
+            branch: String::from_utf8_lossy(
+                branch
+                    .name_bytes()
+                    .map_err(|error| repository_error("Could not list branches", error))?,
+            )
+            .to_string(),
+            ..Default::default()
+        };
+
+        // Fails when there is no upstream, or when the upstream no longer exists.
+        if let Ok(upstream) = branch.upstream() {
+            tracking.upstream = upstream
+                .name_bytes()
+                .ok()
+                .map(|name| String::from_utf8_lossy(name).to_string());
+
+            if let (Some(local), Some(remote)) = (branch.get().target(), upstream.get().target()) {
+                let (ahead, behind) = repository
+                    .graph_ahead_behind(local, remote)
+                    .map_err(|error| repository_error("Could not compare branches", error))?;
+                tracking.ahead = ahead;
+                tracking.behind = behind;
+            }
+        }
+
+        result.push(tracking);
+    }
+
+    result.sort_by(|left, right| left.branch.cmp(&right.branch));
+    Ok(result)
+}
+
+/// Get the default branch, from `origin/HEAD` or else a local `main` or `master` branch.
+fn get_default_branch(repository: &Repository) -> Option<String> {
+    let origin_head = repository
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|head| {
+            head.symbolic_target()
+                .and_then(|target| target.strip_prefix("refs/remotes/origin/"))
+                .map(String::from)
+        });
+
+    origin_head.or_else(|| {
+        ["main", "master"]
+            .into_iter()
+            .find(|branch| repository.find_branch(branch, BranchType::Local).is_ok())
+            .map(String::from)
+    })
+}
+
+fn get_branches(repository: &Repository) -> Result<Vec<BranchInfo>> {
+    let default_branch = get_default_branch(repository);
+
+    // Compare against the remote branch, when the default branch is not checked out locally.
+    let target = default_branch.as_ref().and_then(|branch| {
+        repository
+            .refname_to_id(&format!("refs/heads/{}", branch))
+            .or_else(|_| repository.refname_to_id(&format!("refs/remotes/origin/{}", branch)))
+            .ok()
+    });
+
+    let branches = repository
+        .branches(Some(BranchType::Local))
+        .map_err(|error| repository_error("Could not list branches", error))?;
+
+    let mut result = Vec::new();
+    for branch in branches {
+        let (branch, _) =
+            branch.map_err(|error| repository_error("Could not list branches", error))?;
+
+        let name = String::from_utf8_lossy(
+            branch
+                .name_bytes()
+                .map_err(|error| repository_error("Could not list branches", error))?,
+        )
+        .to_string();
+
+        // The upstream is read from the configuration, so it is also known when it is gone.
+        let upstream = branch
+            .get()
+            .name()
+            .and_then(|refname| repository.branch_upstream_name(refname).ok())
+            .and_then(|upstream| upstream.as_str().map(String::from));
+        let upstream_gone = upstream
+            .as_ref()
+            .is_some_and(|upstream| repository.find_reference(upstream).is_err());
+
+        let commit = branch
+            .get()
+            .peel_to_commit()
+            .map_err(|error| repository_error("Could not read branch", error))?;
+
+        let is_default = default_branch.as_ref() == Some(&name);
+        let is_merged = !is_default
+            && target.is_some_and(|target| {
+                target == commit.id()
+                    || repository
+                        .graph_descendant_of(target, commit.id())
+                        .unwrap_or(false)
+            });
+
+        result.push(BranchInfo {
+            branch: name,
+            upstream: upstream.map(|upstream| {
+                upstream
+                    .strip_prefix("refs/remotes/")
+                    .unwrap_or(&upstream)
+                    .to_string()
+            }),
+            upstream_gone,
+            is_default,
+            is_current: branch.is_head(),
+            is_merged,
+            last_commit: UNIX_EPOCH + Duration::from_secs(commit.time().seconds().max(0) as u64),
+        });
+    }
+
+    result.sort_by(|left, right| left.branch.cmp(&right.branch));
+    Ok(result)
+}
+
+fn get_stashes(repository: &mut Repository) -> Result<Vec<Stash>> {
+    let mut stashes = Vec::new();
+    repository
+        .stash_foreach(|index, message, oid| {
+            stashes.push((index, message.to_string(), *oid));
+            true
+        })
+        .map_err(|error| repository_error("Could not list stashes", error))?;
+
+    stashes
+        .into_iter()
+        .map(|(index, message, oid)| {
+            let commit = repository
+                .find_commit(oid)
+                .map_err(|error| repository_error("Could not read stash", error))?;
+            Ok(Stash {
+                index,
+                message,
+                created: UNIX_EPOCH + Duration::from_secs(commit.time().seconds().max(0) as u64),
+            })
+        })
+        .collect()
+}
+
+fn get_short_id(repository: &Repository, oid: Oid) -> Result<String> {
+    repository
+        .find_object(oid, None)
+        .and_then(|object| object.short_id())
+        .map(|short_id| short_id.as_str().unwrap_or_default().to_string())
+        .map_err(|error| repository_error("Could not resolve the commit", error))
+}
+
+/// Create a merge commit, without touching the worktree.
+fn merge_upstream(repository: &Repository, local: Oid, upstream: &Branch) -> Result<Oid> {
+    let error = |error| repository_error("Cannot merge the upstream", error);
+
+    let local = repository.find_commit(local).map_err(error)?;
+    let upstream_commit = upstream.get().peel_to_commit().map_err(error)?;
+
+    let mut index = repository
+        .merge_commits(&local, &upstream_commit, None)
+        .map_err(error)?;
+    if index.has_conflicts() {
+        return Err(GitStrategyError::RepositoryError {
+            message: String::from("Cannot merge the upstream"),
+            reason: String::from("Merging results in conflicts"),
+        });
+    }
+
+    let tree = index
+        .write_tree_to(repository)
+        .and_then(|tree| repository.find_tree(tree))
+        .map_err(error)?;
+    let signature = repository.signature().map_err(error)?;
+    let upstream_name = upstream
+        .name_bytes()
+        .map(|name| String::from_utf8_lossy(name).to_string())
+        .map_err(error)?;
+
+    repository
+        .commit(
+            None,
+            &signature,
+            &signature,
+            &format!("Merge remote-tracking branch '{}'", upstream_name),
+            &tree,
+            &[&local, &upstream_commit],
+        )
+        .map_err(error)
+}
+
+/// Rebase local commits onto the upstream, without touching the worktree.
+fn rebase_onto_upstream(repository: &Repository, local: Oid, upstream: Oid) -> Result<Oid> {
+    let error = |error| repository_error("Cannot rebase onto the upstream", error);
+
+    let branch = repository.find_annotated_commit(local).map_err(error)?;
+    let onto = repository.find_annotated_commit(upstream).map_err(error)?;
+    let signature = repository.signature().map_err(error)?;
+
+    let mut options = RebaseOptions::new();
+    options.inmemory(true);
+    let mut rebase = repository
+        .rebase(Some(&branch), Some(&onto), None, Some(&mut options))
+        .map_err(error)?;
+
+    let mut head = upstream;
+    while let Some(operation) = rebase.next() {
+        let result = operation.and_then(|_| {
+            if rebase.inmemory_index()?.has_conflicts() {
+                return Err(git2::Error::new(
+                    ErrorCode::Conflict,
+                    ErrorClass::Rebase,
+                    "Rebasing results in conflicts",
+                ));
+            }
+            match rebase.commit(None, &signature, None) {
+                Ok(commit) => head = commit,
+                // The changes of this commit are already part of the upstream.
+                Err(error) if error.code() == ErrorCode::Applied => {}
+                Err(error) => return Err(error),
+            }
+            Ok(())
+        });
+
+        if let Err(result) = result {
+            rebase.abort().ok();
+            return Err(error(result));
+        }
+    }
+
+    rebase.finish(None).map_err(error)?;
+    Ok(head)
+}
+
+/// Strategy which works with git repositories in process, using libgit2.
+///
+/// Git does not have to be installed.
+/// Authentication supports `ssh_key`, `token_env` and `credential_helper`, but not `ssh_command`.
+pub struct NativeGitStrategy<'a, T: PathStrategy> {
+    config: &'a GrassConfig,
+    path_strategy: &'a T,
+}
+
+impl<'a, T: PathStrategy> NativeGitStrategy<'a, T> {
+    pub fn new(config: &'a GrassConfig, path_strategy: &'a T) -> Self {
+        Self {
+            config,
+            path_strategy,
+        }
+    }
+
+    /// Initialize and check out all submodules, and their submodules.
+    fn update_submodules(&self, repository: &Repository, category: &Category) -> Result<()> {
+        let submodules = repository
+            .submodules()
+            .map_err(|error| repository_error("Could not list submodules", error))?;
+
+        for mut submodule in submodules {
+            let mut update_options = SubmoduleUpdateOptions::new();
+            update_options.fetch(self.get_fetch_options(category)?);
+            submodule
+                .update(true, Some(&mut update_options))
+                .map_err(|error| remote_error("Could not clone submodule", error))?;
+
+            let submodule_repository = submodule
+                .open()
+                .map_err(|error| repository_error("Could not open submodule", error))?;
+            self.update_submodules(&submodule_repository, category)?;
+        }
+
+        Ok(())
+    }
+
+    /// Options which configure authentication, for operations which access a remote.
+    fn get_fetch_options(&self, category: &Category) -> Result<FetchOptions<'static>> {
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(self.get_remote_callbacks(category)?);
+        Ok(options)
+    }
+
+    /// Push a refspec to a remote, a rejected update is an error.
+    fn push_reference(
+        &self,
+        category: &Category,
+        repository: &Repository,
+        remote: &str,
+        refspec: String,
+    ) -> Result<()> {
+        let mut remote = repository
+            .find_remote(remote)
+            .map_err(|error| remote_error("Could not find the remote", error))?;
+
+        let mut callbacks = self.get_remote_callbacks(category)?;
+        callbacks.push_update_reference(|reference, status| match status {
+            Some(status) => Err(git2::Error::from_str(&format!(
+                "Updates to '{}' were rejected: {}",
+                reference, status
+            ))),
+            None => Ok(()),
+        });
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+
+        remote
+            .push(&[refspec], Some(&mut options))
+            .map_err(|error| remote_error("Could not push repository", error))
+    }
+
+    /// Callbacks which provide the credentials configured for the category.
+    fn get_remote_callbacks(&self, category: &Category) -> Result<RemoteCallbacks<'static>> {
+        let auth = self
+            .config
+            .get_by_category(category)
+            .map(|category| category.auth.clone())
+            .unwrap_or_default();
+
+        if auth.ssh_command.is_some() {
+            return Err(GitStrategyError::RemoteAuthenticationError {
+                message: String::from("Cannot use the configured ssh_command"),
+                reason: String::from(
+                    "The native git backend cannot run an SSH command, use ssh_key instead",
+                ),
+            });
+        }
+
+        let token = auth.token_env.as_deref().map(read_token).transpose()?;
+
+        let mut attempts = 0;
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username_from_url, allowed| {
+            // Rejected credentials are requested again, so stop at some point.
+            attempts += 1;
+            if attempts > 3 {
+                return Err(authentication_error("The credentials were rejected"));
+            }
+
+            let username = username_from_url.unwrap_or("git");
+
+            if allowed.contains(CredentialType::USERNAME) {
+                return Cred::username(username);
+            }
+
+            if allowed.contains(CredentialType::SSH_KEY) {
+                return match &auth.ssh_key {
+                    Some(ssh_key) => Cred::ssh_key(username, None, ssh_key, None),
+                    None => Cred::ssh_key_from_agent(username),
+                };
+            }
+
+            if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(token) = &token {
+                    return Cred::userpass_plaintext(
+                        auth.token_username.as_deref().unwrap_or("git"),
+                        token,
+                    );
+                }
+                if let Some(credential_helper) = &auth.credential_helper {
+                    return run_credential_helper(credential_helper, url, username_from_url);
+                }
+                return git2::Config::open_default()
+                    .and_then(|config| Cred::credential_helper(&config, url, username_from_url));
+            }
+
+            if allowed.contains(CredentialType::DEFAULT) {
+                return Cred::default();
+            }
+
+            Err(authentication_error(
+                "The remote requires an unsupported authentication method",
+            ))
+        });
+
+        Ok(callbacks)
+    }
+}
+
+impl<'a, T: PathStrategy> GitStrategy for NativeGitStrategy<'a, T> {
+    fn clean<U>(&self, repository: U) -> Result<CleanReport>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let patterns = get_configured_clean_patterns(self.config, &repository.category)?;
+        let git_repository = Repository::open(&repository_path)
+            .map_err(|error| repository_error("Could not open the repository", error))?;
+        let files = get_ignored_files(&git_repository)?;
+
+        remove_ignored_files(
+            &repository_path,
+            files.iter().map(String::as_str),
+            patterns.as_deref(),
+        )
+    }
+
+    fn get_size<U>(&self, repository: U) -> Result<RepositorySize>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        let patterns = get_configured_clean_patterns(self.config, &repository.category)?;
+        let files = match repository_path.join(".git").exists() {
+            true => get_ignored_files(&open_repository(
+                &repository_path,
+                "Could not open the repository",
+            )?)?,
+            false => Vec::new(),
+        };
+
+        Ok(get_repository_size(
+            &repository_path,
+            files.iter().map(String::as_str),
+            patterns.as_deref(),
+        ))
+    }
+
+    fn clone<U, V>(&self, repository: U, remote: V, options: &CloneOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+        V: AsRef<str>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+
+        if options.filter.is_some() {
+            return Err(GitStrategyError::RemoteFetchError {
+                message: String::from("Cannot do a partial clone"),
+                reason: String::from("The native git backend does not support object filters"),
+            });
+        }
+
+        fs::create_dir_all(&repository_path).map_err(|error| {
+            GitStrategyError::FileSystemError {
+                message: String::from("Cannot create directory to clone into"),
+                reason: error.to_string(),
+                reasons: vec![],
+            }
+        })?;
+
+        let mut fetch_options = self.get_fetch_options(&repository.category)?;
+        if let Some(depth) = options.depth {
+            fetch_options.depth(i32::try_from(depth).unwrap_or(i32::MAX));
+        }
+
+        let mut builder = RepoBuilder::new();
+        builder.fetch_options(fetch_options);
+        if let Some(branch) = &options.branch {
+            builder.branch(branch);
+        }
+
+        let git_repository = builder
+            .clone(remote.as_ref(), &repository_path)
+            .map_err(|error| remote_error("Could not clone repository", error))?;
+
+        if options.recurse_submodules {
+            self.update_submodules(&git_repository, &repository.category)?;
+        }
+
+        Ok(())
+    }
+
+    fn init<U>(&self, repository: U, options: &InitOptions) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+        let settings = get_init_settings(self.config, &repository_path, &repository, options)?;
+
+        let mut init_options = RepositoryInitOptions::new();
+        init_options.no_reinit(true);
+        if let Some(branch) = &settings.branch {
+            init_options.initial_head(branch);
+        }
+        let git_repository = Repository::init_opts(&repository_path, &init_options)
+            .map_err(|error| repository_error("Could not initialize the repository", error))?;
+
+        if let Some(template) = &settings.template {
+            apply_template(template, &repository_path)?;
+        }
+
+        if let Some(remote) = &settings.remote {
+            git_repository
+                .remote("origin", remote)
+                .map_err(|error| repository_error("Could not add the remote", error))?;
+        }
+
+        Ok(())
+    }
+
+    fn get_changes<U>(&self, repository: U) -> Result<RepositoryChangeStatus>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+
+        if !repository_path.join(".git").exists() {
+            return Ok(RepositoryChangeStatus::NoRepository);
+        }
+
+        let mut repository = open_repository(&repository_path, "Could not open the repository")?;
+        let recurse = self.config.recurse_submodules;
+        let changes = get_file_changes(&repository, recurse)?;
+
+        let num_conflicts = changes
+            .iter()
+            .filter(|change| change.staged == FileChangeKind::Unmerged)
+            .count();
+
+        if let Some(operation) = get_operation_in_progress(&repository) {
+            return Ok(RepositoryChangeStatus::OperationInProgress {
+                operation,
+                num_conflicts,
+            });
+        }
+
+        if num_conflicts > 0 {
+            return Ok(RepositoryChangeStatus::Conflicts { num_conflicts });
+        }
+
+        if changes.iter().any(|change| change.submodule.is_none()) {
+            return Ok(RepositoryChangeStatus::UncommittedChanges {
+                num_changes: changes.len(),
+            });
+        }
+
+        if let Some(change_status) =
+            get_submodule_change_status(&get_submodules(&repository, recurse)?)
+        {
+            return Ok(change_status);
+        }
+
+        if let Some(lfs_status) = get_lfs_status(&repository)? {
+            if !lfs_status.missing_objects.is_empty() {
+                return Ok(RepositoryChangeStatus::MissingLfsObjects {
+                    num_files: lfs_status.missing_objects.len(),
+                });
+            }
+        }
+
+        if let Some(max_age) = self.config.get_stale_stash_age() {
+            let num_stashes = count_stale_stashes(&get_stashes(&mut repository)?, max_age);
+            if num_stashes > 0 {
+                return Ok(RepositoryChangeStatus::StaleStashes { num_stashes });
+            }
+        }
+
+        let branches: Vec<_> = get_branch_tracking(&repository)?
+            .into_iter()
+            .filter(BranchTracking::has_unpushed_work)
+            .collect();
+
+        if !branches.is_empty() {
+            return Ok(RepositoryChangeStatus::UnpushedWork { branches });
+        }
+
+        Ok(RepositoryChangeStatus::UpToDate)
+    }
+
+    fn get_change_details<U>(&self, repository: U) -> Result<Vec<FileChange>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(
+            &repository_path,
+            "Cannot list the changes of the repository",
+        )?;
+
+        get_file_changes(&repository, self.config.recurse_submodules)
+    }
+
+    fn get_lfs_status<U>(&self, repository: U) -> Result<Option<LfsStatus>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(
+            &repository_path,
+            "Cannot get the LFS status of the repository",
+        )?;
+
+        get_lfs_status(&repository)
+    }
+
+    fn list_submodules<U>(&self, repository: U) -> Result<Vec<Submodule>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(
+            &repository_path,
+            "Cannot list the submodules of the repository",
+        )?;
+
+        get_submodules(&repository, self.config.recurse_submodules)
+    }
+
+    fn get_metadata<U>(&self, repository: U) -> Result<RepositoryMetadata>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let message = "Cannot get the metadata of the repository";
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, message)?;
+
+        let head = match repository.head() {
+            Ok(head) => head,
+            Err(error) if error.code() == ErrorCode::UnbornBranch => {
+                let head = repository
+                    .find_reference("HEAD")
+                    .map_err(|error| repository_error(message, error))?;
+                let branch = head
+                    .symbolic_target()
+                    .map(|target| target.strip_prefix("refs/heads/").unwrap_or(target))
+                    .unwrap_or("HEAD");
+                return Ok(RepositoryMetadata {
+                    head: HeadState::Unborn(String::from(branch)),
+                    last_commit: None,
+                });
+            }
+            Err(error) => return Err(repository_error(message, error)),
+        };
+
+        let commit = head
+            .peel_to_commit()
+            .map_err(|error| repository_error(message, error))?;
+        let id = commit
+            .as_object()
+            .short_id()
+            .map_err(|error| repository_error(message, error))?
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let head = if repository.head_detached().unwrap_or(false) {
+            HeadState::Detached(id.clone())
+        } else {
+            HeadState::Branch(head.shorthand().unwrap_or("HEAD").to_string())
+        };
+
+        Ok(RepositoryMetadata {
+            head,
+            last_commit: Some(CommitSummary {
+                id,
+                subject: commit.summary().unwrap_or_default().to_string(),
+                time: UNIX_EPOCH + Duration::from_secs(commit.time().seconds().max(0) as u64),
+            }),
+        })
+    }
+
+    fn get_branch_tracking<U>(&self, repository: U) -> Result<Vec<BranchTracking>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = Repository::open(&repository_path)
+            .map_err(|error| repository_error("Could not open the repository", error))?;
+
+        get_branch_tracking(&repository)
+    }
+
+    fn fetch<U>(&self, repository: U) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+        let git_repository = open_repository(&repository_path, "Cannot fetch the repository")?;
+
+        // Same as git, use the remote of the current branch, or 'origin'.
+        let remote_name = git_repository
+            .head()
+            .ok()
+            .and_then(|head| head.name().map(String::from))
+            .and_then(|head| git_repository.branch_upstream_remote(&head).ok())
+            .and_then(|remote| remote.as_str().map(String::from))
+            .unwrap_or_else(|| String::from("origin"));
+
+        let mut remote = git_repository
+            .find_remote(&remote_name)
+            .map_err(|error| remote_error("Could not find the remote", error))?;
+
+        remote
+            .fetch(
+                &[] as &[&str],
+                Some(&mut self.get_fetch_options(&repository.category)?),
+                None,
+            )
+            .map_err(|error| remote_error("Could not fetch repository", error))
+    }
+
+    fn pull<U>(&self, repository: U, mode: PullMode) -> Result<PullResult>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        self.fetch(repository.clone())?;
+
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot pull the repository")?;
+
+        // A detached head, or a branch without upstream, cannot be pulled.
+        let branch = match repository.head() {
+            Ok(head) if head.is_branch() => Branch::wrap(head),
+            _ => return Ok(PullResult::NoUpstream),
+        };
+        let upstream = match branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(_) => return Ok(PullResult::NoUpstream),
+        };
+
+        let missing_target = || GitStrategyError::RepositoryError {
+            message: String::from("Cannot pull the repository"),
+            reason: String::from("The branch does not point to a commit"),
+        };
+        let local = branch.get().target().ok_or_else(missing_target)?;
+        let remote = upstream.get().target().ok_or_else(missing_target)?;
+
+        let (ahead, behind) = repository
+            .graph_ahead_behind(local, remote)
+            .map_err(|error| repository_error("Could not count the commits to pull", error))?;
+
+        if behind == 0 {
+            return Ok(PullResult::UpToDate);
+        }
+
+        let target = match mode {
+            _ if ahead == 0 => remote,
+            PullMode::FastForwardOnly => {
+                return Err(GitStrategyError::RepositoryError {
+                    message: String::from("Cannot fast-forward the current branch"),
+                    reason: String::from("Not possible to fast-forward, the branch has diverged"),
+                })
+            }
+            PullMode::Merge => merge_upstream(&repository, local, &upstream)?,
+            PullMode::Rebase => rebase_onto_upstream(&repository, local, remote)?,
+        };
+
+        // Update the worktree first, so the branch is unchanged when this is not possible.
+        let checkout_error = |error| repository_error("Cannot update the worktree", error);
+        let target_commit = repository.find_commit(target).map_err(checkout_error)?;
+        repository
+            .checkout_tree(
+                target_commit.as_object(),
+                Some(CheckoutBuilder::new().safe()),
+            )
+            .map_err(checkout_error)?;
+
+        let reference_name = branch
+            .get()
+            .name()
+            .map(String::from)
+            .ok_or_else(missing_target)?;
+        repository
+            .find_reference(&reference_name)
+            .and_then(|mut reference| reference.set_target(target, "pull"))
+            .map_err(|error| repository_error("Cannot update the current branch", error))?;
+
+        Ok(PullResult::Updated {
+            from: get_short_id(&repository, local)?,
+            to: get_short_id(&repository, target)?,
+            num_commits: behind,
+        })
+    }
+
+    fn commit_all<U>(&self, repository: U, message: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot commit the changes")?;
+
+        if let Some(operation) = get_operation_in_progress(&repository) {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot commit the changes"),
+                reason: format!("{} in progress", operation),
+            });
+        }
+
+        if get_file_changes(&repository, false)?.is_empty() {
+            return Err(GitStrategyError::RepositoryError {
+                message: String::from("Cannot commit the changes"),
+                reason: String::from("There is nothing to commit"),
+            });
+        }
+
+        let mut index = repository
+            .index()
+            .map_err(|error| repository_error("Could not read the index", error))?;
+        index
+            .add_all(["*"], IndexAddOption::DEFAULT, None)
+            .and_then(|_| index.update_all(["*"], None))
+            .and_then(|_| index.write())
+            .map_err(|error| repository_error("Could not stage the changes", error))?;
+
+        let tree = index
+            .write_tree()
+            .and_then(|tree| repository.find_tree(tree))
+            .map_err(|error| repository_error("Could not stage the changes", error))?;
+        let signature = repository
+            .signature()
+            .map_err(|error| repository_error("Could not commit the changes", error))?;
+
+        // The first commit of a repository has no parent.
+        let parent = repository
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .map_err(|error| repository_error("Could not commit the changes", error))?;
+
+        Ok(())
+    }
+
+    fn push<U>(&self, repository: U) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+        let git_repository = open_repository(&repository_path, "Cannot push the repository")?;
+        let head = get_head_branch(&git_repository)?;
+
+        // Same as git, push to the upstream, or else to a branch with the same name on 'origin'.
+        let upstream = git_repository
+            .branch_upstream_remote(&head)
+            .ok()
+            .and_then(|remote| remote.as_str().map(String::from))
+            .zip(
+                git_repository
+                    .branch_upstream_merge(&head)
+                    .ok()
+                    .and_then(|merge| merge.as_str().map(String::from)),
+            );
+
+        match upstream {
+            Some((remote, target)) => self.push_reference(
+                &repository.category,
+                &git_repository,
+                &remote,
+                format!("{}:{}", head, target),
+            ),
+            None => self.push_to_remote(repository, "origin"),
+        }
+    }
+
+    fn push_to_remote<U>(&self, repository: U, remote: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository: RepositoryLocation = repository.into();
+        let repository_path = self.path_strategy.get_directory(repository.clone())?;
+        let git_repository = open_repository(&repository_path, "Cannot push the repository")?;
+        let head = get_head_branch(&git_repository)?;
+        let branch = head.trim_start_matches("refs/heads/").to_string();
+
+        self.push_reference(
+            &repository.category,
+            &git_repository,
+            remote,
+            format!("{}:{}", head, head),
+        )?;
+
+        git_repository
+            .find_branch(&branch, BranchType::Local)
+            .and_then(|mut local| local.set_upstream(Some(&format!("{}/{}", remote, branch))))
+            .map_err(|error| repository_error("Could not set the upstream", error))
+    }
+
+    fn add_remote<U>(&self, repository: U, name: &str, url: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot add the remote")?;
+
+        repository
+            .remote(name, url)
+            .map(|_| ())
+            .map_err(|error| repository_error("Cannot add the remote", error))
+    }
+
+    fn rename_remote<U>(&self, repository: U, from: &str, to: &str) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot rename the remote")?;
+
+        // Refspecs which could not be updated are returned, the default refspec never is.
+        repository
+            .remote_rename(from, to)
+            .map(|_| ())
+            .map_err(|error| repository_error("Cannot rename the remote", error))
+    }
+
+    fn list_worktrees<U>(&self, repository: U) -> Result<Vec<Worktree>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot list worktrees")?;
+
+        Ok(get_worktrees(&repository)?
+            .into_iter()
+            .map(|(_, worktree)| worktree)
+            .collect())
+    }
+
+    fn add_worktree<U>(&self, worktree: U) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        let worktree: WorktreeLocation = worktree.into();
+        let repository_path = self
+            .path_strategy
+            .get_directory(worktree.repository.clone())?;
+        let worktree_path = self
+            .path_strategy
+            .get_worktree_directory(worktree.clone())?;
+        let branch = worktree.branch.as_str();
+
+        let repository = open_repository(&repository_path, "Cannot add a worktree")?;
+
+        if worktree_path.exists() {
+            return Err(GitStrategyError::RepositryExists {
+                message: String::from("Cannot add a worktree"),
+                reason: format!("The directory '{}' already exists", worktree_path.display()),
+            });
+        }
+
+        let local_branch = match repository.find_branch(branch, BranchType::Local) {
+            Ok(local_branch) => local_branch,
+            Err(_) => {
+                let remote_branches: Vec<_> = repository
+                    .branches(Some(BranchType::Remote))
+                    .map_err(|error| repository_error("Could not list remote branches", error))?
+                    .flatten()
+                    .filter(|(remote_branch, _)| {
+                        remote_branch
+                            .name()
+                            .ok()
+                            .flatten()
+                            .and_then(|name| name.split_once('/'))
+                            .is_some_and(|(_, name)| name == branch)
+                    })
+                    .collect();
+
+                let (target, upstream) = match remote_branches.as_slice() {
+                    [(remote_branch, _)] => (
+                        remote_branch.get().peel_to_commit(),
+                        remote_branch.name().ok().flatten().map(String::from),
+                    ),
+                    _ => (
+                        repository.head().and_then(|head| head.peel_to_commit()),
+                        None,
+                    ),
+                };
+                let target = target
+                    .map_err(|error| repository_error("Could not resolve the commit", error))?;
+
+                let mut local_branch = repository
+                    .branch(branch, &target, false)
+                    .map_err(|error| repository_error("Could not create the branch", error))?;
+                if let Some(upstream) = upstream {
+                    local_branch
+                        .set_upstream(Some(&upstream))
+                        .map_err(|error| repository_error("Could not set the upstream", error))?;
+                }
+                local_branch
+            }
+        };
+
+        if let Some(parent) = worktree_path.parent() {
+            fs::create_dir_all(parent).map_err(|error| GitStrategyError::FileSystemError {
+                message: String::from("Cannot create directory for the worktree"),
+                reason: error.to_string(),
+                reasons: vec![],
+            })?;
+        }
+
+        // Git names worktrees after the directory, adding a number when the name is taken.
+        let names: Vec<_> = get_worktrees(&repository)?
+            .into_iter()
+            .filter_map(|(name, _)| name)
+            .collect();
+        let base_name = branch.replace('/', "-");
+        let name = (0..)
+            .map(|index| match index {
+                0 => base_name.clone(),
+                index => format!("{}{}", base_name, index),
+            })
+            .find(|name| !names.contains(name))
+            .unwrap_or(base_name);
+
+        let reference = local_branch.into_reference();
+        repository
+            .worktree(
+                &name,
+                &worktree_path,
+                Some(WorktreeAddOptions::new().reference(Some(&reference))),
+            )
+            .map_err(|error| repository_error("Could not add the worktree", error))?;
+
+        Ok(())
+    }
+
+    fn remove_worktree<U>(&self, worktree: U, force: bool) -> Result<()>
+    where
+        U: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot remove the worktree")?;
+
+        let worktrees = get_worktrees(&repository)?;
+        let worktree = find_worktree(
+            &worktrees
+                .iter()
+                .map(|(_, worktree)| worktree.clone())
+                .collect::<Vec<_>>(),
+            &branch,
+        )?
+        .clone();
+        let name = worktrees
+            .into_iter()
+            .find_map(|(name, candidate)| (candidate == worktree).then_some(name))
+            .flatten()
+            .ok_or(GitStrategyError::UnknownError {
+                message: String::from("Cannot remove the worktree"),
+                reason: String::from("The worktree has no name"),
+            })?;
+
+        let git_worktree = repository
+            .find_worktree(&name)
+            .map_err(|error| repository_error("Could not open the worktree", error))?;
+
+        if !force && worktree.path.exists() {
+            let worktree_repository = Repository::open_from_worktree(&git_worktree)
+                .map_err(|error| repository_error("Could not open the worktree", error))?;
+            let mut options = StatusOptions::new();
+            options.include_untracked(true).include_ignored(false);
+            let is_dirty = !worktree_repository
+                .statuses(Some(&mut options))
+                .map_err(|error| repository_error("Could not get the worktree status", error))?
+                .is_empty();
+
+            if is_dirty {
+                return Err(GitStrategyError::RepositoryError {
+                    message: String::from("Could not remove the worktree"),
+                    reason: format!(
+                        "'{}' contains modified or untracked files, use force to delete it",
+                        worktree.path.display()
+                    ),
+                });
+            }
+        }
+
+        git_worktree
+            .prune(Some(
+                WorktreePruneOptions::new().valid(true).working_tree(true),
+            ))
+            .map_err(|error| repository_error("Could not remove the worktree", error))?;
+
+        Ok(())
+    }
+
+    fn list_branches<U>(&self, repository: U) -> Result<Vec<BranchInfo>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot list branches")?;
+
+        get_branches(&repository)
+    }
+
+    fn delete_branch<U>(&self, repository: U, branch: &str, force: bool) -> Result<()>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let repository = open_repository(&repository_path, "Cannot delete the branch")?;
+
+        check_branch_deletable(&get_branches(&repository)?, branch, force)?;
+
+        repository
+            .find_branch(branch, BranchType::Local)
+            .and_then(|mut branch| branch.delete())
+            .map_err(|error| repository_error("Could not delete the branch", error))
+    }
+
+    fn list_stashes<U>(&self, repository: U) -> Result<Vec<Stash>>
+    where
+        U: Into<RepositoryLocation>,
+    {
+        let repository_path = self.path_strategy.get_directory(repository)?;
+        let mut repository = open_repository(&repository_path, "Cannot list stashes")?;
+
+        get_stashes(&mut repository)
+    }
+}
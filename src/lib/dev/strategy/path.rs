@@ -5,7 +5,10 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
-use crate::{dev::RepositoryLocation, support_strategy};
+use crate::{
+    dev::{RepositoryLocation, WorktreeLocation},
+    support_strategy,
+};
 
 pub use local::LocalPathStrategy;
 pub use mock::MockPathStrategy;
@@ -24,6 +27,9 @@ pub enum PathStrategyError {
 
 pub type Result<T> = std::result::Result<T, PathStrategyError>;
 
+/// Name of the directory containing worktrees, it is never a repository.
+pub const WORKTREES_DIRECTORY: &str = ".worktrees";
+
 pub trait PathStrategy {
     /// Get the containing directory for a repository
     ///
@@ -94,6 +100,38 @@ pub trait PathStrategy {
     fn get_directory<T>(&self, repository: T) -> Result<PathBuf>
     where
         T: Into<RepositoryLocation>;
+
+    /// Get the directory for a worktree of a repository
+    ///
+    /// Like [PathStrategy::get_directory], this will also work if the path doesn't exist.
+    /// Worktrees are kept in a [WORKTREES_DIRECTORY] outside of the category directory,
+    /// or inside it for categories with their own directory,
+    /// so they are not mistaken for repositories.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use grass::dev::strategy::path::{MockPathStrategy, PathStrategy, PathStrategyError};
+    /// use std::path::PathBuf;
+    /// let strategy = MockPathStrategy;
+    /// fn test_strategy<T: PathStrategy>(strategy: &T) {
+    ///     assert_eq!(
+    ///         strategy.get_worktree_directory(("all_good", "first", "feature/login")),
+    ///         Ok(PathBuf::from(
+    ///             "/home/example/worktrees/all_good/first/feature/login"
+    ///         ))
+    ///     );
+    ///
+    ///     assert!(matches!(
+    ///         strategy.get_worktree_directory(("all_good", "does_not_exist", "main")),
+    ///         Err(PathStrategyError::RepositoryNotFound { .. })
+    ///     ));
+    /// }
+    /// test_strategy(&strategy);
+    /// ```
+    fn get_worktree_directory<T>(&self, worktree: T) -> Result<PathBuf>
+    where
+        T: Into<WorktreeLocation>;
 }
 
 support_strategy!(SupportsPath, get_path_strategy, PathStrategy);
@@ -1,8 +1,10 @@
 use std::path::PathBuf;
 
-use crate::dev::{config::GrassConfig, strategy::path::PathStrategyError, RepositoryLocation};
+use crate::dev::{
+    config::GrassConfig, strategy::path::PathStrategyError, RepositoryLocation, WorktreeLocation,
+};
 
-use super::PathStrategy;
+use super::{PathStrategy, WORKTREES_DIRECTORY};
 
 pub struct LocalPathStrategy<'a> {
     config: &'a GrassConfig,
@@ -14,15 +16,35 @@ impl<'a> LocalPathStrategy<'a> {
     }
 }
 
+/// Split a repository name like `team/repo` into its directories.
+fn split_repository_name(repository: &str) -> super::Result<Vec<&str>> {
+    let components: Vec<_> = repository.split('/').collect();
+
+    if components
+        .iter()
+        .any(|component| component.is_empty() || *component == "." || *component == "..")
+    {
+        return Err(PathStrategyError::RepositoryNotFound {
+            context: format!("When resolving the repository name '{}'.", repository),
+            reason: "Repository names cannot contain empty, '.' or '..' directories.".into(),
+        });
+    }
+
+    Ok(components)
+}
+
 impl<'a> PathStrategy for LocalPathStrategy<'a> {
     fn get_containing_directory<T>(&self, repository: T) -> super::Result<PathBuf>
     where
         T: Into<RepositoryLocation>,
     {
-        let RepositoryLocation { category, .. } = repository.into();
+        let RepositoryLocation {
+            category,
+            repository,
+        } = repository.into();
 
-        let result = match self.config.get_by_category(category) {
-            Some(category) => self.config.base_dir.join(&category.name),
+        let result = match self.config.get_by_category_or_discovered(category) {
+            Some(category) => category.get_directory(&self.config.base_dir),
             None => {
                 return Err(PathStrategyError::RepositoryNotFound {
                     context: "When getting the category from configuration.".into(),
@@ -30,7 +52,14 @@ impl<'a> PathStrategy for LocalPathStrategy<'a> {
                 })
             }
         };
-        Ok(result)
+
+        // Nested repositories are contained in the directories before their name.
+        let components = split_repository_name(&repository)?;
+        Ok(result.join(
+            components[..components.len() - 1]
+                .iter()
+                .collect::<PathBuf>(),
+        ))
     }
 
     fn get_directory<T>(&self, repository: T) -> super::Result<PathBuf>
@@ -38,10 +67,43 @@ impl<'a> PathStrategy for LocalPathStrategy<'a> {
         T: Into<RepositoryLocation>,
     {
         let repository: RepositoryLocation = repository.into();
-        let category_directory = self.get_containing_directory(repository.clone())?;
+        let containing_directory = self.get_containing_directory(repository.clone())?;
 
         let RepositoryLocation { repository, .. } = repository;
+        let components = split_repository_name(&repository)?;
+
+        Ok(containing_directory.join(components[components.len() - 1]))
+    }
+
+    fn get_worktree_directory<T>(&self, worktree: T) -> super::Result<PathBuf>
+    where
+        T: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        // Makes sure the category exists.
+        self.get_containing_directory(repository.clone())?;
+
+        let RepositoryLocation {
+            category,
+            repository,
+        } = repository;
+
+        let repository = split_repository_name(&repository)?
+            .iter()
+            .collect::<PathBuf>();
+
+        // Worktrees stay next to the repositories of a category with its own directory.
+        let worktrees_directory = match self.config.get_by_category(&category) {
+            Some(category) if category.path.is_some() => category
+                .get_directory(&self.config.base_dir)
+                .join(WORKTREES_DIRECTORY),
+            _ => self
+                .config
+                .base_dir
+                .join(WORKTREES_DIRECTORY)
+                .join(category.0),
+        };
 
-        Ok(category_directory.join(repository))
+        Ok(worktrees_directory.join(repository).join(branch))
     }
 }
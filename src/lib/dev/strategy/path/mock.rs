@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::dev::RepositoryLocation;
+use crate::dev::{RepositoryLocation, WorktreeLocation};
 
 use super::{PathStrategy, PathStrategyError};
 
@@ -49,6 +49,12 @@ impl PathStrategy for MockPathStrategy {
             ("with_changes", "third") => Ok(PathBuf::from(
                 "/home/example/repositories/with_changes/third",
             )),
+            ("with_changes", "fourth") => Ok(PathBuf::from(
+                "/home/example/repositories/with_changes/fourth",
+            )),
+            ("with_changes", "fifth") => Ok(PathBuf::from(
+                "/home/example/repositories/with_changes/fifth",
+            )),
             ("with_error", "first") => {
                 Ok(PathBuf::from("/home/example/repositories/with_error/first"))
             }
@@ -68,4 +74,17 @@ impl PathStrategy for MockPathStrategy {
             }),
         }
     }
+
+    fn get_worktree_directory<T>(&self, worktree: T) -> super::Result<PathBuf>
+    where
+        T: Into<WorktreeLocation>,
+    {
+        let WorktreeLocation { repository, branch } = worktree.into();
+        self.get_directory(repository.clone())?;
+
+        Ok(PathBuf::from("/home/example/worktrees")
+            .join(repository.category.as_ref() as &str)
+            .join(repository.repository)
+            .join(branch))
+    }
 }
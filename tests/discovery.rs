@@ -0,0 +1,382 @@
+//! Discovery of repositories in the file system, using the local strategies.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use grass::dev::{
+    config::{GrassCategory, GrassConfig, GrassDiscoveryFilter},
+    strategy::{
+        discovery::{
+            DiscoveryExists, DiscoveryStrategy, DiscoveryStrategyError, LocalDiscoveryStrategy,
+        },
+        path::{LocalPathStrategy, PathStrategy, PathStrategyError},
+    },
+    RepositoryLocation,
+};
+
+/// Configure the categories `flat` and `nested`, where `nested` is searched 2 levels deep.
+///
+/// The category `filtered` applies a discovery filter,
+/// and `secure` has its own directory outside of the base directory.
+fn get_config(root: &Path) -> GrassConfig {
+    let flat = GrassCategory {
+        name: String::from("flat"),
+        ..Default::default()
+    };
+    let nested = GrassCategory {
+        name: String::from("nested"),
+        discovery_depth: Some(2),
+        ..Default::default()
+    };
+    let filtered = GrassCategory {
+        name: String::from("filtered"),
+        discovery_depth: Some(2),
+        discovery_filter: GrassDiscoveryFilter {
+            require_git: true,
+            include: vec![String::from("team/*"), String::from("tool*")],
+            exclude: vec![String::from("*/archive"), String::from("tmp")],
+            ignore_hidden: true,
+        },
+        ..Default::default()
+    };
+
+    let secure = GrassCategory {
+        name: String::from("secure"),
+        path: Some(root.join("volume/src")),
+        ..Default::default()
+    };
+
+    GrassConfig {
+        category: HashMap::from([
+            (String::from("flat"), Arc::new(RwLock::new(flat))),
+            (String::from("nested"), Arc::new(RwLock::new(nested))),
+            (String::from("filtered"), Arc::new(RwLock::new(filtered))),
+            (String::from("secure"), Arc::new(RwLock::new(secure))),
+        ]),
+        base_dir: root.join("repos"),
+        ..GrassConfig::try_default().unwrap()
+    }
+}
+
+fn create_repository(root: &Path, path: &str) {
+    fs::create_dir_all(root.join("repos").join(path).join(".git")).unwrap();
+}
+
+fn list_repositories<T: DiscoveryStrategy>(strategy: &T, category: &str) -> Vec<String> {
+    let mut repositories: Vec<_> = strategy
+        .list_repositories_in_category(category)
+        .unwrap()
+        .filter_map(|repository| repository.ok())
+        .map(|repository| repository.repository)
+        .collect();
+    repositories.sort();
+    repositories
+}
+
+#[test]
+fn nested_repositories() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "flat/app");
+    create_repository(root, "flat/team/api");
+    create_repository(root, "nested/tool");
+    create_repository(root, "nested/team/api");
+    create_repository(root, "nested/team/web");
+    create_repository(root, "nested/team/web/vendor");
+    fs::create_dir_all(root.join("repos/nested/other/scratch")).unwrap();
+    fs::write(root.join("repos/nested/notes.txt"), "notes").unwrap();
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert_eq!(list_repositories(&strategy, "flat"), vec!["app", "team"]);
+    // Search stops at '.git', and every directory on the last level is a repository.
+    assert_eq!(
+        list_repositories(&strategy, "nested"),
+        vec!["other/scratch", "team/api", "team/web", "tool"]
+    );
+}
+
+#[test]
+fn nested_repository_paths() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "nested/team/api");
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert_eq!(
+        path_strategy.get_directory(("nested", "team/api")),
+        Ok(root.join("repos/nested/team/api"))
+    );
+    assert_eq!(
+        path_strategy.get_containing_directory(("nested", "team/api")),
+        Ok(root.join("repos/nested/team"))
+    );
+    assert_eq!(
+        path_strategy.get_worktree_directory(("nested", "team/api", "main")),
+        Ok(root.join("repos/.worktrees/nested/team/api/main"))
+    );
+    assert!(matches!(
+        path_strategy.get_directory(("nested", "../flat/app")),
+        Err(PathStrategyError::RepositoryNotFound { .. })
+    ));
+
+    strategy
+        .move_repository(
+            RepositoryLocation::new("nested", "team/api"),
+            RepositoryLocation::new("nested", "platform/api"),
+        )
+        .unwrap();
+    assert!(root.join("repos/nested/platform/api/.git").is_dir());
+    assert_eq!(list_repositories(&strategy, "nested"), vec!["platform/api"]);
+
+    strategy
+        .create_repository(RepositoryLocation::new("nested", "team/web"))
+        .unwrap();
+    assert!(root.join("repos/nested/team/web").is_dir());
+}
+
+#[test]
+fn filtered_repositories() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "filtered/tool");
+    create_repository(root, "filtered/team/api");
+    create_repository(root, "filtered/team/archive");
+    create_repository(root, "filtered/tmp/scratch");
+    create_repository(root, "filtered/.cache/data");
+    create_repository(root, "filtered/website");
+    fs::create_dir_all(root.join("repos/filtered/team/deleted")).unwrap();
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert_eq!(
+        list_repositories(&strategy, "filtered"),
+        vec!["team/api", "tool"]
+    );
+
+    let mut ignored: Vec<_> = strategy
+        .list_repositories_in_category("filtered")
+        .unwrap()
+        .filter_map(|repository| match repository {
+            Err(DiscoveryStrategyError::RepositoryIgnored { repository, reason }) => {
+                Some((repository.repository, reason.to_string()))
+            }
+            _ => None,
+        })
+        .collect();
+    ignored.sort();
+
+    assert_eq!(
+        ignored,
+        vec![
+            (
+                String::from(".cache"),
+                String::from("Hidden directories are ignored")
+            ),
+            (
+                String::from("team/archive"),
+                String::from("Matches the exclude pattern '*/archive'")
+            ),
+            (
+                String::from("team/deleted"),
+                String::from("The directory does not contain '.git'")
+            ),
+            (
+                String::from("tmp"),
+                String::from("Matches the exclude pattern 'tmp'")
+            ),
+            (
+                String::from("website"),
+                String::from("Does not match any include pattern")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn category_path() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "flat/app");
+    fs::create_dir_all(root.join("volume/src/api/.git")).unwrap();
+    fs::create_dir_all(root.join("volume/src/.worktrees/api/main")).unwrap();
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert_eq!(list_repositories(&strategy, "secure"), vec!["api"]);
+    assert_eq!(
+        path_strategy.get_directory(("secure", "api")),
+        Ok(root.join("volume/src/api"))
+    );
+    // Worktrees are kept on the same volume as the repositories.
+    assert_eq!(
+        path_strategy.get_worktree_directory(("secure", "api", "main")),
+        Ok(root.join("volume/src/.worktrees/api/main"))
+    );
+    assert_eq!(
+        path_strategy.get_worktree_directory(("flat", "app", "main")),
+        Ok(root.join("repos/.worktrees/flat/app/main"))
+    );
+
+    strategy
+        .move_repository(
+            RepositoryLocation::new("flat", "app"),
+            RepositoryLocation::new("secure", "app"),
+        )
+        .unwrap();
+    assert!(root.join("volume/src/app/.git").is_dir());
+    assert_eq!(list_repositories(&strategy, "secure"), vec!["api", "app"]);
+    assert!(list_repositories(&strategy, "flat").is_empty());
+}
+
+#[test]
+fn move_between_categories() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "nested/team/api");
+    create_repository(root, "flat/api");
+    fs::write(root.join("repos/nested/team/api/README.md"), "# API").unwrap();
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert!(matches!(
+        strategy.move_repository(
+            RepositoryLocation::new("nested", "team/api"),
+            RepositoryLocation::new("flat", "api"),
+        ),
+        Err(DiscoveryStrategyError::RepositoryExists { .. })
+    ));
+    assert!(matches!(
+        strategy.move_repository(
+            RepositoryLocation::new("nested", "team/web"),
+            RepositoryLocation::new("flat", "web"),
+        ),
+        Err(DiscoveryStrategyError::RepositoryDoesNotExist { .. })
+    ));
+
+    strategy
+        .move_repository(
+            RepositoryLocation::new("nested", "team/api"),
+            RepositoryLocation::new("flat", "team-api"),
+        )
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(root.join("repos/flat/team-api/README.md")).unwrap(),
+        "# API"
+    );
+    assert_eq!(
+        list_repositories(&strategy, "flat"),
+        vec!["api", "team-api"]
+    );
+    assert!(list_repositories(&strategy, "nested").is_empty());
+}
+
+#[test]
+fn move_with_worktrees() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "flat/app");
+    fs::create_dir_all(root.join("repos/flat/app/.git/worktrees/feature")).unwrap();
+
+    let config = get_config(root);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    assert_eq!(
+        strategy.move_repository(
+            RepositoryLocation::new("flat", "app"),
+            RepositoryLocation::new("nested", "app"),
+        ),
+        Err(DiscoveryStrategyError::RepositoryHasWorktrees {
+            repository: RepositoryLocation::new("flat", "app"),
+        })
+    );
+    assert_eq!(list_repositories(&strategy, "flat"), vec!["app"]);
+
+    // Worktrees which have been removed are no longer listed.
+    fs::remove_dir(root.join("repos/flat/app/.git/worktrees/feature")).unwrap();
+    strategy
+        .move_repository(
+            RepositoryLocation::new("flat", "app"),
+            RepositoryLocation::new("nested", "app"),
+        )
+        .unwrap();
+    assert_eq!(list_repositories(&strategy, "nested"), vec!["app"]);
+}
+
+#[test]
+fn discovered_categories() {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    create_repository(root, "flat/app");
+    create_repository(root, "personal/dotfiles");
+    create_repository(root, ".worktrees/flat/app/main");
+    fs::write(root.join("repos/notes.txt"), "notes").unwrap();
+
+    let mut config = get_config(root);
+    // A category with its own directory inside the base directory is not discovered again.
+    config.category.insert(
+        String::from("shared"),
+        Arc::new(RwLock::new(GrassCategory {
+            name: String::from("shared"),
+            path: Some(root.join("repos/shared-projects")),
+            ..Default::default()
+        })),
+    );
+    create_repository(root, "shared-projects/wiki");
+
+    assert_eq!(config.list_undeclared_categories(), vec!["personal"]);
+
+    {
+        let path_strategy = LocalPathStrategy::new(&config);
+        let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+        assert!(!strategy
+            .list_categories::<Vec<_>>()
+            .unwrap()
+            .contains(&String::from("personal")));
+        assert_eq!(
+            strategy.check_category_exists("personal"),
+            Ok(DiscoveryExists::CategoryNotFound)
+        );
+    }
+
+    config.discover_categories = true;
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+
+    let mut categories: Vec<String> = strategy.list_categories().unwrap();
+    categories.sort();
+    assert_eq!(
+        categories,
+        vec!["filtered", "flat", "nested", "personal", "secure", "shared"]
+    );
+    assert_eq!(
+        strategy.check_category_exists("personal"),
+        Ok(DiscoveryExists::Exists)
+    );
+    assert_eq!(
+        strategy.check_category_exists("shared-projects"),
+        Ok(DiscoveryExists::CategoryNotFound)
+    );
+    assert_eq!(list_repositories(&strategy, "personal"), vec!["dotfiles"]);
+    assert_eq!(
+        path_strategy.get_directory(("personal", "dotfiles")),
+        Ok(root.join("repos/personal/dotfiles"))
+    );
+}
@@ -0,0 +1,1173 @@
+//! Runs every git backend against real repositories, created in a temporary directory.
+//!
+//! The native backend is only tested when the `native-git` feature is enabled.
+#![cfg(feature = "dev")]
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Arc, RwLock},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use grass::dev::{
+    config::{GitBackend, GrassCategory, GrassCleanProfile, GrassConfig},
+    strategy::{
+        alias::LocalAliasStrategy,
+        api::LocalApiStrategy,
+        discovery::LocalDiscoveryStrategy,
+        git::{
+            BranchInfo, BranchTracking, CloneOptions, ConfiguredGitStrategy, FileChange,
+            FileChangeKind, GitOperation, GitStrategy, GitStrategyError, HeadState, InitOptions,
+            LfsStatus, PullMode, PullResult, RepositoryChangeStatus, RepositoryMetadata,
+            RepositorySize, Stash, Submodule, SubmoduleChange, Worktree,
+        },
+        path::LocalPathStrategy,
+    },
+    Api,
+};
+
+type Strategy<'a> = ConfiguredGitStrategy<'a, LocalPathStrategy<'a>>;
+
+fn git(directory: &Path, args: &[&str]) {
+    git_with_env(directory, args, &[]);
+}
+
+fn git_with_env(directory: &Path, args: &[&str], env: &[(&str, &str)]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .envs(env.iter().copied())
+        .env("GIT_AUTHOR_NAME", "grass")
+        .env("GIT_AUTHOR_EMAIL", "grass@example.com")
+        .env("GIT_COMMITTER_NAME", "grass")
+        .env("GIT_COMMITTER_EMAIL", "grass@example.com")
+        .output()
+        .expect("git should be installed");
+
+    assert!(
+        output.status.success(),
+        "git {:?} failed:\n{}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn commit_file(directory: &Path, file: &str, content: &str) {
+    fs::write(directory.join(file), content).unwrap();
+    git(directory, &["add", file]);
+    git(directory, &["commit", "-q", "-m", file]);
+}
+
+/// Set up a bare remote with a single commit, and a working copy used to push to it.
+///
+/// Everything lives in `<root>/remote`, away from the repositories managed by grass.
+fn create_remote(root: &Path) -> (String, std::path::PathBuf) {
+    let remote = root.join("remote/origin.git");
+    let upstream = root.join("remote/upstream");
+    fs::create_dir_all(&remote).unwrap();
+    git(&remote, &["init", "-q", "--bare", "-b", "main"]);
+    git(
+        root,
+        &[
+            "clone",
+            "-q",
+            remote.to_str().unwrap(),
+            upstream.to_str().unwrap(),
+        ],
+    );
+    git(&upstream, &["checkout", "-q", "-b", "main"]);
+    git(&upstream, &["config", "user.name", "grass"]);
+    git(&upstream, &["config", "user.email", "grass@example.com"]);
+    commit_file(&upstream, "README.md", "first\n");
+    git(&upstream, &["push", "-q", "-u", "origin", "main"]);
+
+    (remote.to_str().unwrap().to_string(), upstream)
+}
+
+/// Clone the remote as `work/app`, using the strategy.
+fn clone_app(strategy: &Strategy, root: &Path, remote: &str) -> std::path::PathBuf {
+    strategy
+        .clone(("work", "app"), remote, &CloneOptions::default())
+        .unwrap();
+
+    let app = root.join("repos/work/app");
+    git(&app, &["config", "user.name", "grass"]);
+    git(&app, &["config", "user.email", "grass@example.com"]);
+    app
+}
+
+/// Configure the category `work`, which contains the repositories used by the scenarios.
+fn get_config(root: &Path, backend: GitBackend) -> GrassConfig {
+    fs::create_dir_all(root.join("repos/work")).unwrap();
+
+    let work = Arc::new(RwLock::new(GrassCategory {
+        name: String::from("work"),
+        clean_profiles: vec![String::from("rust")],
+        template: Some(root.join("template")),
+        remote_template: Some(format!("{}/remote/{{repository}}.git", root.display())),
+        ..Default::default()
+    }));
+
+    GrassConfig {
+        category: HashMap::from([(String::from("work"), work)]),
+        aliases: HashMap::new(),
+        base_dir: root.join("repos"),
+        clean_profile: HashMap::from([(
+            String::from("rust"),
+            GrassCleanProfile {
+                name: String::from("rust"),
+                patterns: vec![String::from("target/")],
+            },
+        )]),
+        git_backend: backend,
+        stale_stash_days: Some(30),
+        default_branch: Some(String::from("trunk")),
+        recurse_submodules: true,
+        ..GrassConfig::try_default().unwrap()
+    }
+}
+
+fn with_backend(backend: GitBackend, scenario: fn(&Strategy, &Path)) {
+    let root = tempfile::tempdir().unwrap();
+    let config = get_config(root.path(), backend);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let strategy = ConfiguredGitStrategy::new(&config, &path_strategy);
+
+    scenario(&strategy, root.path());
+}
+
+macro_rules! test_backends {
+    ($($scenario:ident),* $(,)?) => {
+        $(
+            mod $scenario {
+                use super::*;
+
+                #[test]
+                fn cli() {
+                    with_backend(GitBackend::Cli, super::$scenario);
+                }
+
+                #[cfg(feature = "native-git")]
+                #[test]
+                fn native() {
+                    with_backend(GitBackend::Native, super::$scenario);
+                }
+            }
+        )*
+    };
+}
+
+test_backends!(
+    no_repository,
+    clone_missing_remote,
+    clone_branch,
+    clone_submodules,
+    submodules,
+    lfs,
+    up_to_date,
+    metadata,
+    uncommitted_changes,
+    unpushed_work,
+    merge_conflict,
+    clean,
+    size,
+    pull_fast_forward,
+    pull_diverged,
+    pull_without_upstream,
+    worktrees,
+    stashes,
+    branches,
+    commit_and_push,
+    push_rejected,
+    fork,
+    init,
+);
+
+fn no_repository(strategy: &Strategy, root: &Path) {
+    fs::create_dir_all(root.join("repos/work/empty")).unwrap();
+
+    assert_eq!(
+        strategy.get_changes(("work", "empty")),
+        Ok(RepositoryChangeStatus::NoRepository)
+    );
+    assert!(matches!(
+        strategy.get_change_details(("work", "empty")),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert!(matches!(
+        strategy.fetch(("work", "empty")),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+}
+
+fn clone_missing_remote(strategy: &Strategy, root: &Path) {
+    let remote = root.join("remote/missing.git");
+
+    assert!(matches!(
+        strategy.clone(
+            ("work", "app"),
+            remote.to_str().unwrap(),
+            &CloneOptions::default()
+        ),
+        Err(GitStrategyError::RemoteFetchError { .. })
+    ));
+}
+
+fn clone_branch(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    git(&upstream, &["checkout", "-q", "-b", "develop"]);
+    commit_file(&upstream, "second.txt", "second\n");
+    git(&upstream, &["push", "-q", "-u", "origin", "develop"]);
+
+    let options = CloneOptions {
+        branch: Some(String::from("develop")),
+        ..Default::default()
+    };
+    strategy.clone(("work", "app"), &remote, &options).unwrap();
+
+    assert!(root.join("repos/work/app/second.txt").exists());
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")),
+        Ok(vec![BranchTracking {
+            branch: String::from("develop"),
+            upstream: Some(String::from("origin/develop")),
+            ahead: 0,
+            behind: 0,
+        }])
+    );
+
+    let options = CloneOptions {
+        branch: Some(String::from("missing")),
+        ..Default::default()
+    };
+    assert!(matches!(
+        strategy.clone(("work", "other"), &remote, &options),
+        Err(GitStrategyError::RemoteFetchError { .. })
+    ));
+}
+
+/// Clone a remote by name, using the remote template of the category.
+fn clone_by_name(backend: GitBackend) {
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path();
+    let config = get_config(root, backend);
+    let alias_strategy = LocalAliasStrategy::new(&config);
+    let path_strategy = LocalPathStrategy::new(&config);
+    let discovery_strategy = LocalDiscoveryStrategy::new(&config, &path_strategy);
+    let git_strategy = ConfiguredGitStrategy::new(&config, &path_strategy);
+    let api = Api::from(LocalApiStrategy::new(
+        &config,
+        &alias_strategy,
+        &discovery_strategy,
+        &git_strategy,
+        &path_strategy,
+    ));
+    create_remote(root);
+
+    assert_eq!(
+        grass::dev::clone_repository(&api, ("work", "app"), "origin", &CloneOptions::default()),
+        Ok(())
+    );
+    assert!(root.join("repos/work/app/README.md").is_file());
+
+    assert!(matches!(
+        grass::dev::clone_repository(
+            &api,
+            ("personal", "app"),
+            "origin",
+            &CloneOptions::default()
+        ),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+}
+
+#[test]
+fn clone_by_name_cli() {
+    clone_by_name(GitBackend::Cli);
+}
+
+#[cfg(feature = "native-git")]
+#[test]
+fn clone_by_name_native() {
+    clone_by_name(GitBackend::Native);
+}
+
+/// Only the git command line tool can do a shallow clone from the local file system.
+#[test]
+fn clone_with_depth() {
+    with_backend(GitBackend::Cli, |strategy, root| {
+        let (remote, upstream) = create_remote(root);
+        commit_file(&upstream, "second.txt", "second\n");
+        git(&upstream, &["push", "-q"]);
+
+        // Local paths are always cloned in full, unlike file URLs.
+        let options = CloneOptions {
+            depth: Some(1),
+            ..Default::default()
+        };
+        strategy
+            .clone(("work", "app"), format!("file://{}", remote), &options)
+            .unwrap();
+
+        let app = root.join("repos/work/app");
+        assert!(app.join("second.txt").exists());
+        assert!(app.join(".git/shallow").exists());
+    });
+}
+
+/// Set up a remote, like `create_remote`, which has a `library` submodule.
+fn create_remote_with_submodule(root: &Path) -> String {
+    // Git only allows submodules from local paths when asked to.
+    std::env::set_var("GIT_CONFIG_COUNT", "1");
+    std::env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+    std::env::set_var("GIT_CONFIG_VALUE_0", "always");
+
+    let (remote, upstream) = create_remote(root);
+    let library = root.join("remote/library");
+    fs::create_dir_all(&library).unwrap();
+    git(&library, &["init", "-q", "-b", "main"]);
+    commit_file(&library, "library.txt", "library\n");
+
+    git(
+        &upstream,
+        &[
+            "submodule",
+            "add",
+            "-q",
+            library.to_str().unwrap(),
+            "library",
+        ],
+    );
+    git(&upstream, &["commit", "-q", "-m", "Add library"]);
+    git(&upstream, &["push", "-q"]);
+
+    remote
+}
+
+fn clone_submodules(strategy: &Strategy, root: &Path) {
+    let remote = create_remote_with_submodule(root);
+
+    strategy
+        .clone(("work", "plain"), &remote, &CloneOptions::default())
+        .unwrap();
+    assert!(!root.join("repos/work/plain/library/library.txt").exists());
+
+    let options = CloneOptions {
+        recurse_submodules: true,
+        ..Default::default()
+    };
+    strategy.clone(("work", "app"), &remote, &options).unwrap();
+    assert!(root.join("repos/work/app/library/library.txt").exists());
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+}
+
+fn submodules(strategy: &Strategy, root: &Path) {
+    let remote = create_remote_with_submodule(root);
+
+    strategy
+        .clone(("work", "plain"), &remote, &CloneOptions::default())
+        .unwrap();
+    assert_eq!(
+        strategy.get_changes(("work", "plain")),
+        Ok(RepositoryChangeStatus::SubmoduleChanges {
+            modified_commits: 0,
+            dirty_contents: 0,
+            uninitialized: 1,
+        })
+    );
+    assert_eq!(
+        strategy.list_submodules(("work", "plain")),
+        Ok(vec![Submodule {
+            path: PathBuf::from("library"),
+            initialized: false,
+            change: SubmoduleChange::default(),
+        }])
+    );
+
+    let options = CloneOptions {
+        recurse_submodules: true,
+        ..Default::default()
+    };
+    strategy.clone(("work", "app"), &remote, &options).unwrap();
+    let library = root.join("repos/work/app/library");
+    git(&library, &["config", "user.name", "grass"]);
+    git(&library, &["config", "user.email", "grass@example.com"]);
+
+    // Changed files inside submodules count as changes, as submodules are recursed into.
+    fs::write(library.join("notes.txt"), "notes\n").unwrap();
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 2 })
+    );
+    let changes = strategy.get_change_details(("work", "app")).unwrap();
+    assert!(changes
+        .iter()
+        .any(|change| change.path == Path::new("library/notes.txt") && change.submodule.is_none()));
+
+    // A new commit in the submodule is only a change of the submodule.
+    git(&library, &["add", "notes.txt"]);
+    git(&library, &["commit", "-q", "-m", "notes"]);
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::SubmoduleChanges {
+            modified_commits: 1,
+            dirty_contents: 0,
+            uninitialized: 0,
+        })
+    );
+    assert_eq!(
+        strategy.list_submodules(("work", "app")),
+        Ok(vec![Submodule {
+            path: PathBuf::from("library"),
+            initialized: true,
+            change: SubmoduleChange {
+                commit_changed: true,
+                ..Default::default()
+            },
+        }])
+    );
+}
+
+fn lfs(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    clone_app(strategy, root, &remote);
+    assert_eq!(strategy.get_lfs_status(("work", "app")), Ok(None));
+
+    // Committed without the LFS extension, so the pointer is stored as is.
+    // The attributes are nested, so they only apply to the assets.
+    fs::create_dir(upstream.join("assets")).unwrap();
+    commit_file(
+        &upstream,
+        "assets/.gitattributes",
+        "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+    );
+    commit_file(
+        &upstream,
+        "assets/data.bin",
+        "version https://git-lfs.github.com/spec/v1\n\
+         oid sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824\n\
+         size 5\n",
+    );
+    git(&upstream, &["push", "-q"]);
+    strategy
+        .pull(("work", "app"), PullMode::FastForwardOnly)
+        .unwrap();
+
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::MissingLfsObjects { num_files: 1 })
+    );
+    assert_eq!(
+        strategy.get_lfs_status(("work", "app")),
+        Ok(Some(LfsStatus {
+            missing_objects: vec![PathBuf::from("assets/data.bin")],
+        }))
+    );
+
+    // Downloaded content differs from the pointer, which is not a change.
+    fs::write(root.join("repos/work/app/assets/data.bin"), "hello").unwrap();
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+
+    // Same size as the pointed to content, so only the hash shows the change.
+    fs::write(root.join("repos/work/app/assets/data.bin"), "hellp").unwrap();
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 1 })
+    );
+
+    fs::write(root.join("repos/work/app/assets/data.bin"), "changed").unwrap();
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 1 })
+    );
+}
+
+fn up_to_date(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    clone_app(strategy, root, &remote);
+
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+    assert_eq!(strategy.get_change_details(("work", "app")), Ok(vec![]));
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")),
+        Ok(vec![BranchTracking {
+            branch: String::from("main"),
+            upstream: Some(String::from("origin/main")),
+            ahead: 0,
+            behind: 0,
+        }])
+    );
+}
+
+fn metadata(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    let metadata = strategy.get_metadata(("work", "app")).unwrap();
+    assert_eq!(metadata.head, HeadState::Branch(String::from("main")));
+    let commit = metadata.last_commit.unwrap();
+    assert_eq!(commit.subject, "README.md");
+    assert!(commit.age() < Duration::from_secs(60 * 60));
+
+    git(&app, &["checkout", "-q", "--detach"]);
+    assert_eq!(
+        strategy.get_metadata(("work", "app")).unwrap().head,
+        HeadState::Detached(commit.id)
+    );
+
+    fs::create_dir_all(root.join("template")).unwrap();
+    fs::create_dir_all(root.join("repos/work/new")).unwrap();
+    strategy
+        .init(("work", "new"), &InitOptions::default())
+        .unwrap();
+    assert_eq!(
+        strategy.get_metadata(("work", "new")),
+        Ok(RepositoryMetadata {
+            head: HeadState::Unborn(String::from("trunk")),
+            last_commit: None,
+        })
+    );
+
+    fs::create_dir_all(root.join("repos/work/empty")).unwrap();
+    assert!(matches!(
+        strategy.get_metadata(("work", "empty")),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+}
+
+fn uncommitted_changes(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+    commit_file(&app, "old file.txt", "old\n");
+
+    fs::write(app.join("README.md"), "changed\n").unwrap();
+    fs::write(app.join("new.txt"), "new\n").unwrap();
+    git(&app, &["add", "new.txt"]);
+    // Paths with spaces and non-ASCII characters are not quoted.
+    git(&app, &["mv", "old file.txt", "h\u{e9}llo w\u{f6}rld.txt"]);
+    fs::create_dir_all(app.join("untracked")).unwrap();
+    fs::write(app.join("untracked/file.txt"), "untracked\n").unwrap();
+
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 4 })
+    );
+
+    let change = |path: &str, staged, unstaged| FileChange {
+        path: path.into(),
+        original_path: None,
+        staged,
+        unstaged,
+        submodule: None,
+    };
+
+    let mut changes = strategy.get_change_details(("work", "app")).unwrap();
+    changes.sort();
+    assert_eq!(
+        changes,
+        vec![
+            change(
+                "README.md",
+                FileChangeKind::Unmodified,
+                FileChangeKind::Modified
+            ),
+            FileChange {
+                original_path: Some("old file.txt".into()),
+                ..change(
+                    "h\u{e9}llo w\u{f6}rld.txt",
+                    FileChangeKind::Renamed,
+                    FileChangeKind::Unmodified
+                )
+            },
+            change("new.txt", FileChangeKind::Added, FileChangeKind::Unmodified),
+            change(
+                "untracked/",
+                FileChangeKind::Untracked,
+                FileChangeKind::Untracked
+            ),
+        ]
+    );
+}
+
+fn unpushed_work(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+    commit_file(&app, "local.txt", "local\n");
+    git(&app, &["branch", "feature"]);
+
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UnpushedWork {
+            branches: vec![
+                BranchTracking {
+                    branch: String::from("feature"),
+                    upstream: None,
+                    ahead: 0,
+                    behind: 0,
+                },
+                BranchTracking {
+                    branch: String::from("main"),
+                    upstream: Some(String::from("origin/main")),
+                    ahead: 1,
+                    behind: 0,
+                },
+            ]
+        })
+    );
+}
+
+fn merge_conflict(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    commit_file(&upstream, "README.md", "upstream\n");
+    git(&upstream, &["push", "-q"]);
+    commit_file(&app, "README.md", "local\n");
+    strategy.fetch(("work", "app")).unwrap();
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&app)
+        .args(["merge", "origin/main"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::OperationInProgress {
+            operation: GitOperation::Merge,
+            num_conflicts: 1,
+        })
+    );
+
+    let changes = strategy.get_change_details(("work", "app")).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].path, Path::new("README.md"));
+    assert_eq!(changes[0].staged, FileChangeKind::Unmerged);
+
+    git(&app, &["merge", "--abort"]);
+    assert!(matches!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UnpushedWork { .. })
+    ));
+}
+
+fn clean(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+    commit_file(&app, ".gitignore", "target/\n*.log\n");
+
+    fs::create_dir_all(app.join("target/debug")).unwrap();
+    fs::write(app.join("target/debug/app"), "binary").unwrap();
+    fs::write(app.join("build.log"), "log").unwrap();
+
+    let report = strategy.clean(("work", "app")).unwrap();
+
+    assert_eq!(report.removed_files, vec![Path::new("target/debug/app")]);
+    assert_eq!(report.freed_bytes, 6);
+    assert!(!app.join("target").exists());
+    assert!(app.join("build.log").exists());
+}
+
+fn size(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+    commit_file(&app, ".gitignore", "target/\n*.log\n");
+
+    fs::create_dir_all(app.join("target/debug")).unwrap();
+    fs::write(app.join("target/debug/app"), "binary").unwrap();
+    fs::write(app.join("build.log"), "log").unwrap();
+
+    let size = strategy.get_size(("work", "app")).unwrap();
+
+    // Only files matching the clean profile are reclaimable, and nothing is removed.
+    assert_eq!(size.reclaimable_bytes, 6);
+    assert!(app.join("target/debug/app").exists());
+    assert!(size.git_bytes > 0);
+    // README.md, .gitignore, target/debug/app and build.log
+    assert_eq!(size.total_bytes, size.git_bytes + 6 + 14 + 6 + 3);
+
+    fs::create_dir_all(root.join("repos/work/empty")).unwrap();
+    fs::write(root.join("repos/work/empty/notes.txt"), "notes").unwrap();
+    assert_eq!(
+        strategy.get_size(("work", "empty")),
+        Ok(RepositorySize {
+            total_bytes: 5,
+            reclaimable_bytes: 0,
+            git_bytes: 0,
+        })
+    );
+}
+
+fn pull_fast_forward(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    commit_file(&upstream, "second.txt", "second\n");
+    commit_file(&upstream, "third.txt", "third\n");
+    git(&upstream, &["push", "-q"]);
+
+    let result = strategy
+        .pull(("work", "app"), PullMode::FastForwardOnly)
+        .unwrap();
+    assert!(matches!(result, PullResult::Updated { num_commits: 2, .. }));
+    assert!(app.join("third.txt").exists());
+
+    assert_eq!(
+        strategy.pull(("work", "app"), PullMode::FastForwardOnly),
+        Ok(PullResult::UpToDate)
+    );
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+}
+
+fn pull_diverged(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    commit_file(&upstream, "upstream.txt", "upstream\n");
+    git(&upstream, &["push", "-q"]);
+    commit_file(&app, "local.txt", "local\n");
+
+    assert!(matches!(
+        strategy.pull(("work", "app"), PullMode::FastForwardOnly),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert!(!app.join("upstream.txt").exists());
+
+    assert!(matches!(
+        strategy.pull(("work", "app"), PullMode::Rebase),
+        Ok(PullResult::Updated { num_commits: 1, .. })
+    ));
+    assert!(app.join("upstream.txt").exists());
+    assert!(app.join("local.txt").exists());
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")),
+        Ok(vec![BranchTracking {
+            branch: String::from("main"),
+            upstream: Some(String::from("origin/main")),
+            ahead: 1,
+            behind: 0,
+        }])
+    );
+
+    commit_file(&upstream, "merged.txt", "merged\n");
+    git(&upstream, &["push", "-q"]);
+
+    assert!(matches!(
+        strategy.pull(("work", "app"), PullMode::Merge),
+        Ok(PullResult::Updated { num_commits: 1, .. })
+    ));
+    assert!(app.join("merged.txt").exists());
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")),
+        Ok(vec![BranchTracking {
+            branch: String::from("main"),
+            upstream: Some(String::from("origin/main")),
+            ahead: 2,
+            behind: 0,
+        }])
+    );
+    assert_eq!(strategy.get_change_details(("work", "app")), Ok(vec![]));
+}
+
+fn pull_without_upstream(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+    git(&app, &["checkout", "-q", "-b", "feature"]);
+
+    assert_eq!(
+        strategy.pull(("work", "app"), PullMode::FastForwardOnly),
+        Ok(PullResult::NoUpstream)
+    );
+}
+
+fn worktrees(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    git(&upstream, &["checkout", "-q", "-b", "remote-only"]);
+    commit_file(&upstream, "remote.txt", "remote\n");
+    git(&upstream, &["push", "-q", "-u", "origin", "remote-only"]);
+    let app = clone_app(strategy, root, &remote);
+
+    let worktree_dir = root.join("repos/.worktrees/work/app");
+    let worktree = |path: &Path, branch: &str, is_main| Worktree {
+        path: path.to_path_buf(),
+        branch: Some(String::from(branch)),
+        is_main,
+    };
+
+    strategy
+        .add_worktree(("work", "app", "feature/login"))
+        .unwrap();
+    strategy
+        .add_worktree(("work", "app", "remote-only"))
+        .unwrap();
+
+    assert_eq!(
+        strategy.list_worktrees(("work", "app")),
+        Ok(vec![
+            worktree(&app, "main", true),
+            worktree(&worktree_dir.join("feature/login"), "feature/login", false),
+            worktree(&worktree_dir.join("remote-only"), "remote-only", false),
+        ])
+    );
+    assert!(worktree_dir.join("remote-only/remote.txt").exists());
+    assert!(strategy
+        .get_branch_tracking(("work", "app"))
+        .unwrap()
+        .contains(&BranchTracking {
+            branch: String::from("remote-only"),
+            upstream: Some(String::from("origin/remote-only")),
+            ahead: 0,
+            behind: 0,
+        }));
+
+    assert!(matches!(
+        strategy.add_worktree(("work", "app", "feature/login")),
+        Err(GitStrategyError::RepositryExists { .. })
+    ));
+    assert!(matches!(
+        strategy.add_worktree(("work", "app", "main")),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+
+    fs::write(worktree_dir.join("feature/login/draft.txt"), "draft\n").unwrap();
+    assert!(matches!(
+        strategy.remove_worktree(("work", "app", "feature/login"), false),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert_eq!(
+        strategy.remove_worktree(("work", "app", "feature/login"), true),
+        Ok(())
+    );
+    assert!(!worktree_dir.join("feature/login").exists());
+    assert_eq!(
+        strategy.remove_worktree(("work", "app", "remote-only"), false),
+        Ok(())
+    );
+
+    assert!(matches!(
+        strategy.remove_worktree(("work", "app", "main"), true),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert!(matches!(
+        strategy.remove_worktree(("work", "app", "missing"), false),
+        Err(GitStrategyError::RepositoryNotFound { .. })
+    ));
+
+    assert_eq!(
+        strategy.list_worktrees(("work", "app")),
+        Ok(vec![worktree(&app, "main", true)])
+    );
+    // The branches are kept.
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")).unwrap().len(),
+        3
+    );
+}
+
+fn stashes(strategy: &Strategy, root: &Path) {
+    let (remote, _) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    assert_eq!(strategy.list_stashes(("work", "app")), Ok(vec![]));
+
+    fs::write(app.join("README.md"), "recent\n").unwrap();
+    git(&app, &["stash", "push", "-q", "-m", "Recent work"]);
+
+    let stashes = strategy.list_stashes(("work", "app")).unwrap();
+    assert_eq!(stashes.len(), 1);
+    assert_eq!(stashes[0].index, 0);
+    assert_eq!(stashes[0].message, "On main: Recent work");
+    assert!(!stashes[0].is_stale(Duration::from_secs(60 * 60)));
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+
+    fs::write(app.join("README.md"), "old\n").unwrap();
+    git_with_env(
+        &app,
+        &["stash", "push", "-q", "-m", "Old work"],
+        &[("GIT_COMMITTER_DATE", "1000000000 +0000")],
+    );
+
+    assert_eq!(
+        strategy.list_stashes(("work", "app")).unwrap()[0],
+        Stash {
+            index: 0,
+            message: String::from("On main: Old work"),
+            created: UNIX_EPOCH + Duration::from_secs(1_000_000_000),
+        }
+    );
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::StaleStashes { num_stashes: 1 })
+    );
+}
+
+fn branches(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    git(&upstream, &["push", "-q", "origin", "main:removed"]);
+    let app = clone_app(strategy, root, &remote);
+
+    git(&app, &["branch", "merged"]);
+    git(&app, &["branch", "--track", "removed", "origin/removed"]);
+    git(&upstream, &["push", "-q", "origin", "--delete", "removed"]);
+    git(&app, &["fetch", "-q", "--prune"]);
+
+    git(&app, &["checkout", "-q", "-b", "old"]);
+    fs::write(app.join("old.txt"), "old\n").unwrap();
+    git(&app, &["add", "old.txt"]);
+    git_with_env(
+        &app,
+        &["commit", "-q", "-m", "old"],
+        &[("GIT_COMMITTER_DATE", "1000000000 +0000")],
+    );
+    git(&app, &["checkout", "-q", "main"]);
+
+    let branches = strategy.list_branches(("work", "app")).unwrap();
+    let names: Vec<_> = branches
+        .iter()
+        .map(|branch| branch.branch.as_str())
+        .collect();
+    assert_eq!(names, vec!["main", "merged", "old", "removed"]);
+
+    let [main, merged, old, removed] = branches.as_slice() else {
+        unreachable!()
+    };
+    assert!(main.is_default && main.is_current && !main.is_merged);
+    assert_eq!(main.upstream, Some(String::from("origin/main")));
+    assert!(merged.is_merged && !merged.is_current && merged.upstream.is_none());
+    assert!(!old.is_merged && !old.upstream_gone);
+    assert!(old.is_stale(Duration::from_secs(90 * 24 * 60 * 60)));
+    assert_eq!(
+        removed,
+        &BranchInfo {
+            upstream: Some(String::from("origin/removed")),
+            upstream_gone: true,
+            is_merged: true,
+            ..removed.clone()
+        }
+    );
+
+    assert!(matches!(
+        strategy.delete_branch(("work", "app"), "old", false),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert!(matches!(
+        strategy.delete_branch(("work", "app"), "main", true),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert_eq!(
+        strategy.delete_branch(("work", "app"), "merged", false),
+        Ok(())
+    );
+    assert_eq!(strategy.delete_branch(("work", "app"), "old", true), Ok(()));
+
+    let branches = strategy.list_branches(("work", "app")).unwrap();
+    let names: Vec<_> = branches
+        .iter()
+        .map(|branch| branch.branch.as_str())
+        .collect();
+    assert_eq!(names, vec!["main", "removed"]);
+}
+
+fn commit_and_push(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    assert!(matches!(
+        strategy.commit_all(("work", "app"), "Nothing"),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+
+    fs::write(app.join("README.md"), "changed\n").unwrap();
+    fs::write(app.join("notes.md"), "new\n").unwrap();
+    assert_eq!(strategy.commit_all(("work", "app"), "Update notes"), Ok(()));
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UnpushedWork {
+            branches: vec![BranchTracking {
+                branch: String::from("main"),
+                upstream: Some(String::from("origin/main")),
+                ahead: 1,
+                behind: 0,
+            }]
+        })
+    );
+
+    assert_eq!(strategy.push(("work", "app")), Ok(()));
+    assert_eq!(
+        strategy.get_changes(("work", "app")),
+        Ok(RepositoryChangeStatus::UpToDate)
+    );
+    git(&upstream, &["pull", "-q"]);
+    assert_eq!(
+        fs::read_to_string(upstream.join("notes.md")).unwrap(),
+        "new\n"
+    );
+
+    // A new branch is pushed to 'origin', and tracks the pushed branch.
+    git(&app, &["checkout", "-q", "-b", "feature"]);
+    fs::write(app.join("feature.md"), "feature\n").unwrap();
+    assert_eq!(strategy.commit_all(("work", "app"), "Add feature"), Ok(()));
+    assert_eq!(strategy.push(("work", "app")), Ok(()));
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")).unwrap()[0],
+        BranchTracking {
+            branch: String::from("feature"),
+            upstream: Some(String::from("origin/feature")),
+            ahead: 0,
+            behind: 0,
+        }
+    );
+}
+
+fn push_rejected(strategy: &Strategy, root: &Path) {
+    let (remote, upstream) = create_remote(root);
+    let app = clone_app(strategy, root, &remote);
+
+    commit_file(&upstream, "upstream.md", "upstream\n");
+    git(&upstream, &["push", "-q"]);
+    commit_file(&app, "local.md", "local\n");
+
+    assert!(matches!(
+        strategy.push(("work", "app")),
+        Err(GitStrategyError::RemoteFetchError { .. })
+    ));
+}
+
+/// Get the URL of a remote, using git directly.
+fn remote_url(directory: &Path, remote: &str) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(["remote", "get-url", remote])
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn fork(strategy: &Strategy, root: &Path) {
+    let (source, _) = create_remote(root);
+    let target = root.join("remote/app.git");
+    fs::create_dir_all(&target).unwrap();
+    git(&target, &["init", "-q", "--bare", "-b", "main"]);
+
+    strategy
+        .clone(("work", "app"), &source, &CloneOptions::default())
+        .unwrap();
+    let app = root.join("repos/work/app");
+    assert_eq!(
+        strategy.rename_remote(("work", "app"), "origin", "upstream"),
+        Ok(())
+    );
+    assert_eq!(
+        strategy.add_remote(("work", "app"), "origin", target.to_str().unwrap()),
+        Ok(())
+    );
+    assert!(matches!(
+        strategy.add_remote(("work", "app"), "origin", target.to_str().unwrap()),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+    assert_eq!(remote_url(&app, "upstream"), source);
+    assert_eq!(remote_url(&app, "origin"), target.to_str().unwrap());
+
+    // Renaming moves the tracking branch to the new remote name.
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")).unwrap()[0].upstream,
+        Some(String::from("upstream/main"))
+    );
+
+    assert_eq!(strategy.push_to_remote(("work", "app"), "origin"), Ok(()));
+    assert_eq!(
+        strategy.get_branch_tracking(("work", "app")).unwrap()[0],
+        BranchTracking {
+            branch: String::from("main"),
+            upstream: Some(String::from("origin/main")),
+            ahead: 0,
+            behind: 0,
+        }
+    );
+
+    assert!(matches!(
+        strategy.rename_remote(("work", "app"), "missing", "other"),
+        Err(GitStrategyError::RepositoryError { .. })
+    ));
+}
+
+fn init(strategy: &Strategy, root: &Path) {
+    let template = root.join("template");
+    fs::create_dir_all(template.join("hooks")).unwrap();
+    fs::write(template.join(".gitignore"), "target/\n").unwrap();
+    fs::write(template.join("README.md"), "# New\n").unwrap();
+    fs::write(template.join("hooks/pre-commit"), "#!/bin/sh\n").unwrap();
+
+    let new = root.join("repos/work/new");
+    fs::create_dir_all(&new).unwrap();
+    let options = InitOptions {
+        branch: None,
+        add_remote: true,
+    };
+    assert_eq!(strategy.init(("work", "new"), &options), Ok(()));
+
+    assert_eq!(
+        fs::read_to_string(new.join(".git/HEAD")).unwrap(),
+        "ref: refs/heads/trunk\n"
+    );
+    assert_eq!(
+        fs::read_to_string(new.join("README.md")).unwrap(),
+        "# New\n"
+    );
+    assert!(new.join(".git/hooks/pre-commit").is_file());
+    assert!(!new.join("hooks").exists());
+    assert_eq!(
+        strategy.get_changes(("work", "new")),
+        Ok(RepositoryChangeStatus::UncommittedChanges { num_changes: 2 })
+    );
+    let remote = format!("{}/remote/new.git", root.display());
+    assert!(fs::read_to_string(new.join(".git/config"))
+        .unwrap()
+        .contains(&remote));
+
+    assert!(matches!(
+        strategy.init(("work", "new"), &options),
+        Err(GitStrategyError::RepositryExists { .. })
+    ));
+    assert!(matches!(
+        strategy.init(("work", "missing"), &options),
+        Err(GitStrategyError::RepositoryNotFound { .. })
+    ));
+
+    let other = root.join("repos/work/other");
+    fs::create_dir_all(&other).unwrap();
+    let options = InitOptions {
+        branch: Some(String::from("main")),
+        add_remote: false,
+    };
+    assert_eq!(strategy.init(("work", "other"), &options), Ok(()));
+    assert_eq!(
+        fs::read_to_string(other.join(".git/HEAD")).unwrap(),
+        "ref: refs/heads/main\n"
+    );
+    assert!(!fs::read_to_string(other.join(".git/config"))
+        .unwrap()
+        .contains("[remote"));
+}